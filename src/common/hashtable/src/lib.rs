@@ -26,6 +26,7 @@ pub use hash_table_iter::HashTableIter;
 pub use hash_table_iter::HashTableIteratorKind;
 pub use hash_table_iter::TwoLevelHashTableIter;
 pub use hash_table_key::HashTableKeyable;
+pub use two_level_hash_table::two_level_bucket;
 pub use two_level_hash_table::HashTableKind;
 pub use two_level_hash_table::TwoLevelHashTable;
 
@@ -27,6 +27,12 @@ static BITS_FOR_BUCKET: u8 = 8;
 static NUM_BUCKETS: usize = 1 << BITS_FOR_BUCKET;
 static MAX_BUCKECT: usize = NUM_BUCKETS - 1;
 
+/// The bucket of the two-level hash table that the key of `hash_value` goes to.
+#[inline(always)]
+pub fn two_level_bucket(hash_value: u64) -> usize {
+    ((hash_value >> (64 - BITS_FOR_BUCKET)) & (MAX_BUCKECT as u64)) as usize
+}
+
 pub enum HashTableKind<
     Key: HashTableKeyable,
     Entity: HashTableEntity<Key>,
@@ -220,6 +226,6 @@ impl<
 
     #[inline(always)]
     fn get_bucket_from_hash(&self, hash_value: &u64) -> usize {
-        ((hash_value >> (64 - BITS_FOR_BUCKET)) & (MAX_BUCKECT as u64)) as usize
+        two_level_bucket(*hash_value)
     }
 }
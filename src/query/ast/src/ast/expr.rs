@@ -22,6 +22,7 @@ use common_exception::Result;
 use crate::ast::write_comma_separated_list;
 use crate::ast::write_period_separated_list;
 use crate::ast::Identifier;
+use crate::ast::OrderByExpr;
 use crate::ast::Query;
 use crate::parser::token::Token;
 
@@ -127,6 +128,9 @@ pub enum Expr<'a> {
     Literal { span: &'a [Token<'a>], lit: Literal },
     /// `COUNT(*)` expression
     CountAll { span: &'a [Token<'a>] },
+    /// A positional parameter of prepared statement, such as `$1`, or `?` whose index is 0
+    /// until it is numbered by the binder
+    Placeholder { span: &'a [Token<'a>], index: usize },
     /// `(foo, bar)`
     Tuple {
         span: &'a [Token<'a>],
@@ -140,6 +144,8 @@ pub enum Expr<'a> {
         name: Identifier<'a>,
         args: Vec<Expr<'a>>,
         params: Vec<Literal>,
+        /// The window of a window function call, like `row_number() OVER (ORDER BY a)`
+        window: Option<Window<'a>>,
     },
     /// `CASE ... WHEN ... ELSE ...` expression
     Case {
@@ -217,6 +223,28 @@ pub enum Literal {
     Null,
 }
 
+/// The window following `OVER` of a window function call
+#[derive(Debug, Clone, PartialEq)]
+pub enum Window<'a> {
+    /// `OVER w`, refers to a window defined in `WINDOW` clause
+    WindowReference(Identifier<'a>),
+    /// `OVER (PARTITION BY ... ORDER BY ...)`
+    WindowSpec(WindowSpec<'a>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct WindowSpec<'a> {
+    pub partition_by: Vec<Expr<'a>>,
+    pub order_by: Vec<OrderByExpr<'a>>,
+}
+
+/// A named window in `WINDOW` clause, like `WINDOW w AS (PARTITION BY a)`
+#[derive(Debug, Clone, PartialEq)]
+pub struct WindowDefinition<'a> {
+    pub name: Identifier<'a>,
+    pub spec: WindowSpec<'a>,
+}
+
 /// The display style for a map access expression
 #[derive(Debug, Clone, PartialEq)]
 pub enum MapAccessor<'a> {
@@ -350,6 +378,7 @@ impl<'a> Expr<'a> {
             | Expr::Trim { span, .. }
             | Expr::Literal { span, .. }
             | Expr::CountAll { span }
+            | Expr::Placeholder { span, .. }
             | Expr::Tuple { span, .. }
             | Expr::FunctionCall { span, .. }
             | Expr::Case { span, .. }
@@ -579,6 +608,38 @@ impl Display for TrimWhere {
     }
 }
 
+impl<'a> Display for Window<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Window::WindowReference(name) => write!(f, "{name}"),
+            Window::WindowSpec(spec) => write!(f, "({spec})"),
+        }
+    }
+}
+
+impl<'a> Display for WindowSpec<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if !self.partition_by.is_empty() {
+            write!(f, "PARTITION BY ")?;
+            write_comma_separated_list(f, &self.partition_by)?;
+        }
+        if !self.order_by.is_empty() {
+            if !self.partition_by.is_empty() {
+                write!(f, " ")?;
+            }
+            write!(f, "ORDER BY ")?;
+            write_comma_separated_list(f, &self.order_by)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> Display for WindowDefinition<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} AS ({})", self.name, self.spec)
+    }
+}
+
 impl Display for Literal {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -749,6 +810,9 @@ impl<'a> Display for Expr<'a> {
             Expr::CountAll { .. } => {
                 write!(f, "COUNT(*)")?;
             }
+            Expr::Placeholder { index, .. } => {
+                write!(f, "${index}")?;
+            }
             Expr::Tuple { exprs, .. } => {
                 write!(f, "(")?;
                 write_comma_separated_list(f, exprs)?;
@@ -762,6 +826,7 @@ impl<'a> Display for Expr<'a> {
                 name,
                 args,
                 params,
+                window,
                 ..
             } => {
                 write!(f, "{name}")?;
@@ -776,6 +841,9 @@ impl<'a> Display for Expr<'a> {
                 }
                 write_comma_separated_list(f, args)?;
                 write!(f, ")")?;
+                if let Some(window) = window {
+                    write!(f, " OVER {window}")?;
+                }
             }
             Expr::Case {
                 operand,
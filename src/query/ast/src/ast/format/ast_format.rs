@@ -438,6 +438,13 @@ impl<'ast> Visitor<'ast> for AstFormatVisitor {
         self.children.push(node);
     }
 
+    fn visit_placeholder(&mut self, _span: &'ast [Token<'ast>], index: usize) {
+        let name = format!("Placeholder ${}", index);
+        let format_ctx = AstFormatContext::new(name);
+        let node = FormatTreeNode::new(format_ctx);
+        self.children.push(node);
+    }
+
     fn visit_tuple(&mut self, _span: &'ast [Token<'ast>], elements: &'ast [Expr<'ast>]) {
         let mut children = Vec::with_capacity(elements.len());
         for element in elements.iter() {
@@ -457,12 +464,18 @@ impl<'ast> Visitor<'ast> for AstFormatVisitor {
         name: &'ast Identifier<'ast>,
         args: &'ast [Expr<'ast>],
         _params: &'ast [Literal],
+        window: &'ast Option<Window<'ast>>,
     ) {
-        let mut children = Vec::with_capacity(args.len());
+        let mut children = Vec::with_capacity(args.len() + 1);
         for arg in args.iter() {
             self.visit_expr(arg);
             children.push(self.children.pop().unwrap());
         }
+        if let Some(window) = window {
+            let window_name = format!("Window {window}");
+            let window_format_ctx = AstFormatContext::new(window_name);
+            children.push(FormatTreeNode::new(window_format_ctx));
+        }
         let node_name = if distinct {
             format!("Function {name}Distinct")
         } else {
@@ -698,6 +711,11 @@ impl<'ast> Visitor<'ast> for AstFormatVisitor {
             let offset_node = FormatTreeNode::with_children(offset_format_ctx, vec![offset_child]);
             children.push(offset_node);
         }
+        if query.with_ties {
+            let with_ties_format_ctx = AstFormatContext::new("WithTies".to_string());
+            let with_ties_node = FormatTreeNode::new(with_ties_format_ctx);
+            children.push(with_ties_node);
+        }
         if let Some(format) = &query.format {
             let format_format_ctx = AstFormatContext::new(format!("FormatElement {}", format));
             let format_node = FormatTreeNode::new(format_format_ctx);
@@ -721,6 +739,7 @@ impl<'ast> Visitor<'ast> for AstFormatVisitor {
             ExplainKind::Pipeline => "Pipeline",
             ExplainKind::Fragments => "Fragments",
             ExplainKind::Raw => "Raw",
+            ExplainKind::Lineage => "Lineage",
             ExplainKind::Plan => "Plan",
         });
         let format_ctx = AstFormatContext::with_children(name, 1);
@@ -869,6 +888,13 @@ impl<'ast> Visitor<'ast> for AstFormatVisitor {
         self.children.push(node);
     }
 
+    fn visit_show_warnings(&mut self) {
+        let name = "ShowWarnings".to_string();
+        let format_ctx = AstFormatContext::new(name);
+        let node = FormatTreeNode::new(format_ctx);
+        self.children.push(node);
+    }
+
     fn visit_show_functions(&mut self, limit: &'ast Option<ShowLimit<'ast>>) {
         let mut children = Vec::new();
         if let Some(limit) = limit {
@@ -2048,6 +2074,20 @@ impl<'ast> Visitor<'ast> for AstFormatVisitor {
             let having_node = FormatTreeNode::with_children(having_format_ctx, vec![having_child]);
             children.push(having_node);
         }
+        if !stmt.window_list.is_empty() {
+            let mut window_children = Vec::with_capacity(stmt.window_list.len());
+            for window in stmt.window_list.iter() {
+                let window_name = format!("WindowDefinition {window}");
+                let window_format_ctx = AstFormatContext::new(window_name);
+                window_children.push(FormatTreeNode::new(window_format_ctx));
+            }
+            let window_list_name = "WindowList".to_string();
+            let window_list_format_ctx =
+                AstFormatContext::with_children(window_list_name, window_children.len());
+            let window_list_node =
+                FormatTreeNode::with_children(window_list_format_ctx, window_children);
+            children.push(window_list_node);
+        }
 
         let name = "SelectQuery".to_string();
         let format_ctx = AstFormatContext::with_children(name, children.len());
@@ -2069,7 +2109,7 @@ impl<'ast> Visitor<'ast> for AstFormatVisitor {
                 let node = FormatTreeNode::with_children(format_ctx, vec![child]);
                 self.children.push(node);
             }
-            SelectTarget::QualifiedName(_) => {
+            SelectTarget::QualifiedName { .. } => {
                 let name = format!("Target {}", target);
                 let format_ctx = AstFormatContext::new(name);
                 let node = FormatTreeNode::new(format_ctx);
@@ -2133,6 +2173,35 @@ impl<'ast> Visitor<'ast> for AstFormatVisitor {
                 let node = FormatTreeNode::with_children(format_ctx, vec![child]);
                 self.children.push(node);
             }
+            TableReference::Values {
+                span: _,
+                values,
+                alias,
+            } => {
+                let mut children = Vec::with_capacity(values.len());
+                for row in values.iter() {
+                    let mut row_children = Vec::with_capacity(row.len());
+                    for expr in row.iter() {
+                        self.visit_expr(expr);
+                        row_children.push(self.children.pop().unwrap());
+                    }
+                    let row_format_ctx =
+                        AstFormatContext::with_children("Row".to_string(), row_children.len());
+                    children.push(FormatTreeNode::with_children(row_format_ctx, row_children));
+                }
+                let name = "Values".to_string();
+                let format_ctx = if let Some(alias) = alias {
+                    AstFormatContext::with_children_alias(
+                        name,
+                        children.len(),
+                        Some(format!("{}", alias)),
+                    )
+                } else {
+                    AstFormatContext::with_children(name, children.len())
+                };
+                let node = FormatTreeNode::with_children(format_ctx, children);
+                self.children.push(node);
+            }
             TableReference::TableFunction {
                 span: _,
                 name,
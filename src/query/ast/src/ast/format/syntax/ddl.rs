@@ -204,6 +204,18 @@ pub(crate) fn pretty_create_view(stmt: CreateViewStmt) -> RcDoc {
                 })
                 .append(RcDoc::text(stmt.view.to_string())),
         )
+        .append(if !stmt.columns.is_empty() {
+            RcDoc::space().append(parenthenized(
+                interweave_comma(
+                    stmt.columns
+                        .into_iter()
+                        .map(|column| RcDoc::text(column.to_string())),
+                )
+                .group(),
+            ))
+        } else {
+            RcDoc::nil()
+        })
         .append(
             RcDoc::line().append(RcDoc::text("AS")).append(
                 RcDoc::line()
@@ -229,6 +241,18 @@ pub(crate) fn pretty_alter_view(stmt: AlterViewStmt) -> RcDoc {
                 })
                 .append(RcDoc::text(stmt.view.to_string())),
         )
+        .append(if !stmt.columns.is_empty() {
+            RcDoc::space().append(parenthenized(
+                interweave_comma(
+                    stmt.columns
+                        .into_iter()
+                        .map(|column| RcDoc::text(column.to_string())),
+                )
+                .group(),
+            ))
+        } else {
+            RcDoc::nil()
+        })
         .append(
             RcDoc::line().append(RcDoc::text("AS")).append(
                 RcDoc::line()
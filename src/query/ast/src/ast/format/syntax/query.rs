@@ -30,15 +30,20 @@ use crate::ast::SetExpr;
 use crate::ast::SetOperator;
 use crate::ast::TableReference;
 use crate::ast::TimeTravelPoint;
+use crate::ast::WindowDefinition;
 use crate::ast::With;
 use crate::ast::CTE;
 
 pub(crate) fn pretty_query(query: Query) -> RcDoc {
+    let limit_offset = if query.with_ties {
+        pretty_offset(query.offset).append(pretty_fetch_with_ties(query.limit))
+    } else {
+        pretty_limit(query.limit).append(pretty_offset(query.offset))
+    };
     pretty_with(query.with)
         .append(pretty_body(query.body))
         .append(pretty_order_by(query.order_by))
-        .append(pretty_limit(query.limit))
-        .append(pretty_offset(query.offset))
+        .append(limit_offset)
         .append(pretty_format(query.format))
         .group()
 }
@@ -76,11 +81,13 @@ fn pretty_body(body: SetExpr) -> RcDoc {
         } else {
             RcDoc::text("SELECT")
         }
+        .append(pretty_distinct_on(select_stmt.distinct_on))
         .append(pretty_select_list(select_stmt.select_list))
         .append(pretty_from(select_stmt.from))
         .append(pretty_selection(select_stmt.selection))
         .append(pretty_group_by(select_stmt.group_by))
-        .append(pretty_having(select_stmt.having)),
+        .append(pretty_having(select_stmt.having))
+        .append(pretty_window_list(select_stmt.window_list)),
         SetExpr::Query(query) => parenthenized(pretty_query(*query)),
         SetExpr::SetOperation(set_operation) => pretty_body(*set_operation.left)
             .append(
@@ -121,11 +128,23 @@ fn pretty_select_list(select_list: Vec<SelectTarget>) -> RcDoc {
                         RcDoc::nil()
                     })
                 }
-                SelectTarget::QualifiedName(object_name) => inline_dot(
-                    object_name
+                SelectTarget::QualifiedName { qualified, exclude } => inline_dot(
+                    qualified
                         .into_iter()
                         .map(|indirection| RcDoc::text(indirection.to_string())),
                 )
+                .append(if let Some(exclude) = exclude {
+                    RcDoc::space()
+                        .append(RcDoc::text("EXCLUDE"))
+                        .append(RcDoc::space())
+                        .append(parenthenized(interweave_comma(
+                            exclude
+                                .into_iter()
+                                .map(|ident| RcDoc::text(ident.to_string())),
+                        )))
+                } else {
+                    RcDoc::nil()
+                })
                 .group(),
             }
         }))
@@ -160,6 +179,16 @@ fn pretty_selection(selection: Option<Expr>) -> RcDoc {
     }
 }
 
+fn pretty_distinct_on(distinct_on: Vec<Expr>) -> RcDoc {
+    if !distinct_on.is_empty() {
+        RcDoc::text(" ON ").append(parenthenized(
+            interweave_comma(distinct_on.into_iter().map(pretty_expr)).group(),
+        ))
+    } else {
+        RcDoc::nil()
+    }
+}
+
 fn pretty_group_by(group_by: Vec<Expr>) -> RcDoc {
     if !group_by.is_empty() {
         RcDoc::line()
@@ -193,6 +222,24 @@ fn pretty_having(having: Option<Expr>) -> RcDoc {
     }
 }
 
+fn pretty_window_list(window_list: Vec<WindowDefinition>) -> RcDoc {
+    if !window_list.is_empty() {
+        RcDoc::line()
+            .append(RcDoc::text("WINDOW").append(RcDoc::line().nest(NEST_FACTOR)))
+            .append(
+                interweave_comma(
+                    window_list
+                        .into_iter()
+                        .map(|window| RcDoc::text(window.to_string())),
+                )
+                .nest(NEST_FACTOR)
+                .group(),
+            )
+    } else {
+        RcDoc::nil()
+    }
+}
+
 pub(crate) fn pretty_table(table: TableReference) -> RcDoc {
     match table {
         TableReference::Table {
@@ -234,6 +281,24 @@ pub(crate) fn pretty_table(table: TableReference) -> RcDoc {
         } else {
             RcDoc::nil()
         }),
+        TableReference::Values {
+            span: _,
+            values,
+            alias,
+        } => parenthenized(
+            RcDoc::text("VALUES")
+                .append(RcDoc::space())
+                .append(inline_comma(values.into_iter().map(|row| {
+                    RcDoc::text("(")
+                        .append(inline_comma(row.into_iter().map(pretty_expr)))
+                        .append(RcDoc::text(")"))
+                }))),
+        )
+        .append(if let Some(alias) = alias {
+            RcDoc::text(format!(" AS {alias}"))
+        } else {
+            RcDoc::nil()
+        }),
         TableReference::TableFunction {
             span: _,
             name,
@@ -339,6 +404,14 @@ fn pretty_offset(offset: Option<Expr>) -> RcDoc {
     }
 }
 
+fn pretty_fetch_with_ties(limit: Vec<Expr>) -> RcDoc {
+    RcDoc::line()
+        .append(RcDoc::text("FETCH FIRST").append(RcDoc::space().nest(NEST_FACTOR)))
+        .append(interweave_comma(limit.into_iter().map(pretty_expr)).group())
+        .append(RcDoc::space())
+        .append(RcDoc::text("ROWS WITH TIES"))
+}
+
 fn pretty_format<'a>(format: Option<String>) -> RcDoc<'a> {
     if let Some(format) = format {
         RcDoc::line()
@@ -19,6 +19,7 @@ use crate::ast::write_comma_separated_list;
 use crate::ast::write_period_separated_list;
 use crate::ast::Expr;
 use crate::ast::Identifier;
+use crate::ast::WindowDefinition;
 use crate::parser::token::Token;
 
 /// Root node of a query tree
@@ -39,6 +40,8 @@ pub struct Query<'a> {
     pub offset: Option<Expr<'a>>,
     // FORMAT <format>
     pub format: Option<String>,
+    // `FETCH FIRST n ROWS WITH TIES`, the rows tying with the last row are also returned
+    pub with_ties: bool,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -69,6 +72,8 @@ pub struct SetOperation<'a> {
 pub struct SelectStmt<'a> {
     pub span: &'a [Token<'a>],
     pub distinct: bool,
+    // `DISTINCT ON (...)`, which is parsed but not supported by the binder yet
+    pub distinct_on: Vec<Expr<'a>>,
     // Result set of current subquery
     pub select_list: Vec<SelectTarget<'a>>,
     // `FROM` clause, a list of table references.
@@ -81,6 +86,8 @@ pub struct SelectStmt<'a> {
     pub group_by: Vec<Expr<'a>>,
     // `HAVING` clause
     pub having: Option<Expr<'a>>,
+    // `WINDOW` clause, the named windows can be referred by window functions
+    pub window_list: Vec<WindowDefinition<'a>>,
 }
 
 /// A relational set expression, like `SELECT ... FROM ... {UNION|EXCEPT|INTERSECT} SELECT ... FROM ...`
@@ -120,7 +127,11 @@ pub enum SelectTarget<'a> {
 
     // Qualified name, e.g. `SELECT t.a, t.* FROM t`.
     // For simplicity, wildcard is involved.
-    QualifiedName(QualifiedName<'a>),
+    // Wildcard can be followed by excluded columns, e.g. `SELECT * EXCLUDE (a, b) FROM t`.
+    QualifiedName {
+        qualified: QualifiedName<'a>,
+        exclude: Option<Vec<Identifier<'a>>>,
+    },
 }
 
 pub type QualifiedName<'a> = Vec<Indirection<'a>>;
@@ -167,6 +178,12 @@ pub enum TableReference<'a> {
         subquery: Box<Query<'a>>,
         alias: Option<TableAlias<'a>>,
     },
+    // `(VALUES (expr, ...), ...)[ AS alias ]`
+    Values {
+        span: &'a [Token<'a>],
+        values: Vec<Vec<Expr<'a>>>,
+        alias: Option<TableAlias<'a>>,
+    },
     Join {
         span: &'a [Token<'a>],
         join: Join<'a>,
@@ -294,6 +311,25 @@ impl<'a> Display for TableReference<'a> {
                     write!(f, " AS {alias}")?;
                 }
             }
+            TableReference::Values {
+                span: _,
+                values,
+                alias,
+            } => {
+                write!(f, "(VALUES ")?;
+                for (i, row) in values.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "(")?;
+                    write_comma_separated_list(f, row)?;
+                    write!(f, ")")?;
+                }
+                write!(f, ")")?;
+                if let Some(alias) = alias {
+                    write!(f, " AS {alias}")?;
+                }
+            }
             TableReference::Join { span: _, join } => {
                 write!(f, "{}", join.left)?;
                 if join.condition == JoinCondition::Natural {
@@ -369,8 +405,13 @@ impl<'a> Display for SelectTarget<'a> {
                     write!(f, " AS {ident}")?;
                 }
             }
-            SelectTarget::QualifiedName(indirections) => {
-                write_period_separated_list(f, indirections)?;
+            SelectTarget::QualifiedName { qualified, exclude } => {
+                write_period_separated_list(f, qualified)?;
+                if let Some(exclude) = exclude {
+                    write!(f, " EXCLUDE (")?;
+                    write_comma_separated_list(f, exclude)?;
+                    write!(f, ")")?;
+                }
             }
         }
         Ok(())
@@ -384,6 +425,11 @@ impl<'a> Display for SelectStmt<'a> {
         if self.distinct {
             write!(f, "DISTINCT ")?;
         }
+        if !self.distinct_on.is_empty() {
+            write!(f, "ON (")?;
+            write_comma_separated_list(f, &self.distinct_on)?;
+            write!(f, ") ")?;
+        }
         write_comma_separated_list(f, &self.select_list)?;
 
         // FROM clause
@@ -408,6 +454,12 @@ impl<'a> Display for SelectStmt<'a> {
             write!(f, " HAVING {having}")?;
         }
 
+        // WINDOW clause
+        if !self.window_list.is_empty() {
+            write!(f, " WINDOW ")?;
+            write_comma_separated_list(f, &self.window_list)?;
+        }
+
         Ok(())
     }
 }
@@ -477,15 +529,25 @@ impl<'a> Display for Query<'a> {
             write_comma_separated_list(f, &self.order_by)?;
         }
 
-        // LIMIT clause
-        if !self.limit.is_empty() {
-            write!(f, " LIMIT ")?;
+        if self.with_ties {
+            // OFFSET and FETCH clause
+            if let Some(offset) = &self.offset {
+                write!(f, " OFFSET {offset}")?;
+            }
+            write!(f, " FETCH FIRST ")?;
             write_comma_separated_list(f, &self.limit)?;
-        }
+            write!(f, " ROWS WITH TIES")?;
+        } else {
+            // LIMIT clause
+            if !self.limit.is_empty() {
+                write!(f, " LIMIT ")?;
+                write_comma_separated_list(f, &self.limit)?;
+            }
 
-        // TODO: We should validate if offset exists, limit should be empty or just one element
-        if let Some(offset) = &self.offset {
-            write!(f, " OFFSET {offset}")?;
+            // TODO: We should validate if offset exists, limit should be empty or just one element
+            if let Some(offset) = &self.offset {
+                write!(f, " OFFSET {offset}")?;
+            }
         }
 
         if let Some(format) = &self.format {
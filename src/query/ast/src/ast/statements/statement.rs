@@ -45,6 +45,7 @@ pub enum Statement<'a> {
     ShowProcessList,
     ShowMetrics,
     ShowEngines,
+    ShowWarnings,
     ShowFunctions {
         limit: Option<ShowLimit<'a>>,
     },
@@ -189,6 +190,7 @@ impl<'a> Display for Statement<'a> {
                     ExplainKind::Pipeline => write!(f, " PIPELINE")?,
                     ExplainKind::Fragments => write!(f, " FRAGMENTS")?,
                     ExplainKind::Raw => write!(f, " RAW")?,
+                    ExplainKind::Lineage => write!(f, " LINEAGE")?,
                     ExplainKind::Plan => (),
                 }
                 write!(f, " {query}")?;
@@ -215,6 +217,7 @@ impl<'a> Display for Statement<'a> {
             Statement::ShowProcessList => write!(f, "SHOW PROCESSLIST")?,
             Statement::ShowMetrics => write!(f, "SHOW METRICS")?,
             Statement::ShowEngines => write!(f, "SHOW ENGINES")?,
+            Statement::ShowWarnings => write!(f, "SHOW WARNINGS")?,
             Statement::ShowFunctions { limit } => {
                 write!(f, "SHOW FUNCTIONS")?;
                 if let Some(limit) = limit {
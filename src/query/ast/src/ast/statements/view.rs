@@ -15,6 +15,7 @@
 use std::fmt::Display;
 use std::fmt::Formatter;
 
+use crate::ast::write_comma_separated_list;
 use crate::ast::write_period_separated_list;
 use crate::ast::Identifier;
 use crate::ast::Query;
@@ -25,6 +26,7 @@ pub struct CreateViewStmt<'a> {
     pub catalog: Option<Identifier<'a>>,
     pub database: Option<Identifier<'a>>,
     pub view: Identifier<'a>,
+    pub columns: Vec<Identifier<'a>>,
     pub query: Box<Query<'a>>,
 }
 
@@ -41,6 +43,11 @@ impl Display for CreateViewStmt<'_> {
                 .chain(&self.database)
                 .chain(Some(&self.view)),
         )?;
+        if !self.columns.is_empty() {
+            write!(f, " (")?;
+            write_comma_separated_list(f, &self.columns)?;
+            write!(f, ")")?;
+        }
         write!(f, " AS {}", self.query)
     }
 }
@@ -50,6 +57,7 @@ pub struct AlterViewStmt<'a> {
     pub catalog: Option<Identifier<'a>>,
     pub database: Option<Identifier<'a>>,
     pub view: Identifier<'a>,
+    pub columns: Vec<Identifier<'a>>,
     pub query: Box<Query<'a>>,
 }
 
@@ -63,6 +71,11 @@ impl Display for AlterViewStmt<'_> {
                 .chain(&self.database)
                 .chain(Some(&self.view)),
         )?;
+        if !self.columns.is_empty() {
+            write!(f, " (")?;
+            write_comma_separated_list(f, &self.columns)?;
+            write!(f, ")")?;
+        }
         write!(f, " AS {}", self.query)
     }
 }
@@ -27,6 +27,8 @@ pub use visitors::walk_expr;
 pub use visitors::walk_expr_mut;
 pub use visitors::walk_query;
 pub use visitors::walk_query_mut;
+pub use visitors::walk_table_reference;
+pub use visitors::walk_window;
 pub use visitors::Visitor;
 pub use visitors::VisitorMut;
 
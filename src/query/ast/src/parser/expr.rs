@@ -19,6 +19,7 @@ use nom::combinator::consumed;
 use nom::combinator::map;
 use nom::combinator::value;
 use nom::error::context;
+use nom::Slice;
 use pratt::Affix;
 use pratt::Associativity;
 use pratt::PrattParser;
@@ -223,6 +224,10 @@ pub enum ExprElement<'a> {
     },
     /// `Count(*)` expression
     CountAll,
+    /// A positional parameter of prepared statement, such as `$1`
+    Placeholder {
+        index: usize,
+    },
     /// `(foo, bar)`
     Tuple {
         exprs: Vec<Expr<'a>>,
@@ -234,6 +239,7 @@ pub enum ExprElement<'a> {
         name: Identifier<'a>,
         args: Vec<Expr<'a>>,
         params: Vec<Literal>,
+        window: Option<Window<'a>>,
     },
     /// `CASE ... WHEN ... ELSE ...` expression
     Case {
@@ -400,6 +406,10 @@ impl<'a, I: Iterator<Item = WithSpan<'a, ExprElement<'a>>>> PrattParser<I> for E
                 lit,
             },
             ExprElement::CountAll => Expr::CountAll { span: elem.span.0 },
+            ExprElement::Placeholder { index } => Expr::Placeholder {
+                span: elem.span.0,
+                index,
+            },
             ExprElement::Tuple { exprs } => Expr::Tuple {
                 span: elem.span.0,
                 exprs,
@@ -409,12 +419,14 @@ impl<'a, I: Iterator<Item = WithSpan<'a, ExprElement<'a>>>> PrattParser<I> for E
                 name,
                 args,
                 params,
+                window,
             } => Expr::FunctionCall {
                 span: elem.span.0,
                 distinct,
                 name,
                 args,
                 params,
+                window,
             },
             ExprElement::Case {
                 operand,
@@ -732,12 +744,14 @@ pub fn expr_element(i: Input) -> IResult<WithSpan<ExprElement>> {
             ~ DISTINCT?
             ~ #comma_separated_list0(subexpr(0))?
             ~ ")"
+            ~ ( OVER ~ ^#window )?
         },
-        |(name, _, opt_distinct, opt_args, _)| ExprElement::FunctionCall {
+        |(name, _, opt_distinct, opt_args, _, opt_window)| ExprElement::FunctionCall {
             distinct: opt_distinct.is_some(),
             name,
             args: opt_args.unwrap_or_default(),
             params: vec![],
+            window: opt_window.map(|(_, window)| window),
         },
     );
     let function_call_with_param = map(
@@ -751,6 +765,7 @@ pub fn expr_element(i: Input) -> IResult<WithSpan<ExprElement>> {
             name,
             args: opt_args.unwrap_or_default(),
             params,
+            window: None,
         },
     );
     let case = map(
@@ -800,6 +815,14 @@ pub fn expr_element(i: Input) -> IResult<WithSpan<ExprElement>> {
     let binary_op = map(binary_op, |op| ExprElement::BinaryOp { op });
     let unary_op = map(unary_op, |op| ExprElement::UnaryOp { op });
     let literal = map(literal, |lit| ExprElement::Literal { lit });
+    let placeholder = map_res(rule! { Placeholder }, |token| {
+        let index = match token.text() {
+            // `?` is numbered by its position in the statement when binding.
+            "?" => 0,
+            text => text[1..].parse()?,
+        };
+        Ok(ExprElement::Placeholder { index })
+    });
     let map_access = map(map_access, |accessor| ExprElement::MapAccess { accessor });
     let array = map(
         // Array that contains a single literal item will be parsed as a bracket map access,
@@ -854,6 +877,8 @@ pub fn expr_element(i: Input) -> IResult<WithSpan<ExprElement>> {
         },
         |(_, not, _, _)| ExprElement::IsDistinctFrom { not: not.is_some() },
     );
+    // `a <=> b` is the MySQL spelling of `a IS NOT DISTINCT FROM b`
+    let null_safe_eq = value(ExprElement::IsDistinctFrom { not: true }, rule! { "<=>" });
     let (rest, (span, elem)) = consumed(alt((
         // Note: each `alt` call supports maximum of 21 parsers
         rule!(
@@ -878,10 +903,13 @@ pub fn expr_element(i: Input) -> IResult<WithSpan<ExprElement>> {
         ),
         rule!(
             #is_distinct_from: "`... IS [NOT] DISTINCT FROM ...`"
+            | #null_safe_eq: "`... <=> ...`"
             | #count_all : "COUNT(*)"
+            | #star_argument : "<function>"
             | #function_call_with_param : "<function>"
             | #function_call : "<function>"
             | #literal : "<literal>"
+            | #placeholder : "<placeholder>"
             | #case : "`CASE ... END`"
             | #subquery : "`(SELECT ...)`"
             | #tuple : "`(<expr> [, ...])`"
@@ -894,6 +922,27 @@ pub fn expr_element(i: Input) -> IResult<WithSpan<ExprElement>> {
     Ok((rest, WithSpan { span, elem }))
 }
 
+/// Reject `*` arguments other than `COUNT(*)`, e.g. `SUM(*)` or `COUNT(t.*)`, with
+/// a clear message instead of complaining about the unexpected `*` token.
+fn star_argument(i: Input) -> IResult<ExprElement> {
+    let star = map(consumed(rule! { "*" }), |(span, _)| (span, false));
+    let qualified_star = map(consumed(rule! { #ident ~ "." ~ "*" }), |(span, _)| {
+        (span.slice(2..), true)
+    });
+    let (_, (name, _, (star, qualified))) = rule! {
+        #function_name ~ "(" ~ ( #qualified_star | #star )
+    }(i)?;
+    let msg = if name.name.eq_ignore_ascii_case("count") && qualified {
+        "COUNT(<table>.*) is not supported, use COUNT(*) to count rows"
+    } else {
+        "only COUNT accepts `*` as argument"
+    };
+    Err(nom::Err::Failure(Error::from_error_kind(
+        star,
+        ErrorKind::Other(msg),
+    )))
+}
+
 pub fn unary_op(i: Input) -> IResult<UnaryOperator> {
     // Plus and Minus are parsed as binary op at first.
     value(UnaryOperator::Not, rule! { NOT })(i)
@@ -28,31 +28,64 @@ use crate::parser::expr::*;
 use crate::parser::token::*;
 use crate::rule;
 use crate::util::*;
+use crate::ErrorKind;
 
 pub fn query(i: Input) -> IResult<Query> {
-    map(
+    let with_ties = alt((
+        value(false, rule! { ONLY }),
+        value(true, rule! { WITH ~ TIES }),
+    ));
+    // `FETCH { FIRST | NEXT } n { ROW | ROWS } { ONLY | WITH TIES }`
+    let fetch = map(
+        rule! {
+            FETCH ~ ^( FIRST | NEXT ) ~ ^#expr ~ ^( ROW | ROWS ) ~ ^#with_ties
+        },
+        |(_, _, count, _, with_ties)| (count, with_ties),
+    );
+
+    map_res(
         consumed(rule! {
             #with?
             ~ #set_operation
             ~ ( ORDER ~ ^BY ~ ^#comma_separated_list1(order_by_expr) )?
             ~ ( LIMIT ~ ^#comma_separated_list1(expr) )?
-            ~ ( OFFSET ~ ^#expr )?
+            ~ ( OFFSET ~ ^#expr ~ ( ROW | ROWS )? )?
+            ~ #fetch?
             ~ ( FORMAT ~ #ident )?
             : "`SELECT ...`"
         }),
         |(
             span,
-            (with, body, opt_order_by_block, opt_limit_block, opt_offset_block, opt_format),
-        )| Query {
-            span: span.0,
-            with,
-            body,
-            order_by: opt_order_by_block
-                .map(|(_, _, order_by)| order_by)
-                .unwrap_or_default(),
-            limit: opt_limit_block.map(|(_, limit)| limit).unwrap_or_default(),
-            offset: opt_offset_block.map(|(_, offset)| offset),
-            format: opt_format.map(|(_, format)| format.name),
+            (
+                with,
+                body,
+                opt_order_by_block,
+                opt_limit_block,
+                opt_offset_block,
+                opt_fetch,
+                opt_format,
+            ),
+        )| {
+            let (limit, with_ties) = match (opt_limit_block, opt_fetch) {
+                (Some(_), Some(_)) => {
+                    return Err(ErrorKind::Other("LIMIT and FETCH cannot be used together"));
+                }
+                (Some((_, limit)), None) => (limit, false),
+                (None, Some((count, with_ties))) => (vec![count], with_ties),
+                (None, None) => (vec![], false),
+            };
+            Ok(Query {
+                span: span.0,
+                with,
+                body,
+                order_by: opt_order_by_block
+                    .map(|(_, _, order_by)| order_by)
+                    .unwrap_or_default(),
+                limit,
+                offset: opt_offset_block.map(|(_, offset, _)| offset),
+                format: opt_format.map(|(_, format)| format.name),
+                with_ties,
+            })
         },
     )(i)
 }
@@ -82,20 +115,33 @@ pub fn with(i: Input) -> IResult<With> {
 }
 
 pub fn select_target(i: Input) -> IResult<SelectTarget> {
+    let exclude_col = alt((
+        map(
+            rule! {
+                "(" ~ ^#comma_separated_list1(ident) ~ ^")"
+            },
+            |(_, cols, _)| cols,
+        ),
+        map(ident, |col| vec![col]),
+    ));
     let qualified_wildcard = map(
         rule! {
-            ( #ident ~ "." ~ ( #ident ~ "." )? )? ~ "*"
-        },
-        |(res, _)| match res {
-            Some((fst, _, Some((snd, _)))) => SelectTarget::QualifiedName(vec![
-                Indirection::Identifier(fst),
-                Indirection::Identifier(snd),
-                Indirection::Star,
-            ]),
-            Some((fst, _, None)) => {
-                SelectTarget::QualifiedName(vec![Indirection::Identifier(fst), Indirection::Star])
+            ( #ident ~ "." ~ ( #ident ~ "." )? )? ~ "*" ~ ( EXCLUDE ~ ^#exclude_col )?
+        },
+        |(res, _, exclude)| {
+            let qualified = match res {
+                Some((fst, _, Some((snd, _)))) => vec![
+                    Indirection::Identifier(fst),
+                    Indirection::Identifier(snd),
+                    Indirection::Star,
+                ],
+                Some((fst, _, None)) => vec![Indirection::Identifier(fst), Indirection::Star],
+                None => vec![Indirection::Star],
+            };
+            SelectTarget::QualifiedName {
+                qualified,
+                exclude: exclude.map(|(_, cols)| cols),
             }
-            None => SelectTarget::QualifiedName(vec![Indirection::Star]),
         },
     );
     let projection = map(
@@ -191,6 +237,41 @@ pub fn order_by_expr(i: Input) -> IResult<OrderByExpr> {
     )(i)
 }
 
+pub fn window(i: Input) -> IResult<Window> {
+    alt((
+        map(rule! { "(" ~ #window_spec ~ ^")" }, |(_, spec, _)| {
+            Window::WindowSpec(spec)
+        }),
+        map(rule! { #ident }, Window::WindowReference),
+    ))(i)
+}
+
+pub fn window_spec(i: Input) -> IResult<WindowSpec> {
+    map(
+        rule! {
+            ( PARTITION ~ ^BY ~ ^#comma_separated_list1(expr) )?
+            ~ ( ORDER ~ ^BY ~ ^#comma_separated_list1(order_by_expr) )?
+        },
+        |(opt_partition_by, opt_order_by)| WindowSpec {
+            partition_by: opt_partition_by
+                .map(|(_, _, partition_by)| partition_by)
+                .unwrap_or_default(),
+            order_by: opt_order_by
+                .map(|(_, _, order_by)| order_by)
+                .unwrap_or_default(),
+        },
+    )(i)
+}
+
+pub fn window_definition(i: Input) -> IResult<WindowDefinition> {
+    map(
+        rule! {
+            #ident ~ ^AS ~ ^"(" ~ ^#window_spec ~ ^")"
+        },
+        |(name, _, _, spec, _)| WindowDefinition { name, spec },
+    )(i)
+}
+
 pub fn table_reference(i: Input) -> IResult<TableReference> {
     let (rest, table_reference_elements) = rule!(#table_reference_element+)(i)?;
     let iter = &mut table_reference_elements.into_iter();
@@ -217,6 +298,11 @@ pub enum TableReferenceElement<'a> {
         subquery: Box<Query<'a>>,
         alias: Option<TableAlias<'a>>,
     },
+    // `(VALUES (expr, ...), ...)[ AS alias ]`
+    Values {
+        values: Vec<Vec<Expr<'a>>>,
+        alias: Option<TableAlias<'a>>,
+    },
     // [NATURAL] [INNER|OUTER|CROSS|...] JOIN
     Join {
         op: JoinOperator,
@@ -259,6 +345,18 @@ pub fn table_reference_element(i: Input) -> IResult<WithSpan<TableReferenceEleme
             alias,
         },
     );
+    let values_row = map(
+        rule! {
+            "(" ~ #comma_separated_list1(expr) ~ ^")"
+        },
+        |(_, row, _)| row,
+    );
+    let values = map(
+        rule! {
+            "(" ~ VALUES ~ ^#comma_separated_list1(values_row) ~ ^")" ~ #table_alias?
+        },
+        |(_, _, values, _, alias)| TableReferenceElement::Values { values, alias },
+    );
     let join = map(
         rule! {
             NATURAL? ~ #join_operator? ~ JOIN
@@ -288,7 +386,8 @@ pub fn table_reference_element(i: Input) -> IResult<WithSpan<TableReferenceEleme
     );
 
     let (rest, (span, elem)) = consumed(rule! {
-        #subquery
+        #values
+        | #subquery
         | #table_function
         | #aliased_table
         | #group
@@ -349,6 +448,11 @@ impl<'a, I: Iterator<Item = WithSpan<'a, TableReferenceElement<'a>>>> PrattParse
                 subquery,
                 alias,
             },
+            TableReferenceElement::Values { values, alias } => TableReference::Values {
+                span: input.span.0,
+                values,
+                alias,
+            },
             _ => unreachable!(),
         };
         Ok(table_ref)
@@ -425,11 +529,13 @@ pub fn set_operation(i: Input) -> IResult<SetExpr> {
 pub enum SetOperationElement<'a> {
     SelectStmt {
         distinct: bool,
+        distinct_on: Box<Vec<Expr<'a>>>,
         select_list: Box<Vec<SelectTarget<'a>>>,
         from: Box<Vec<TableReference<'a>>>,
         selection: Box<Option<Expr<'a>>>,
         group_by: Box<Vec<Expr<'a>>>,
         having: Box<Option<Expr<'a>>>,
+        window_list: Box<Vec<WindowDefinition<'a>>>,
     },
     SetOperation {
         op: SetOperator,
@@ -458,11 +564,13 @@ pub fn set_operation_element(i: Input) -> IResult<WithSpan<SetOperationElement>>
     );
     let select_stmt = map(
         rule! {
-             SELECT ~ DISTINCT? ~ ^#comma_separated_list1(select_target)
+             SELECT ~ ( DISTINCT ~ ( ON ~ ^"(" ~ ^#comma_separated_list1(expr) ~ ^")" )? )?
+                ~ ^#comma_separated_list1(select_target)
                 ~ ( FROM ~ ^#comma_separated_list1(table_reference) )?
                 ~ ( WHERE ~ ^#expr )?
                 ~ ( GROUP ~ ^BY ~ ^#comma_separated_list1(expr) )?
                 ~ ( HAVING ~ ^#expr )?
+                ~ ( WINDOW ~ ^#comma_separated_list1(window_definition) )?
         },
         |(
             _select,
@@ -472,9 +580,16 @@ pub fn set_operation_element(i: Input) -> IResult<WithSpan<SetOperationElement>>
             opt_where_block,
             opt_group_by_block,
             opt_having_block,
+            opt_window_block,
         )| {
             SetOperationElement::SelectStmt {
                 distinct: opt_distinct.is_some(),
+                distinct_on: Box::new(
+                    opt_distinct
+                        .and_then(|(_, opt_on)| opt_on)
+                        .map(|(_, _, exprs, _)| exprs)
+                        .unwrap_or_default(),
+                ),
                 select_list: Box::new(select_list),
                 from: Box::new(
                     opt_from_block
@@ -488,6 +603,11 @@ pub fn set_operation_element(i: Input) -> IResult<WithSpan<SetOperationElement>>
                         .unwrap_or_default(),
                 ),
                 having: Box::new(opt_having_block.map(|(_, having)| having)),
+                window_list: Box::new(
+                    opt_window_block
+                        .map(|(_, window_list)| window_list)
+                        .unwrap_or_default(),
+                ),
             }
         },
     );
@@ -529,19 +649,23 @@ impl<'a, I: Iterator<Item = WithSpan<'a, SetOperationElement<'a>>>> PrattParser<
             SetOperationElement::Group(expr) => expr,
             SetOperationElement::SelectStmt {
                 distinct,
+                distinct_on,
                 select_list,
                 from,
                 selection,
                 group_by,
                 having,
+                window_list,
             } => SetExpr::Select(Box::new(SelectStmt {
                 span: input.span.0,
                 distinct,
+                distinct_on: *distinct_on,
                 select_list: *select_list,
                 from: *from,
                 selection: *selection,
                 group_by: *group_by,
                 having: *having,
+                window_list: *window_list,
             })),
             _ => unreachable!(),
         };
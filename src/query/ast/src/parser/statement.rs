@@ -53,7 +53,7 @@ pub enum CreateDatabaseOption {
 pub fn statement(i: Input) -> IResult<StatementMsg> {
     let explain = map_res(
         rule! {
-            EXPLAIN ~ ( AST | SYNTAX | PIPELINE | GRAPH | FRAGMENTS | RAW )? ~ #statement
+            EXPLAIN ~ ( AST | SYNTAX | PIPELINE | GRAPH | FRAGMENTS | RAW | LINEAGE )? ~ #statement
         },
         |(_, opt_kind, statement)| {
             Ok(Statement::Explain {
@@ -72,6 +72,7 @@ pub fn statement(i: Input) -> IResult<StatementMsg> {
                     Some(TokenKind::GRAPH) => ExplainKind::Graph,
                     Some(TokenKind::FRAGMENTS) => ExplainKind::Fragments,
                     Some(TokenKind::RAW) => ExplainKind::Raw,
+                    Some(TokenKind::LINEAGE) => ExplainKind::Lineage,
                     None => ExplainKind::Plan,
                     _ => unreachable!(),
                 },
@@ -123,6 +124,7 @@ pub fn statement(i: Input) -> IResult<StatementMsg> {
     let show_process_list = value(Statement::ShowProcessList, rule! { SHOW ~ PROCESSLIST });
     let show_metrics = value(Statement::ShowMetrics, rule! { SHOW ~ METRICS });
     let show_engines = value(Statement::ShowEngines, rule! { SHOW ~ ENGINES });
+    let show_warnings = value(Statement::ShowWarnings, rule! { SHOW ~ WARNINGS });
     let show_functions = map(
         rule! {
             SHOW ~ FUNCTIONS ~ #show_limit?
@@ -447,14 +449,18 @@ pub fn statement(i: Input) -> IResult<StatementMsg> {
         rule! {
             CREATE ~ VIEW ~ ( IF ~ NOT ~ EXISTS )?
             ~ #peroid_separated_idents_1_to_3
+            ~ ( "(" ~ #comma_separated_list1(ident) ~ ")" )?
             ~ AS ~ #query
         },
-        |(_, _, opt_if_not_exists, (catalog, database, view), _, query)| {
+        |(_, _, opt_if_not_exists, (catalog, database, view), opt_columns, _, query)| {
             Statement::CreateView(CreateViewStmt {
                 if_not_exists: opt_if_not_exists.is_some(),
                 catalog,
                 database,
                 view,
+                columns: opt_columns
+                    .map(|(_, columns, _)| columns)
+                    .unwrap_or_default(),
                 query: Box::new(query),
             })
         },
@@ -476,13 +482,17 @@ pub fn statement(i: Input) -> IResult<StatementMsg> {
         rule! {
             ALTER ~ VIEW
             ~ #peroid_separated_idents_1_to_3
+            ~ ( "(" ~ #comma_separated_list1(ident) ~ ")" )?
             ~ AS ~ #query
         },
-        |(_, _, (catalog, database, view), _, query)| {
+        |(_, _, (catalog, database, view), opt_columns, _, query)| {
             Statement::AlterView(AlterViewStmt {
                 catalog,
                 database,
                 view,
+                columns: opt_columns
+                    .map(|(_, columns, _)| columns)
+                    .unwrap_or_default(),
                 query: Box::new(query),
             })
         },
@@ -895,6 +905,7 @@ pub fn statement(i: Input) -> IResult<StatementMsg> {
             | #show_engines : "`SHOW ENGINES`"
             | #show_process_list : "`SHOW PROCESSLIST`"
             | #show_metrics : "`SHOW METRICS`"
+            | #show_warnings : "`SHOW WARNINGS`"
             | #show_functions : "`SHOW FUNCTIONS [<show_limit>]`"
             | #kill_stmt : "`KILL (QUERY | CONNECTION) <object_id>`"
             | #set_variable : "`SET <variable> = <value>`"
@@ -922,9 +933,9 @@ pub fn statement(i: Input) -> IResult<StatementMsg> {
             | #exists_table : "`EXISTS TABLE [<database>.]<table>`"
         ),
         rule!(
-            #create_view : "`CREATE VIEW [IF NOT EXISTS] [<database>.]<view> AS SELECT ...`"
+            #create_view : "`CREATE VIEW [IF NOT EXISTS] [<database>.]<view> [(<column>, ...)] AS SELECT ...`"
             | #drop_view : "`DROP VIEW [IF EXISTS] [<database>.]<view>`"
-            | #alter_view : "`ALTER VIEW [<database>.]<view> AS SELECT ...`"
+            | #alter_view : "`ALTER VIEW [<database>.]<view> [(<column>, ...)] AS SELECT ...`"
         ),
         rule!(
             #show_users : "`SHOW USERS`"
@@ -133,6 +133,10 @@ pub enum TokenKind {
     #[regex(r"([0-9]*\.[0-9]+([eE][+-]?[0-9]+)?)|([0-9]+\.[0-9]*([eE][+-]?[0-9]+)?)")]
     LiteralFloat,
 
+    #[regex(r"\$[0-9]+")]
+    #[token("?")]
+    Placeholder,
+
     // Symbols
     #[token("==")]
     DoubleEq,
@@ -363,6 +367,8 @@ pub enum TokenKind {
     DROP,
     #[token("EXCEPT", ignore(ascii_case))]
     EXCEPT,
+    #[token("EXCLUDE", ignore(ascii_case))]
+    EXCLUDE,
     #[token("ELSE", ignore(ascii_case))]
     ELSE,
     #[token("END", ignore(ascii_case))]
@@ -387,6 +393,8 @@ pub enum TokenKind {
     EXTRACT,
     #[token("FALSE", ignore(ascii_case))]
     FALSE,
+    #[token("FETCH", ignore(ascii_case))]
+    FETCH,
     #[token("FIELDS", ignore(ascii_case))]
     FIELDS,
     #[token("FIELD_DELIMITER", ignore(ascii_case))]
@@ -489,6 +497,8 @@ pub enum TokenKind {
     LIKE,
     #[token("LIMIT", ignore(ascii_case))]
     LIMIT,
+    #[token("LINEAGE", ignore(ascii_case))]
+    LINEAGE,
     #[token("LIST", ignore(ascii_case))]
     LIST,
     #[token("MAP", ignore(ascii_case))]
@@ -513,6 +523,8 @@ pub enum TokenKind {
     MONTH,
     #[token("NATURAL", ignore(ascii_case))]
     NATURAL,
+    #[token("NEXT", ignore(ascii_case))]
+    NEXT,
     #[token("NO_PASSWORD", ignore(ascii_case))]
     NO_PASSWORD,
     #[token("NOT", ignore(ascii_case))]
@@ -529,6 +541,8 @@ pub enum TokenKind {
     OFFSET,
     #[token("ON", ignore(ascii_case))]
     ON,
+    #[token("ONLY", ignore(ascii_case))]
+    ONLY,
     #[token("OPTIMIZE", ignore(ascii_case))]
     OPTIMIZE,
     #[token("OR", ignore(ascii_case))]
@@ -539,10 +553,14 @@ pub enum TokenKind {
     OUTER,
     #[token("ON_ERROR", ignore(ascii_case))]
     ON_ERROR,
+    #[token("OVER", ignore(ascii_case))]
+    OVER,
     #[token("OVERWRITE", ignore(ascii_case))]
     OVERWRITE,
     #[token("PARQUET", ignore(ascii_case))]
     PARQUET,
+    #[token("PARTITION", ignore(ascii_case))]
+    PARTITION,
     #[token("PATTERN", ignore(ascii_case))]
     PATTERN,
     #[token("PIPELINE", ignore(ascii_case))]
@@ -573,6 +591,10 @@ pub enum TokenKind {
     GRANT,
     #[token("ROLE", ignore(ascii_case))]
     ROLE,
+    #[token("ROW", ignore(ascii_case))]
+    ROW,
+    #[token("ROWS", ignore(ascii_case))]
+    ROWS,
     #[token("PRESIGN", ignore(ascii_case))]
     PRESIGN,
     #[token("PRIVILEGES", ignore(ascii_case))]
@@ -657,6 +679,8 @@ pub enum TokenKind {
     TENANTS,
     #[token("THEN", ignore(ascii_case))]
     THEN,
+    #[token("TIES", ignore(ascii_case))]
+    TIES,
     #[token("TIMESTAMP", ignore(ascii_case))]
     TIMESTAMP,
     #[token("TIMEZONE_HOUR", ignore(ascii_case))]
@@ -721,12 +745,16 @@ pub enum TokenKind {
     VARIANT,
     #[token("VIEW", ignore(ascii_case))]
     VIEW,
+    #[token("WARNINGS", ignore(ascii_case))]
+    WARNINGS,
     #[token("WEEK", ignore(ascii_case))]
     WEEK,
     #[token("WHEN", ignore(ascii_case))]
     WHEN,
     #[token("WHERE", ignore(ascii_case))]
     WHERE,
+    #[token("WINDOW", ignore(ascii_case))]
+    WINDOW,
     #[token("WITH", ignore(ascii_case))]
     WITH,
     #[token("XOR", ignore(ascii_case))]
@@ -760,6 +788,7 @@ impl TokenKind {
                 | MySQLLiteralHex
                 | LiteralInteger
                 | LiteralFloat
+                | Placeholder
                 | DoubleEq
                 | Eq
                 | NotEq
@@ -1032,7 +1061,7 @@ impl TokenKind {
             | TokenKind::AS
             | TokenKind::CREATE
             | TokenKind::EXCEPT
-            // | TokenKind::FETCH
+            | TokenKind::FETCH
             | TokenKind::FOR
             | TokenKind::FROM
             // | TokenKind::GRANT
@@ -1056,7 +1085,7 @@ impl TokenKind {
             | TokenKind::TO
             | TokenKind::UNION
             | TokenKind::WHERE
-            // | TokenKind::WINDOW
+            | TokenKind::WINDOW
             | TokenKind::WITH
             if !after_as => true,
             _ => false
@@ -21,8 +21,10 @@ use common_functions::is_builtin_function;
 use crate::ast::Expr;
 use crate::ast::Identifier;
 use crate::ast::Literal;
+use crate::ast::Window;
 use crate::parser::token::Token;
 use crate::walk_expr;
+use crate::walk_window;
 use crate::Visitor;
 
 #[derive(Default)]
@@ -87,6 +89,7 @@ impl<'ast> Visitor<'ast> for UDFValidator {
         name: &'ast Identifier<'ast>,
         args: &'ast [Expr<'ast>],
         _params: &'ast [Literal],
+        window: &'ast Option<Window<'ast>>,
     ) {
         let name = name.to_string();
         if !is_builtin_function(&name) && self.name.eq_ignore_ascii_case(&name) {
@@ -97,5 +100,8 @@ impl<'ast> Visitor<'ast> for UDFValidator {
         for arg in args {
             walk_expr(self, arg);
         }
+        if let Some(window) = window {
+            walk_window(self, window);
+        }
     }
 }
@@ -25,6 +25,8 @@ use super::walk::walk_select_target;
 use super::walk::walk_set_expr;
 use super::walk::walk_statement;
 use super::walk::walk_table_reference;
+use super::walk::walk_window;
+use super::walk::walk_window_spec;
 use super::walk_time_travel_point;
 use crate::ast::*;
 use crate::parser::token::Token;
@@ -220,6 +222,8 @@ pub trait Visitor<'ast>: Sized {
 
     fn visit_count_all(&mut self, _span: &'ast [Token<'ast>]) {}
 
+    fn visit_placeholder(&mut self, _span: &'ast [Token<'ast>], _index: usize) {}
+
     fn visit_tuple(&mut self, _span: &'ast [Token<'ast>], elements: &'ast [Expr<'ast>]) {
         for element in elements {
             walk_expr(self, element);
@@ -233,10 +237,18 @@ pub trait Visitor<'ast>: Sized {
         _name: &'ast Identifier<'ast>,
         args: &'ast [Expr<'ast>],
         _params: &'ast [Literal],
+        window: &'ast Option<Window<'ast>>,
     ) {
         for arg in args {
             walk_expr(self, arg);
         }
+        if let Some(window) = window {
+            self.visit_window(window);
+        }
+    }
+
+    fn visit_window(&mut self, window: &'ast Window<'ast>) {
+        walk_window(self, window);
     }
 
     fn visit_case_when(
@@ -358,6 +370,8 @@ pub trait Visitor<'ast>: Sized {
 
     fn visit_show_engines(&mut self) {}
 
+    fn visit_show_warnings(&mut self) {}
+
     fn visit_show_functions(&mut self, _limit: &'ast Option<ShowLimit<'ast>>) {}
 
     fn visit_show_limit(&mut self, _limit: &'ast ShowLimit<'ast>) {}
@@ -534,6 +548,7 @@ pub trait Visitor<'ast>: Sized {
             selection,
             group_by,
             having,
+            window_list,
             ..
         } = stmt;
 
@@ -556,6 +571,10 @@ pub trait Visitor<'ast>: Sized {
         if let Some(having) = having {
             walk_expr(self, having);
         }
+
+        for window in window_list.iter() {
+            walk_window_spec(self, &window.spec);
+        }
     }
 
     fn visit_select_target(&mut self, target: &'ast SelectTarget<'ast>) {
@@ -25,6 +25,8 @@ use super::walk_mut::walk_select_target_mut;
 use super::walk_mut::walk_set_expr_mut;
 use super::walk_mut::walk_statement_mut;
 use super::walk_mut::walk_table_reference_mut;
+use super::walk_mut::walk_window_mut;
+use super::walk_mut::walk_window_spec_mut;
 use super::walk_time_travel_point_mut;
 use crate::ast::*;
 use crate::parser::token::Token;
@@ -225,6 +227,8 @@ pub trait VisitorMut: Sized {
 
     fn visit_count_all(&mut self, _span: &mut &[Token<'_>]) {}
 
+    fn visit_placeholder(&mut self, _span: &mut &[Token<'_>], _index: &mut usize) {}
+
     fn visit_tuple(&mut self, _span: &mut &[Token<'_>], elements: &mut [Expr<'_>]) {
         for elem in elements.iter_mut() {
             walk_expr_mut(self, elem);
@@ -238,10 +242,18 @@ pub trait VisitorMut: Sized {
         _name: &mut Identifier<'_>,
         args: &mut [Expr<'_>],
         _params: &mut [Literal],
+        window: &mut Option<Window<'_>>,
     ) {
         for arg in args.iter_mut() {
             walk_expr_mut(self, arg);
         }
+        if let Some(window) = window {
+            self.visit_window(window);
+        }
+    }
+
+    fn visit_window(&mut self, window: &mut Window<'_>) {
+        walk_window_mut(self, window);
     }
 
     fn visit_case_when(
@@ -361,6 +373,8 @@ pub trait VisitorMut: Sized {
 
     fn visit_show_engines(&mut self) {}
 
+    fn visit_show_warnings(&mut self) {}
+
     fn visit_show_functions(&mut self, _limit: &mut Option<ShowLimit<'_>>) {}
 
     fn visit_show_limit(&mut self, _limit: &mut ShowLimit<'_>) {}
@@ -537,6 +551,7 @@ pub trait VisitorMut: Sized {
             selection,
             group_by,
             having,
+            window_list,
             ..
         } = stmt;
 
@@ -559,6 +574,10 @@ pub trait VisitorMut: Sized {
         if let Some(having) = having {
             walk_expr_mut(self, having);
         }
+
+        for window in window_list.iter_mut() {
+            walk_window_spec_mut(self, &mut window.spec);
+        }
     }
 
     fn visit_select_target(&mut self, target: &mut SelectTarget<'_>) {
@@ -86,6 +86,7 @@ pub fn walk_expr_mut<'a, V: VisitorMut>(visitor: &mut V, expr: &mut Expr<'a>) {
         } => visitor.visit_trim(span, expr, trim_where),
         Expr::Literal { span, lit } => visitor.visit_literal(span, lit),
         Expr::CountAll { span } => visitor.visit_count_all(span),
+        Expr::Placeholder { span, index } => visitor.visit_placeholder(span, index),
         Expr::Tuple { span, exprs } => visitor.visit_tuple(span, exprs),
         Expr::FunctionCall {
             span,
@@ -93,7 +94,8 @@ pub fn walk_expr_mut<'a, V: VisitorMut>(visitor: &mut V, expr: &mut Expr<'a>) {
             name,
             args,
             params,
-        } => visitor.visit_function_call(span, *distinct, name, args, params),
+            window,
+        } => visitor.visit_function_call(span, *distinct, name, args, params, window),
         Expr::Case {
             span,
             operand,
@@ -138,6 +140,22 @@ pub fn walk_identifier_mut<'a, V: VisitorMut>(visitor: &mut V, ident: &mut Ident
     visitor.visit_identifier(ident);
 }
 
+pub fn walk_window_mut<'a, V: VisitorMut>(visitor: &mut V, window: &mut Window<'a>) {
+    match window {
+        Window::WindowReference(name) => visitor.visit_identifier(name),
+        Window::WindowSpec(spec) => walk_window_spec_mut(visitor, spec),
+    }
+}
+
+pub fn walk_window_spec_mut<'a, V: VisitorMut>(visitor: &mut V, spec: &mut WindowSpec<'a>) {
+    for expr in spec.partition_by.iter_mut() {
+        visitor.visit_expr(expr);
+    }
+    for order_by in spec.order_by.iter_mut() {
+        visitor.visit_order_by(order_by);
+    }
+}
+
 pub fn walk_query_mut<'a, V: VisitorMut>(visitor: &mut V, query: &mut Query<'a>) {
     let Query {
         with,
@@ -185,8 +203,8 @@ pub fn walk_select_target_mut<'a, V: VisitorMut>(visitor: &mut V, target: &mut S
                 visitor.visit_identifier(alias);
             }
         }
-        SelectTarget::QualifiedName(names) => {
-            for indirection in names {
+        SelectTarget::QualifiedName { qualified, exclude } => {
+            for indirection in qualified {
                 match indirection {
                     Indirection::Identifier(ident) => {
                         visitor.visit_identifier(ident);
@@ -194,6 +212,11 @@ pub fn walk_select_target_mut<'a, V: VisitorMut>(visitor: &mut V, target: &mut S
                     Indirection::Star => {}
                 }
             }
+            if let Some(exclude) = exclude {
+                for ident in exclude {
+                    visitor.visit_identifier(ident);
+                }
+            }
         }
     }
 }
@@ -237,6 +260,14 @@ pub fn walk_table_reference_mut<'a, V: VisitorMut>(
                 visitor.visit_identifier(&mut alias.name);
             }
         }
+        TableReference::Values { values, alias, .. } => {
+            for expr in values.iter_mut().flatten() {
+                visitor.visit_expr(expr);
+            }
+            if let Some(alias) = alias {
+                visitor.visit_identifier(&mut alias.name);
+            }
+        }
         TableReference::TableFunction {
             name,
             params,
@@ -305,6 +336,7 @@ pub fn walk_statement_mut<'a, V: VisitorMut>(visitor: &mut V, statement: &mut St
         Statement::ShowProcessList => visitor.visit_show_process_list(),
         Statement::ShowMetrics => visitor.visit_show_metrics(),
         Statement::ShowEngines => visitor.visit_show_engines(),
+        Statement::ShowWarnings => visitor.visit_show_warnings(),
         Statement::ShowFunctions { limit } => visitor.visit_show_functions(limit),
         Statement::KillStmt {
             kill_target,
@@ -68,6 +68,7 @@ fn test_statement() {
         r#"show tables"#,
         r#"show tables format TabSeparatedWithNamesAndTypes;"#,
         r#"show processlist;"#,
+        r#"show warnings;"#,
         r#"show create table a.b;"#,
         r#"show create table a.b format TabSeparatedWithNamesAndTypes;"#,
         r#"explain pipeline select a from b;"#,
@@ -91,6 +92,7 @@ fn test_statement() {
         r#"drop database if exists t;"#,
         r#"create table c(a DateTime null, b DateTime(3));"#,
         r#"create view v as select number % 3 as a from numbers(1000);"#,
+        r#"create view v(x) as select number % 3 as a from numbers(1000);"#,
         r#"alter view v as select number % 3 as a from numbers(1000);"#,
         r#"drop view v;"#,
         r#"rename table d.t to e.s;"#,
@@ -402,6 +404,7 @@ fn test_query() {
         r#"select * from t1 union select * from t2 intersect select * from t3"#,
         r#"(select * from t1 union select * from t2) union select * from t3"#,
         r#"select * from t1 union (select * from t2 union select * from t3)"#,
+        r#"select * from a order by b offset 1 rows fetch next 2 rows with ties"#,
     ];
 
     for case in cases {
@@ -494,6 +497,7 @@ fn test_expr() {
         r#"1 is distinct from 2"#,
         r#"a is distinct from b"#,
         r#"1 is not distinct from null"#,
+        r#"sum(a) over (partition by b order by c desc)"#,
     ];
 
     for case in cases {
@@ -515,6 +519,8 @@ fn test_expr_error() {
         r#"G.E.B IS NOT NULL AND
             col1 NOT BETWEEN col2 AND
                 AND 1 + col3 DIV sum(col4)"#,
+        r#"SUM(*)"#,
+        r#"COUNT(t.*)"#,
     ];
 
     for case in cases {
@@ -99,4 +99,9 @@ pub trait TableContext: Send + Sync {
     async fn get_table(&self, catalog: &str, database: &str, table: &str)
     -> Result<Arc<dyn Table>>;
     fn get_processes_info(&self) -> Vec<ProcessInfo>;
+    /// Report a warning of the running statement to the session, see `SHOW WARNINGS`.
+    fn push_warning(&self, warning: String);
+    /// Get the warnings of the last statement of the session.
+    fn get_warnings(&self) -> Vec<String>;
+    fn clear_warnings(&self);
 }
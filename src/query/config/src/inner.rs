@@ -142,6 +142,8 @@ pub struct QueryConfig {
     pub table_disk_cache_root: String,
     /// Table disk cache size (mb)
     pub table_disk_cache_mb_size: u64,
+    /// Folder of the temporary files spilled by queries, e.g. the partial group by states
+    pub spill_dir: String,
     /// If in management mode, only can do some meta level operations(database/table/user/stage etc.) with metasrv.
     pub management_mode: bool,
     pub jwt_key_file: String,
@@ -188,6 +190,7 @@ impl Default for QueryConfig {
             table_memory_cache_mb_size: 256,
             table_disk_cache_root: "_cache".to_string(),
             table_disk_cache_mb_size: 1024,
+            spill_dir: "./.databend/spill".to_string(),
             management_mode: false,
             jwt_key_file: "".to_string(),
             async_insert_max_data_size: 10000,
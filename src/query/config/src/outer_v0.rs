@@ -811,6 +811,10 @@ pub struct QueryConfig {
     #[clap(long, default_value = "1024")]
     pub table_disk_cache_mb_size: u64,
 
+    /// Folder of the temporary files spilled by queries, e.g. the partial group by states
+    #[clap(long, default_value = "./.databend/spill")]
+    pub spill_dir: String,
+
     /// If in management mode, only can do some meta level operations(database/table/user/stage etc.) with metasrv.
     #[clap(long)]
     pub management_mode: bool,
@@ -879,6 +883,7 @@ impl TryInto<InnerQueryConfig> for QueryConfig {
             table_memory_cache_mb_size: self.table_memory_cache_mb_size,
             table_disk_cache_root: self.table_disk_cache_root,
             table_disk_cache_mb_size: self.table_disk_cache_mb_size,
+            spill_dir: self.spill_dir,
             management_mode: self.management_mode,
             jwt_key_file: self.jwt_key_file,
             async_insert_max_data_size: self.async_insert_max_data_size,
@@ -935,6 +940,7 @@ impl From<InnerQueryConfig> for QueryConfig {
             table_memory_cache_mb_size: inner.table_memory_cache_mb_size,
             table_disk_cache_root: inner.table_disk_cache_root,
             table_disk_cache_mb_size: inner.table_disk_cache_mb_size,
+            spill_dir: inner.spill_dir,
             management_mode: inner.management_mode,
             jwt_key_file: inner.jwt_key_file,
             async_insert_max_data_size: inner.async_insert_max_data_size,
@@ -15,6 +15,7 @@
 use common_datavalues::remove_nullable;
 use common_datavalues::DataType;
 use common_datavalues::DataTypeImpl;
+use common_exception::ErrorCode;
 use common_exception::Result;
 
 use crate::kernels::HashMethodKeysU16;
@@ -46,6 +47,63 @@ impl DataBlock {
     pub fn choose_hash_method_with_types(
         hash_key_types: &[DataTypeImpl],
     ) -> Result<HashMethodKind> {
+        let group_key_len = match Self::fixed_group_key_len(hash_key_types)? {
+            Ok(len) => len,
+            Err(_) => return Ok(HashMethodKind::Serializer(HashMethodSerializer::default())),
+        };
+
+        match group_key_len {
+            1 => Ok(HashMethodKind::KeysU8(HashMethodKeysU8::default())),
+            2 => Ok(HashMethodKind::KeysU16(HashMethodKeysU16::default())),
+            3..=4 => Ok(HashMethodKind::KeysU32(HashMethodKeysU32::default())),
+            5..=8 => Ok(HashMethodKind::KeysU64(HashMethodKeysU64::default())),
+            9..=16 => Ok(HashMethodKind::KeysU128(HashMethodKeysU128::default())),
+            17..=32 => Ok(HashMethodKind::KeysU256(HashMethodKeysU256::default())),
+            33..=64 => Ok(HashMethodKind::KeysU512(HashMethodKeysU512::default())),
+            _ => Ok(HashMethodKind::Serializer(HashMethodSerializer::default())),
+        }
+    }
+
+    /// Choose the hash method by its setting name: "auto" picks one from the key types like
+    /// `choose_hash_method_with_types`, the others force that method if it can hold the keys.
+    pub fn choose_hash_method_with_name(
+        hash_key_types: &[DataTypeImpl],
+        name: &str,
+    ) -> Result<HashMethodKind> {
+        let (method, capacity) = match name.to_lowercase().as_str() {
+            "auto" => return Self::choose_hash_method_with_types(hash_key_types),
+            "serializer" => {
+                return Ok(HashMethodKind::Serializer(HashMethodSerializer::default()));
+            }
+            "keys_u8" => (HashMethodKind::KeysU8(HashMethodKeysU8::default()), 1),
+            "keys_u16" => (HashMethodKind::KeysU16(HashMethodKeysU16::default()), 2),
+            "keys_u32" => (HashMethodKind::KeysU32(HashMethodKeysU32::default()), 4),
+            "keys_u64" => (HashMethodKind::KeysU64(HashMethodKeysU64::default()), 8),
+            "keys_u128" => (HashMethodKind::KeysU128(HashMethodKeysU128::default()), 16),
+            "keys_u256" => (HashMethodKind::KeysU256(HashMethodKeysU256::default()), 32),
+            "keys_u512" => (HashMethodKind::KeysU512(HashMethodKeysU512::default()), 64),
+            _ => {
+                return Err(ErrorCode::BadArguments(format!(
+                    "Unknown group by hash method: {name}"
+                )));
+            }
+        };
+
+        match Self::fixed_group_key_len(hash_key_types)? {
+            Ok(len) if len <= capacity => Ok(method),
+            Ok(len) => Err(ErrorCode::BadArguments(format!(
+                "Group by hash method {name} cannot represent the group keys: they take {len} bytes, but it holds at most {capacity}"
+            ))),
+            Err(typ) => Err(ErrorCode::BadArguments(format!(
+                "Group by hash method {name} cannot represent the group keys: key of type {} has no fixed size",
+                typ.name()
+            ))),
+        }
+    }
+
+    /// Total bytes of the group keys packed into a fixed-size key, or the first key type
+    /// that cannot be packed.
+    fn fixed_group_key_len(hash_key_types: &[DataTypeImpl]) -> Result<Result<usize, DataTypeImpl>> {
         let mut group_key_len = 0;
         for typ in hash_key_types {
             let not_null_type = remove_nullable(typ);
@@ -60,20 +118,10 @@ impl DataBlock {
                     group_key_len += 1;
                 }
             } else {
-                return Ok(HashMethodKind::Serializer(HashMethodSerializer::default()));
+                return Ok(Err(typ.clone()));
             }
         }
-
-        match group_key_len {
-            1 => Ok(HashMethodKind::KeysU8(HashMethodKeysU8::default())),
-            2 => Ok(HashMethodKind::KeysU16(HashMethodKeysU16::default())),
-            3..=4 => Ok(HashMethodKind::KeysU32(HashMethodKeysU32::default())),
-            5..=8 => Ok(HashMethodKind::KeysU64(HashMethodKeysU64::default())),
-            9..=16 => Ok(HashMethodKind::KeysU128(HashMethodKeysU128::default())),
-            17..=32 => Ok(HashMethodKind::KeysU256(HashMethodKeysU256::default())),
-            33..=64 => Ok(HashMethodKind::KeysU512(HashMethodKeysU512::default())),
-            _ => Ok(HashMethodKind::Serializer(HashMethodSerializer::default())),
-        }
+        Ok(Ok(group_key_len))
     }
 
     pub fn group_by_blocks(block: &DataBlock, indices: &[usize]) -> Result<Vec<DataBlock>> {
@@ -57,3 +57,37 @@ fn test_data_block_group_by_hash() -> Result<()> {
     ]);
     Ok(())
 }
+
+#[test]
+fn test_data_block_choose_hash_method_with_name() -> Result<()> {
+    let types = vec![
+        i32::to_data_type(),
+        NullableType::new_impl(u16::to_data_type()),
+    ];
+
+    let method = DataBlock::choose_hash_method_with_name(&types, "auto")?;
+    assert_eq!(method.name(), HashMethodKeysU64::default().name());
+
+    let method = DataBlock::choose_hash_method_with_name(&types, "serializer")?;
+    assert_eq!(method.name(), HashMethodSerializer::default().name());
+
+    let method = DataBlock::choose_hash_method_with_name(&types, "KEYS_U128")?;
+    assert_eq!(method.name(), HashMethodKeysU128::default().name());
+
+    let err = DataBlock::choose_hash_method_with_name(&types, "keys_u32").unwrap_err();
+    assert_eq!(
+        err.message(),
+        "Group by hash method keys_u32 cannot represent the group keys: they take 7 bytes, but it holds at most 4"
+    );
+
+    let types = vec![i32::to_data_type(), Vu8::to_data_type()];
+    let err = DataBlock::choose_hash_method_with_name(&types, "keys_u64").unwrap_err();
+    assert_eq!(
+        err.message(),
+        "Group by hash method keys_u64 cannot represent the group keys: key of type String has no fixed size"
+    );
+
+    let err = DataBlock::choose_hash_method_with_name(&types, "keys_u24").unwrap_err();
+    assert_eq!(err.message(), "Unknown group by hash method: keys_u24");
+    Ok(())
+}
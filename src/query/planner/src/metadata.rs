@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::BTreeMap;
 use std::collections::HashSet;
 use std::collections::VecDeque;
 use std::fmt::Debug;
@@ -49,6 +50,11 @@ pub type MetadataRef = Arc<RwLock<Metadata>>;
 pub struct Metadata {
     tables: Vec<TableEntry>,
     columns: Vec<ColumnEntry>,
+    /// Placeholders of prepared statement, with the index of the column bound to it
+    /// once its type is inferred.
+    placeholders: BTreeMap<usize, Option<IndexType>>,
+    /// True if a placeholder is used before its type is inferred.
+    pending_placeholder_usage: bool,
 }
 
 impl Metadata {
@@ -99,6 +105,34 @@ impl Metadata {
         column_index
     }
 
+    pub fn placeholders(&self) -> &BTreeMap<usize, Option<IndexType>> {
+        &self.placeholders
+    }
+
+    pub fn has_pending_placeholder_usage(&self) -> bool {
+        self.pending_placeholder_usage
+    }
+
+    /// Add a placeholder such as `$1`, the type of it is inferred from the usage context.
+    /// The column of a placeholder is added on the first usage that can infer its type,
+    /// and all the usages will share it.
+    pub fn add_placeholder(
+        &mut self,
+        index: usize,
+        data_type: Option<DataTypeImpl>,
+    ) -> Option<IndexType> {
+        if let Some(Some(column_index)) = self.placeholders.get(&index) {
+            return Some(*column_index);
+        }
+        let column_index =
+            data_type.map(|data_type| self.add_column(format!("${index}"), data_type, None, None));
+        if column_index.is_none() {
+            self.pending_placeholder_usage = true;
+        }
+        self.placeholders.insert(index, column_index);
+        column_index
+    }
+
     pub fn add_table(
         &mut self,
         catalog: String,
@@ -22,6 +22,7 @@ pub struct AlterViewPlan {
     pub catalog: String,
     pub database: String,
     pub viewname: String,
+    pub column_names: Vec<String>,
     pub subquery: String,
 }
 
@@ -18,6 +18,7 @@ mod suites;
 
 criterion_main! {
     suites::bench_aggregate_query_sql::benches,
+    suites::bench_aggregator_block::benches,
     suites::bench_filter_query_sql::benches,
     suites::bench_limit_query_sql::benches,
     suites::bench_sort_query_sql::benches,
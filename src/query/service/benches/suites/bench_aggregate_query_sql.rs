@@ -17,6 +17,7 @@ use criterion::criterion_main;
 use criterion::Criterion;
 
 use crate::suites::criterion_benchmark_suite;
+use crate::suites::criterion_benchmark_suite_with_settings;
 
 fn criterion_benchmark_aggregate_query(c: &mut Criterion) {
     let queries = vec![
@@ -34,6 +35,22 @@ fn criterion_benchmark_aggregate_query(c: &mut Criterion) {
     for query in queries {
         criterion_benchmark_suite(c, query);
     }
+
+    // Same number of groups, repeated keys clustered vs scattered within a block,
+    // with and without pre-grouping the rows of each block by key.
+    let group_by_queries = vec![
+        "SELECT COUNT(number) FROM numbers_mt(1000000) GROUP BY number/1000",
+        "SELECT COUNT(number) FROM numbers_mt(1000000) GROUP BY number%1000",
+    ];
+
+    for query in group_by_queries {
+        for fold in ["0", "1"] {
+            criterion_benchmark_suite_with_settings(c, query, &[(
+                "group_by_fold_repeated_keys",
+                fold,
+            )]);
+        }
+    }
 }
 
 criterion_group!(benches, criterion_benchmark_aggregate_query);
@@ -0,0 +1,146 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_base::base::tokio;
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_functions::aggregates::AggregateFunctionFactory;
+use criterion::criterion_group;
+use criterion::criterion_main;
+use criterion::Criterion;
+use databend_query::pipelines::processors::connect;
+use databend_query::pipelines::processors::port::InputPort;
+use databend_query::pipelines::processors::port::OutputPort;
+use databend_query::pipelines::processors::processor::Event;
+use databend_query::pipelines::processors::AggregatorParams;
+use databend_query::pipelines::processors::AggregatorTransformParams;
+use databend_query::pipelines::processors::TransformAggregator;
+use databend_query::sessions::QueryContext;
+use databend_query::sessions::SessionManager;
+use databend_query::sessions::SessionType;
+use databend_query::sessions::TableContext;
+use databend_query::Config;
+use databend_query::GlobalServices;
+
+async fn create_query_context(settings: &[(&str, &str)]) -> Result<Arc<QueryContext>> {
+    GlobalServices::init(Config::default()).await?;
+    let sessions = SessionManager::instance();
+    let executor_session = sessions.create_session(SessionType::Dummy).await?;
+    let ctx = executor_session.create_query_context().await?;
+    for (key, value) in settings {
+        ctx.get_settings()
+            .set_settings(key.to_string(), value.to_string(), false)?;
+    }
+    Ok(ctx)
+}
+
+/// Push the block through a partial aggregator and drain the generated blocks.
+fn partial_aggregate(
+    ctx: &Arc<QueryContext>,
+    params: &Arc<AggregatorParams>,
+    block: DataBlock,
+) -> Result<()> {
+    let input = InputPort::create();
+    let output = OutputPort::create();
+    let transform = TransformAggregator::try_create_partial(
+        input.clone(),
+        output.clone(),
+        AggregatorTransformParams::try_create(input.clone(), output.clone(), params)?,
+        ctx.clone(),
+    )?;
+
+    let upstream = OutputPort::create();
+    let downstream = InputPort::create();
+    let mut block = Some(block);
+    unsafe {
+        connect(&input, &upstream);
+        connect(&downstream, &output);
+
+        downstream.set_need_data();
+        loop {
+            match transform.event()? {
+                Event::NeedData => match block.take() {
+                    Some(block) => upstream.push_data(Ok(block)),
+                    None => upstream.finish(),
+                },
+                Event::Sync => transform.process()?,
+                Event::NeedConsume => {
+                    if let Some(block) = downstream.pull_data() {
+                        block?;
+                    }
+                    downstream.set_need_data();
+                }
+                Event::Finished => return Ok(()),
+                event => {
+                    return Err(ErrorCode::LogicalError(format!(
+                        "Unexpected event {:?}",
+                        event
+                    )));
+                }
+            }
+        }
+    }
+}
+
+fn criterion_benchmark_aggregator_block(c: &mut Criterion) {
+    // select k, count() from t group by k, on a block of 65536 rows with 64 distinct keys
+    // scattered over the block.
+    let input_schema = DataSchemaRefExt::create(vec![DataField::new("k", u64::to_data_type())]);
+    let partial_schema = DataSchemaRefExt::create(vec![
+        DataField::new("count()", Vu8::to_data_type()),
+        DataField::new("_group_by_key", u64::to_data_type()),
+        DataField::new("_group_by_bucket", i64::to_data_type()),
+    ]);
+    let keys = (0..65536u64).map(|i| i * 7 % 64).collect::<Vec<_>>();
+    let block = DataBlock::create(input_schema.clone(), vec![Series::from_data(keys)]);
+
+    let count = AggregateFunctionFactory::instance()
+        .get("count", vec![], vec![])
+        .unwrap();
+    let params = AggregatorParams::try_create(
+        partial_schema,
+        input_schema,
+        &[0],
+        &[count],
+        &["count()".to_string()],
+        &[vec![]],
+        "auto",
+        0,
+    )
+    .unwrap();
+
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    for fold in ["0", "1"] {
+        let ctx = runtime
+            .block_on(create_query_context(&[(
+                "group_by_fold_repeated_keys",
+                fold,
+            )]))
+            .unwrap();
+
+        let name = format!(
+            "partial aggregate a block with repeated keys [group_by_fold_repeated_keys={fold}]"
+        );
+        c.bench_function(&name, |b| {
+            b.iter(|| partial_aggregate(&ctx, &params, block.clone()).unwrap())
+        });
+    }
+}
+
+criterion_group!(benches, criterion_benchmark_aggregator_block);
+criterion_main!(benches);
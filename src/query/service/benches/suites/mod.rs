@@ -26,15 +26,20 @@ use databend_query::GlobalServices;
 use futures_util::StreamExt;
 
 pub mod bench_aggregate_query_sql;
+pub mod bench_aggregator_block;
 pub mod bench_filter_query_sql;
 pub mod bench_limit_query_sql;
 pub mod bench_sort_query_sql;
 
-pub async fn select_executor(sql: &str) -> Result<()> {
+pub async fn select_executor(sql: &str, settings: &[(&str, &str)]) -> Result<()> {
     GlobalServices::init(Config::default()).await?;
     let sessions = SessionManager::instance();
     let executor_session = sessions.create_session(SessionType::Dummy).await?;
     let ctx = executor_session.create_query_context().await?;
+    for (key, value) in settings {
+        ctx.get_settings()
+            .set_settings(key.to_string(), value.to_string(), false)?;
+    }
     let mut planner = Planner::new(ctx.clone());
 
     let (plan, _, _) = planner.plan_sql(sql).await?;
@@ -56,11 +61,24 @@ pub async fn select_executor(sql: &str) -> Result<()> {
 }
 
 pub fn criterion_benchmark_suite(c: &mut Criterion, sql: &str) {
-    c.bench_function(sql, |b| {
+    criterion_benchmark_suite_with_settings(c, sql, &[]);
+}
+
+/// Benchmark the query with the given session settings, which are appended to its name.
+pub fn criterion_benchmark_suite_with_settings(
+    c: &mut Criterion,
+    sql: &str,
+    settings: &[(&str, &str)],
+) {
+    let name = settings.iter().fold(sql.to_string(), |name, (key, value)| {
+        format!("{name} [{key}={value}]")
+    });
+
+    c.bench_function(&name, |b| {
         b.iter(|| {
             tokio::runtime::Runtime::new()
                 .unwrap()
-                .block_on(select_executor(sql))
+                .block_on(select_executor(sql, settings))
         })
     });
 }
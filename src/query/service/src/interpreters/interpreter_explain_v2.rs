@@ -30,6 +30,7 @@ use crate::sql::executor::PhysicalPlanBuilder;
 use crate::sql::executor::PipelineBuilder;
 use crate::sql::optimizer::SExpr;
 use crate::sql::plans::Plan;
+use crate::sql::BindContext;
 
 pub struct ExplainInterpreterV2 {
     ctx: Arc<QueryContext>,
@@ -86,6 +87,12 @@ impl Interpreter for ExplainInterpreterV2 {
                     return Err(ErrorCode::UnImplement("Unsupported EXPLAIN statement"));
                 }
             },
+            ExplainKind::Lineage => match &self.plan {
+                Plan::Query { bind_context, .. } => self.explain_lineage(bind_context)?,
+                _ => {
+                    return Err(ErrorCode::UnImplement("Unsupported EXPLAIN statement"));
+                }
+            },
             ExplainKind::Graph => {
                 return Err(ErrorCode::UnImplement("ExplainKind graph is unimplemented"));
             }
@@ -124,6 +131,22 @@ impl ExplainInterpreterV2 {
         ])])
     }
 
+    /// Print where each output column comes from, one column per line.
+    pub fn explain_lineage(&self, bind_context: &BindContext) -> Result<Vec<DataBlock>> {
+        let lines = bind_context
+            .result_column_bindings()
+            .iter()
+            .map(|column| match &column.lineage {
+                Some(lineage) => format!("{}: {}", column.column_name, lineage),
+                None => format!("{}: unknown", column.column_name),
+            })
+            .collect::<Vec<_>>();
+        let formatted_lineage = Series::from_data(lines);
+        Ok(vec![DataBlock::create(self.schema.clone(), vec![
+            formatted_lineage,
+        ])])
+    }
+
     pub fn explain_physical_plan(
         &self,
         plan: &PhysicalPlan,
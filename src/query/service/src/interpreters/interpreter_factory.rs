@@ -52,12 +52,15 @@ impl InterpreterFactory {
                 bind_context,
                 metadata,
                 ..
-            } => Ok(Arc::new(SelectInterpreterV2::try_create(
-                ctx,
-                *bind_context.clone(),
-                *s_expr.clone(),
-                metadata.clone(),
-            )?)),
+            } => Ok(Arc::new(
+                SelectInterpreterV2::try_create(
+                    ctx,
+                    *bind_context.clone(),
+                    *s_expr.clone(),
+                    metadata.clone(),
+                )?
+                .with_result_rows_limit(),
+            )),
             Plan::Explain { kind, plan } => Ok(Arc::new(ExplainInterpreterV2::try_create(
                 ctx,
                 *plan.clone(),
@@ -159,7 +159,10 @@ impl Interpreter for InsertInterpreterV2 {
                         } => {
                             let builder1 =
                                 PhysicalPlanBuilder::new(metadata.clone(), self.ctx.clone());
-                            (builder1.build(s_expr).await?, bind_context.columns.clone())
+                            (
+                                builder1.build(s_expr).await?,
+                                bind_context.result_column_bindings(),
+                            )
                         }
                         _ => unreachable!(),
                     };
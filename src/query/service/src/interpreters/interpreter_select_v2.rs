@@ -34,6 +34,7 @@ pub struct SelectInterpreterV2 {
     s_expr: SExpr,
     bind_context: BindContext,
     metadata: MetadataRef,
+    limit_result_rows: bool,
 }
 
 impl SelectInterpreterV2 {
@@ -48,9 +49,17 @@ impl SelectInterpreterV2 {
             s_expr,
             bind_context,
             metadata,
+            limit_result_rows: false,
         })
     }
 
+    /// Cap the rows of the result set sent to the client with `max_result_rows`.
+    /// Not for COPY or INSERT reusing the query pipeline, they must see every row.
+    pub fn with_result_rows_limit(mut self) -> Self {
+        self.limit_result_rows = true;
+        self
+    }
+
     pub async fn build_pipeline(&self) -> Result<PipelineBuildResult> {
         let builder = PhysicalPlanBuilder::new(self.metadata.clone(), self.ctx.clone());
         let physical_plan = builder.build(&self.s_expr).await?;
@@ -64,14 +73,26 @@ impl SelectInterpreterV2 {
             PipelineBuilder::render_result_set(
                 &self.ctx.try_get_function_context()?,
                 last_schema,
-                &self.bind_context.columns,
+                &self.bind_context.result_column_bindings(),
                 &mut build_res.main_pipeline,
             )?;
+            if self.limit_result_rows {
+                PipelineBuilder::limit_result_rows(&self.ctx, &mut build_res.main_pipeline)?;
+            }
 
             build_res.set_max_threads(self.ctx.get_settings().get_max_threads()? as usize);
             Ok(build_res)
         } else {
-            schedule_query_v2(self.ctx.clone(), &self.bind_context.columns, &physical_plan).await
+            let mut build_res = schedule_query_v2(
+                self.ctx.clone(),
+                &self.bind_context.result_column_bindings(),
+                &physical_plan,
+            )
+            .await?;
+            if self.limit_result_rows {
+                PipelineBuilder::limit_result_rows(&self.ctx, &mut build_res.main_pipeline)?;
+            }
+            Ok(build_res)
         }
     }
 }
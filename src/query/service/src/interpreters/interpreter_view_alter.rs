@@ -27,6 +27,7 @@ use crate::interpreters::Interpreter;
 use crate::pipelines::PipelineBuildResult;
 use crate::sessions::QueryContext;
 use crate::sessions::TableContext;
+use crate::storages::view::view_table::COLUMN_NAMES;
 use crate::storages::view::view_table::VIEW_ENGINE;
 
 pub struct AlterViewInterpreter {
@@ -87,6 +88,12 @@ impl AlterViewInterpreter {
         // create new view
         let mut options = BTreeMap::new();
         options.insert("query".to_string(), self.plan.subquery.clone());
+        if !self.plan.column_names.is_empty() {
+            options.insert(
+                COLUMN_NAMES.to_string(),
+                serde_json::to_string(&self.plan.column_names)?,
+            );
+        }
         let plan = CreateTableReq {
             if_not_exists: true,
             name_ident: TableNameIdent {
@@ -26,6 +26,7 @@ use crate::interpreters::Interpreter;
 use crate::pipelines::PipelineBuildResult;
 use crate::sessions::QueryContext;
 use crate::sessions::TableContext;
+use crate::storages::view::view_table::COLUMN_NAMES;
 use crate::storages::view::view_table::VIEW_ENGINE;
 
 pub struct CreateViewInterpreter {
@@ -70,6 +71,12 @@ impl CreateViewInterpreter {
         let catalog = self.ctx.get_catalog(&self.plan.catalog)?;
         let mut options = BTreeMap::new();
         options.insert("query".to_string(), self.plan.subquery.clone());
+        if !self.plan.column_names.is_empty() {
+            options.insert(
+                COLUMN_NAMES.to_string(),
+                serde_json::to_string(&self.plan.column_names)?,
+            );
+        }
         let plan = CreateTableReq {
             if_not_exists: self.plan.if_not_exists,
             name_ident: TableNameIdent {
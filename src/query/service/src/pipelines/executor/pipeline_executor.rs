@@ -179,9 +179,10 @@ impl PipelineExecutor {
                 let finished_future = Box::pin(this.finished_notify.notified());
                 let max_execute_future = Box::pin(tokio::time::sleep(max_execute_time));
                 if let Either::Left(_) = select(max_execute_future, finished_future).await {
-                    this.finish(Some(ErrorCode::AbortedQuery(
-                        "Aborted query, because the execution time exceeds the maximum execution time limit",
-                    )));
+                    this.finish(Some(ErrorCode::AbortedQuery(format!(
+                        "Aborted query, because the execution time exceeds the maximum execution time limit ({}ms)",
+                        max_execute_time.as_millis()
+                    ))));
                 }
             });
         }
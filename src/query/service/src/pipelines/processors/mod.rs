@@ -32,8 +32,12 @@ pub use sources::EmptySource;
 pub use sources::StreamSource;
 pub use sources::SyncSource;
 pub use sources::SyncSourcer;
+pub use transforms::deserialize_state_version;
+pub use transforms::serialize_state_version;
 pub use transforms::AggregatorParams;
+pub use transforms::AggregatorSpiller;
 pub use transforms::AggregatorTransformParams;
+pub use transforms::AGGREGATE_STATE_VERSION;
 pub use transforms::BlockCompactor;
 pub use transforms::ExpressionTransform;
 pub use transforms::HashJoinDesc;
@@ -56,11 +60,14 @@ pub use transforms::SortMergeCompactor;
 pub use transforms::TransformAddOn;
 pub use transforms::TransformAggregator;
 pub use transforms::TransformBlockCompact;
+pub use transforms::TransformBucketRouter;
 pub use transforms::TransformCastSchema;
 pub use transforms::TransformCompact;
 pub use transforms::TransformCreateSets;
 pub use transforms::TransformDummy;
 pub use transforms::TransformHashJoinProbe;
 pub use transforms::TransformLimit;
+pub use transforms::TransformLimitWithTies;
+pub use transforms::TransformMaxResultRows;
 pub use transforms::TransformSortMerge;
 pub use transforms::TransformSortPartial;
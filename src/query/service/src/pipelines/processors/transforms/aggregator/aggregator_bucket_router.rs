@@ -0,0 +1,132 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::pipelines::processors::port::InputPort;
+use crate::pipelines::processors::port::OutputPort;
+use crate::pipelines::processors::processor::Event;
+use crate::pipelines::processors::Processor;
+
+/// Route the blocks of the partial aggregators to the final aggregators by the hash table
+/// bucket in their last column, so that each group is merged by a single final aggregator.
+pub struct TransformBucketRouter {
+    inputs: Vec<Arc<InputPort>>,
+    outputs: Vec<Arc<OutputPort>>,
+
+    // The block pulled but not pushed yet, with the index of its output.
+    routed: Option<(usize, DataBlock)>,
+}
+
+impl TransformBucketRouter {
+    pub fn create(inputs: usize, outputs: usize) -> Self {
+        TransformBucketRouter {
+            inputs: (0..inputs).map(|_| InputPort::create()).collect(),
+            outputs: (0..outputs).map(|_| OutputPort::create()).collect(),
+            routed: None,
+        }
+    }
+
+    pub fn get_inputs(&self) -> &[Arc<InputPort>] {
+        &self.inputs
+    }
+
+    pub fn get_outputs(&self) -> &[Arc<OutputPort>] {
+        &self.outputs
+    }
+
+    fn route(&self, block: &DataBlock) -> Result<usize> {
+        let bucket = block.column(block.num_columns() - 1).get_i64(0)?;
+        match bucket < 0 {
+            true => Err(ErrorCode::LogicalError(
+                "Cannot route a partial aggregated block without bucket",
+            )),
+            false => Ok(bucket as usize % self.outputs.len()),
+        }
+    }
+
+    /// Push the block to its output, or keep it until the output can take it. The block is
+    /// dropped if the output is finished, `None` is returned to go on pulling the inputs.
+    fn push(&mut self, index: usize, block: DataBlock) -> Option<Event> {
+        let output = &self.outputs[index];
+        if output.is_finished() {
+            return None;
+        }
+
+        if output.can_push() {
+            output.push_data(Ok(block));
+        } else {
+            self.routed = Some((index, block));
+            self.inputs
+                .iter()
+                .for_each(|input| input.set_not_need_data());
+        }
+        Some(Event::NeedConsume)
+    }
+}
+
+#[async_trait::async_trait]
+impl Processor for TransformBucketRouter {
+    fn name(&self) -> &'static str {
+        "BucketRouter"
+    }
+
+    fn as_any(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn event(&mut self) -> Result<Event> {
+        if self.outputs.iter().all(|output| output.is_finished()) {
+            self.inputs.iter().for_each(|input| input.finish());
+            return Ok(Event::Finished);
+        }
+
+        if let Some((index, block)) = self.routed.take() {
+            if let Some(event) = self.push(index, block) {
+                return Ok(event);
+            }
+        }
+
+        let mut finished = true;
+        for index in 0..self.inputs.len() {
+            let input = &self.inputs[index];
+            if input.is_finished() {
+                continue;
+            }
+
+            finished = false;
+            input.set_need_data();
+            if let Some(data) = input.pull_data() {
+                let block = data?;
+                if block.num_rows() != 0 {
+                    let index = self.route(&block)?;
+                    if let Some(event) = self.push(index, block) {
+                        return Ok(event);
+                    }
+                }
+            }
+        }
+
+        if finished {
+            self.outputs.iter().for_each(|output| output.finish());
+            return Ok(Event::Finished);
+        }
+        Ok(Event::NeedData)
+    }
+}
@@ -34,11 +34,15 @@ use common_exception::Result;
 use common_functions::aggregates::StateAddr;
 use common_functions::aggregates::StateAddrs;
 
+use crate::pipelines::processors::transforms::aggregator::deserialize_state_version;
+use crate::pipelines::processors::transforms::aggregator::ReferenceAggregator;
 use crate::pipelines::processors::transforms::group_by::AggregatorState;
 use crate::pipelines::processors::transforms::group_by::GroupColumnsBuilder;
+use crate::pipelines::processors::transforms::group_by::KeysColumnBuilder;
 use crate::pipelines::processors::transforms::group_by::KeysColumnIter;
 use crate::pipelines::processors::transforms::group_by::PolymorphicKeysHelper;
 use crate::pipelines::processors::transforms::group_by::StateEntity;
+use crate::pipelines::processors::transforms::group_by::StateEntityIterator;
 use crate::pipelines::processors::transforms::transform_aggregator::Aggregator;
 use crate::pipelines::processors::AggregatorParams;
 use crate::sessions::QueryContext;
@@ -62,7 +66,6 @@ pub struct FinalAggregator<
     const HAS_AGG: bool,
     Method: HashMethod + PolymorphicKeysHelper<Method> + Send,
 > {
-    is_generated: bool,
     states_dropped: bool,
 
     method: Method,
@@ -70,6 +73,14 @@ pub struct FinalAggregator<
     params: Arc<AggregatorParams>,
     // used for deserialization only, so we can reuse it during the loop
     temp_place: Option<StateAddr>,
+    // only present if enable_aggregator_self_check is set
+    reference: Option<ReferenceAggregator>,
+    // The state values in the order their keys are inserted, see `group_by_first_seen_order`.
+    first_seen: Option<Vec<usize>>,
+    // The entities not generated yet, iterated from the first `generate`.
+    pending_entities:
+        Option<StateEntityIterator<<Method::State as AggregatorState<Method>>::Iterator>>,
+    max_block_size: usize,
     ctx: Arc<QueryContext>,
 }
 
@@ -88,13 +99,27 @@ impl<const HAS_AGG: bool, Method: HashMethod + PolymorphicKeysHelper<Method> + S
             state.alloc_layout(&params)
         };
 
+        let reference = match ctx.get_settings().get_enable_aggregator_self_check()? {
+            true => ReferenceAggregator::try_create(params.clone()),
+            false => None,
+        };
+
+        let first_seen = match ctx.get_settings().get_group_by_first_seen_order()? {
+            true => Some(vec![]),
+            false => None,
+        };
+        let max_block_size = ctx.get_settings().get_max_block_size()? as usize;
+
         Ok(Self {
-            is_generated: false,
             states_dropped: false,
             state,
             method,
             params,
             temp_place,
+            reference,
+            first_seen,
+            pending_entities: None,
+            max_block_size: max_block_size.max(1),
             ctx,
         })
     }
@@ -107,6 +132,7 @@ impl<Method: HashMethod + PolymorphicKeysHelper<Method> + Send> FinalAggregator<
         params: &AggregatorParams,
         state: &mut Method::State,
         keys: &[<Method::State as AggregatorState<Method>>::Key],
+        mut first_seen: Option<&mut Vec<usize>>,
     ) -> StateAddrs {
         let mut places = Vec::with_capacity(keys.len());
 
@@ -119,6 +145,9 @@ impl<Method: HashMethod + PolymorphicKeysHelper<Method> + Send> FinalAggregator<
                     if let Some(place) = state.alloc_layout(params) {
                         places.push(place);
                         entity.set_state_value(place.addr());
+                        if let Some(first_seen) = first_seen.as_mut() {
+                            first_seen.push(place.addr());
+                        }
                     }
                 }
                 false => {
@@ -149,7 +178,12 @@ impl<Method: HashMethod + PolymorphicKeysHelper<Method> + Send> Aggregator
         }
 
         // first state places of current block
-        let places = Self::lookup_state(&self.params, &mut self.state, keys_iter.get_slice());
+        let places = Self::lookup_state(
+            &self.params,
+            &mut self.state,
+            keys_iter.get_slice(),
+            self.first_seen.as_mut(),
+        );
 
         let states_columns = (0..aggregate_function_len)
             .map(|i| block.column(i))
@@ -170,25 +204,42 @@ impl<Method: HashMethod + PolymorphicKeysHelper<Method> + Send> Aggregator
                     let state_place = temp_place.next(offsets_aggregate_states[idx]);
 
                     let mut data = states_binary_columns[idx].get_data(row);
+                    deserialize_state_version(&mut data)?;
                     aggregate_function.deserialize(state_place, &mut data)?;
                     aggregate_function.merge(final_place, state_place)?;
                 }
             }
         }
+
+        if let Some(reference) = self.reference.as_mut() {
+            reference.consume(&block)?;
+        }
         Ok(())
     }
 
     fn generate(&mut self) -> Result<Option<DataBlock>> {
-        match self.state.len() == 0 || self.is_generated {
+        let entities = self.state.next_entities(
+            &mut self.pending_entities,
+            self.first_seen.as_deref(),
+            self.max_block_size,
+        );
+        match entities.is_empty() {
             true => {
+                if let (false, Some(reference)) = (self.states_dropped, &self.reference) {
+                    reference.verify()?;
+                }
+
                 self.drop_states();
                 Ok(None)
             }
             false => {
-                self.is_generated = true;
                 let mut group_columns_builder = self
                     .method
-                    .group_columns_builder(self.state.len(), &self.params);
+                    .group_columns_builder(entities.len(), &self.params);
+                let mut keys_column_builder = self
+                    .reference
+                    .as_ref()
+                    .map(|_| self.method.keys_column_builder(entities.len()));
 
                 let aggregate_functions = &self.params.aggregate_functions;
                 let offsets_aggregate_states = &self.params.offsets_aggregate_states;
@@ -202,7 +253,7 @@ impl<Method: HashMethod + PolymorphicKeysHelper<Method> + Send> Aggregator
                     values
                 };
 
-                for group_entity in self.state.iter() {
+                for group_entity in entities {
                     let place: StateAddr = (*group_entity.get_state_value()).into();
 
                     for (idx, aggregate_function) in aggregate_functions.iter().enumerate() {
@@ -213,6 +264,9 @@ impl<Method: HashMethod + PolymorphicKeysHelper<Method> + Send> Aggregator
                     }
 
                     group_columns_builder.append_value(group_entity.get_state_key());
+                    if let Some(keys_column_builder) = keys_column_builder.as_mut() {
+                        keys_column_builder.append_value(group_entity.get_state_key());
+                    }
                 }
 
                 // Build final state block.
@@ -223,6 +277,12 @@ impl<Method: HashMethod + PolymorphicKeysHelper<Method> + Send> Aggregator
                     columns.push(array.to_column());
                 }
 
+                if let (Some(reference), Some(keys_column_builder)) =
+                    (self.reference.as_mut(), keys_column_builder)
+                {
+                    reference.verify_block(&[keys_column_builder.finish()], &columns)?;
+                }
+
                 columns.extend_from_slice(&group_columns_builder.finish()?);
                 Ok(Some(DataBlock::create(
                     self.params.output_schema.clone(),
@@ -250,21 +310,31 @@ impl<Method: HashMethod + PolymorphicKeysHelper<Method> + Send> Aggregator
 
         let mut inserted = true;
         for keys_ref in keys_iter.get_slice() {
-            self.state.entity_by_key(keys_ref, &mut inserted);
+            let entity = self.state.entity_by_key(keys_ref, &mut inserted);
+
+            // There is no aggregate state, use the insertion index as the state value.
+            if let (true, Some(first_seen)) = (inserted, self.first_seen.as_mut()) {
+                entity.set_state_value(first_seen.len());
+                first_seen.push(first_seen.len());
+            }
         }
 
         Ok(())
     }
 
     fn generate(&mut self) -> Result<Option<DataBlock>> {
-        match self.state.len() == 0 || self.is_generated {
+        let entities = self.state.next_entities(
+            &mut self.pending_entities,
+            self.first_seen.as_deref(),
+            self.max_block_size,
+        );
+        match entities.is_empty() {
             true => Ok(None),
             false => {
-                self.is_generated = true;
                 let mut columns_builder = self
                     .method
-                    .group_columns_builder(self.state.len(), &self.params);
-                for group_entity in self.state.iter() {
+                    .group_columns_builder(entities.len(), &self.params);
+                for group_entity in entities {
                     columns_builder.append_value(group_entity.get_state_key());
                 }
 
@@ -31,6 +31,10 @@ pub struct AggregatorParams {
     pub input_schema: DataSchemaRef,
     pub group_columns: Vec<usize>,
     pub group_data_types: Vec<DataTypeImpl>,
+    /// Name of the hash method for group keys, "auto" to choose it by the key types.
+    pub hash_method: String,
+    /// Spill the partial hash table once it takes more bytes than this, 0 never spills.
+    pub spill_threshold_bytes: usize,
 
     pub aggregate_functions: Vec<AggregateFunctionRef>,
     pub aggregate_functions_column_name: Vec<String>,
@@ -50,6 +54,8 @@ impl AggregatorParams {
         agg_funcs: &[AggregateFunctionRef],
         agg_output_names: &[String],
         agg_args: &[Vec<usize>],
+        hash_method: &str,
+        spill_threshold_bytes: usize,
     ) -> Result<Arc<AggregatorParams>> {
         let mut states_offsets: Vec<usize> = Vec::with_capacity(agg_funcs.len());
         let mut states_layout = None;
@@ -67,6 +73,8 @@ impl AggregatorParams {
             input_schema,
             group_columns: group_columns.to_vec(),
             group_data_types,
+            hash_method: hash_method.to_string(),
+            spill_threshold_bytes,
             aggregate_functions: agg_funcs.to_vec(),
             aggregate_functions_column_name: agg_output_names.to_vec(),
             aggregate_functions_arguments: agg_args.to_vec(),
@@ -89,10 +97,10 @@ impl AggregatorTransformParams {
         transform_output_port: Arc<OutputPort>,
         aggregator_params: &Arc<AggregatorParams>,
     ) -> Result<AggregatorTransformParams> {
-        let group_cols = &aggregator_params.group_columns;
-        let schema_before_group_by = aggregator_params.input_schema.clone();
-        let sample_block = DataBlock::empty_with_schema(schema_before_group_by);
-        let method = DataBlock::choose_hash_method(&sample_block, group_cols)?;
+        let method = DataBlock::choose_hash_method_with_name(
+            &aggregator_params.group_data_types,
+            &aggregator_params.hash_method,
+        )?;
 
         Ok(AggregatorTransformParams {
             method,
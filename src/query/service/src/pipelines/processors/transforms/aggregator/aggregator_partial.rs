@@ -12,6 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::collections::VecDeque;
 use std::sync::Arc;
 
 use bytes::BytesMut;
@@ -29,14 +32,27 @@ use common_datavalues::ColumnRef;
 use common_datavalues::MutableColumn;
 use common_datavalues::MutableStringColumn;
 use common_datavalues::ScalarColumnBuilder;
+use common_datavalues::Series;
+use common_datavalues::SeriesFrom;
+use common_datavalues::StringColumn;
 use common_exception::Result;
 use common_functions::aggregates::StateAddr;
 use common_functions::aggregates::StateAddrs;
-
+use common_hashtable::two_level_bucket;
+use common_hashtable::HashTableKeyable;
+use itertools::Either;
+
+use crate::pipelines::processors::transforms::aggregator::deserialize_state_version;
+use crate::pipelines::processors::transforms::aggregator::serialize_state_version;
+use crate::pipelines::processors::transforms::aggregator::AggregatorSpiller;
+use crate::pipelines::processors::transforms::aggregator::ReferenceAggregator;
 use crate::pipelines::processors::transforms::group_by::AggregatorState;
+use crate::pipelines::processors::transforms::group_by::GroupColumnsBuilder;
 use crate::pipelines::processors::transforms::group_by::KeysColumnBuilder;
+use crate::pipelines::processors::transforms::group_by::KeysColumnIter;
 use crate::pipelines::processors::transforms::group_by::PolymorphicKeysHelper;
 use crate::pipelines::processors::transforms::group_by::StateEntity;
+use crate::pipelines::processors::transforms::group_by::StateEntityIterator;
 use crate::pipelines::processors::transforms::transform_aggregator::Aggregator;
 use crate::pipelines::processors::AggregatorParams;
 use crate::sessions::QueryContext;
@@ -66,67 +82,304 @@ pub struct PartialAggregator<
     const HAS_AGG: bool,
     Method: HashMethod + PolymorphicKeysHelper<Method>,
 > {
-    is_generated: bool,
     states_dropped: bool,
 
     method: Method,
     state: Method::State,
     params: Arc<AggregatorParams>,
     ctx: Arc<QueryContext>,
+    // The state values in the order their keys are inserted, see `group_by_first_seen_order`.
+    first_seen: Option<Vec<usize>>,
+    // The entities not generated yet, iterated from the first `generate`.
+    pending_entities:
+        Option<StateEntityIterator<<Method::State as AggregatorState<Method>>::Iterator>>,
+    // The entities not generated yet by hash table bucket, unless the first seen order is kept.
+    pending_buckets: Option<
+        VecDeque<(
+            usize,
+            StateEntityIterator<<Method::State as AggregatorState<Method>>::Iterator>,
+        )>,
+    >,
+    // The blocks of the bucket merged with its spilled blocks, not generated yet.
+    pending_blocks: VecDeque<DataBlock>,
+    max_block_size: usize,
+    // Group the rows of each block by key before looking up the state, see `lookup_state`.
+    fold_repeated_keys: bool,
+    // The hash tables spilled once they outgrew `spill_threshold_bytes`.
+    spiller: AggregatorSpiller,
+    // Accumulates the raw input naively, see `enable_aggregator_self_check`.
+    reference: Option<ReferenceAggregator>,
 }
 
 impl<const HAS_AGG: bool, Method: HashMethod + PolymorphicKeysHelper<Method> + Send>
     PartialAggregator<HAS_AGG, Method>
 {
-    pub fn create(ctx: Arc<QueryContext>, method: Method, params: Arc<AggregatorParams>) -> Self {
+    pub fn create(
+        ctx: Arc<QueryContext>,
+        method: Method,
+        params: Arc<AggregatorParams>,
+    ) -> Result<Self> {
         let state = method.aggregate_state();
-        Self {
-            is_generated: false,
+        let first_seen = match ctx.get_settings().get_group_by_first_seen_order()? {
+            true => Some(vec![]),
+            false => None,
+        };
+        let max_block_size = ctx.get_settings().get_max_block_size()? as usize;
+        let fold_repeated_keys = ctx.get_settings().get_group_by_fold_repeated_keys()?;
+        let spill_dir = ctx.get_config().query.spill_dir;
+        let spiller = AggregatorSpiller::create(params.output_schema.clone(), spill_dir);
+        let reference = match ctx.get_settings().get_enable_aggregator_self_check()? {
+            true => ReferenceAggregator::try_create(params.clone()),
+            false => None,
+        };
+
+        Ok(Self {
             states_dropped: false,
             state,
             method,
             params,
             ctx,
+            first_seen,
+            pending_entities: None,
+            pending_buckets: None,
+            pending_blocks: VecDeque::new(),
+            max_block_size: max_block_size.max(1),
+            fold_repeated_keys,
+            spiller,
+            reference,
+        })
+    }
+
+    /// Write the hash table out to a spill file once it takes more than
+    /// `spill_threshold_bytes`, and go on with an empty one.
+    fn spill_if_needed(&mut self) -> Result<()> {
+        let threshold = self.params.spill_threshold_bytes;
+        if threshold == 0 || self.state.allocated_bytes() < threshold {
+            return Ok(());
+        }
+
+        for (bucket, entities) in self.bucket_entities() {
+            for entities in entities.chunks(self.max_block_size) {
+                let block = self.build_block(entities, bucket as i64)?;
+                self.spiller.spill(bucket, block)?;
+            }
+        }
+        self.spiller.finish_file()?;
+
+        self.drop_states();
+        self.states_dropped = false;
+        self.state = self.method.aggregate_state();
+        self.pending_entities = None;
+        self.pending_buckets = None;
+        if let Some(first_seen) = self.first_seen.as_mut() {
+            first_seen.clear();
+        }
+        Ok(())
+    }
+
+    /// Partition the groups by the buckets of the two-level hash table.
+    fn bucket_entities(
+        &self,
+    ) -> BTreeMap<usize, Vec<*mut <Method::State as AggregatorState<Method>>::Entity>> {
+        let mut buckets = BTreeMap::<usize, Vec<_>>::new();
+        for entity in self.state.iter() {
+            let bucket = two_level_bucket(entity.get_state_key().fast_hash());
+            buckets.entry(bucket).or_default().push(entity);
+        }
+        buckets
+    }
+
+    /// Merge the spilled blocks of a bucket and the in-memory groups of the same bucket into
+    /// a single hash table, so that each group of the bucket is generated once.
+    fn merge_bucket(
+        &self,
+        bucket: usize,
+        spilled: Vec<DataBlock>,
+        entities: &[*mut <Method::State as AggregatorState<Method>>::Entity],
+    ) -> Result<VecDeque<DataBlock>> {
+        let mut state = self.method.aggregate_state();
+        let temp_place = state.alloc_layout(&self.params);
+        let blocks = self.merge_into(&mut state, temp_place, bucket, spilled, entities);
+
+        // The merged states are dropped even if merging failed.
+        let places = state
+            .iter()
+            .map(|entity| StateAddr::from(*entity.get_state_value()));
+        Self::drop_places(&self.params, places.chain(temp_place));
+        blocks
+    }
+
+    fn merge_into(
+        &self,
+        state: &mut Method::State,
+        temp_place: Option<StateAddr>,
+        bucket: usize,
+        spilled: Vec<DataBlock>,
+        entities: &[*mut <Method::State as AggregatorState<Method>>::Entity],
+    ) -> Result<VecDeque<DataBlock>> {
+        let aggregate_functions = &self.params.aggregate_functions;
+        let offsets_aggregate_states = &self.params.offsets_aggregate_states;
+
+        for block in &spilled {
+            let keys_column = block.column(aggregate_functions.len());
+            let keys_iter = self.method.keys_iter_from_column(keys_column)?;
+            let states_columns = (0..aggregate_functions.len())
+                .map(|idx| Series::check_get::<StringColumn>(block.column(idx)))
+                .collect::<Result<Vec<_>>>()?;
+
+            for (row, key) in keys_iter.get_slice().iter().enumerate() {
+                // Without aggregate functions, only the key is inserted.
+                let place = Self::merge_place(&self.params, state, key);
+                if let (Some(place), Some(temp_place)) = (place, temp_place) {
+                    for (idx, aggregate_function) in aggregate_functions.iter().enumerate() {
+                        let final_place = place.next(offsets_aggregate_states[idx]);
+                        let state_place = temp_place.next(offsets_aggregate_states[idx]);
+
+                        let mut data = states_columns[idx].get_data(row);
+                        deserialize_state_version(&mut data)?;
+                        aggregate_function.deserialize(state_place, &mut data)?;
+                        aggregate_function.merge(final_place, state_place)?;
+                    }
+                }
+            }
+        }
+
+        for entity in entities.iter().copied() {
+            if let Some(place) = Self::merge_place(&self.params, state, entity.get_state_key()) {
+                let memory_place: StateAddr = (*entity.get_state_value()).into();
+                for (idx, aggregate_function) in aggregate_functions.iter().enumerate() {
+                    let offset = offsets_aggregate_states[idx];
+                    aggregate_function.merge(place.next(offset), memory_place.next(offset))?;
+                }
+            }
         }
+
+        let merged = state.iter().collect::<Vec<_>>();
+        merged
+            .chunks(self.max_block_size)
+            .map(|entities| self.build_block(entities, bucket as i64))
+            .collect()
     }
 
     #[inline(always)]
-    fn lookup_key(keys_iter: Method::HashKeyIter<'_>, state: &mut Method::State) {
+    fn merge_place(
+        params: &Arc<AggregatorParams>,
+        state: &mut Method::State,
+        key: &<Method::State as AggregatorState<Method>>::Key,
+    ) -> Option<StateAddr> {
         let mut inserted = true;
-        for key in keys_iter {
-            state.entity(key, &mut inserted);
+        let entity = state.entity_by_key(key, &mut inserted);
+
+        match inserted {
+            true => {
+                let place = state.alloc_layout(params)?;
+                entity.set_state_value(place.addr());
+                Some(place)
+            }
+            false => Some((*entity.get_state_value()).into()),
+        }
+    }
+
+    #[inline(always)]
+    fn lookup_key(
+        keys_iter: Method::HashKeyIter<'_>,
+        state: &mut Method::State,
+        first_seen: Option<&mut Vec<usize>>,
+    ) {
+        let mut inserted = true;
+        match first_seen {
+            None => {
+                for key in keys_iter {
+                    state.entity(key, &mut inserted);
+                }
+            }
+            Some(first_seen) => {
+                for key in keys_iter {
+                    let entity = state.entity(key, &mut inserted);
+
+                    // There is no aggregate state, use the insertion index as the state value.
+                    if inserted {
+                        entity.set_state_value(first_seen.len());
+                        first_seen.push(first_seen.len());
+                    }
+                }
+            }
         }
     }
 
     /// Allocate aggregation function state for each key(the same key can always get the same state)
+    ///
+    /// With `fold_repeated_keys`, the rows of the block are grouped by key first, so the hash
+    /// table is looked up once per distinct key of the block instead of once per row.
     #[inline(always)]
     fn lookup_state(
         params: &Arc<AggregatorParams>,
         keys_iter: Method::HashKeyIter<'_>,
         state: &mut Method::State,
+        mut first_seen: Option<&mut Vec<usize>>,
+        fold_repeated_keys: bool,
     ) -> StateAddrs {
         let mut places = Vec::with_capacity(keys_iter.size_hint().0);
 
-        let mut inserted = true;
-        for key in keys_iter {
-            let entity = state.entity(key, &mut inserted);
-
-            match inserted {
-                true => {
-                    if let Some(place) = state.alloc_layout(params) {
-                        places.push(place);
-                        entity.set_state_value(place.addr());
-                    }
-                }
-                false => {
-                    let place: StateAddr = (*entity.get_state_value()).into();
+        if !fold_repeated_keys {
+            for key in keys_iter {
+                if let Some(place) =
+                    Self::lookup_place(params, key, state, first_seen.as_deref_mut())
+                {
                     places.push(place);
                 }
             }
+            return places;
+        }
+
+        // Keys are collected in the order they first appear, which keeps the first seen order.
+        let mut keys = Vec::new();
+        let mut groups = HashMap::with_hasher(ahash::RandomState::new());
+        let mut rows_group = Vec::with_capacity(keys_iter.size_hint().0);
+        for key in keys_iter {
+            let group = *groups.entry(key.clone()).or_insert_with(|| {
+                keys.push(key);
+                keys.len() - 1
+            });
+            rows_group.push(group);
+        }
+
+        let groups_place = keys
+            .into_iter()
+            .map(|key| Self::lookup_place(params, key, state, first_seen.as_deref_mut()))
+            .collect::<Vec<_>>();
+
+        for group in rows_group {
+            if let Some(place) = groups_place[group] {
+                places.push(place);
+            }
         }
         places
     }
 
+    #[inline(always)]
+    fn lookup_place(
+        params: &Arc<AggregatorParams>,
+        key: Method::HashKeyRef<'_>,
+        state: &mut Method::State,
+        first_seen: Option<&mut Vec<usize>>,
+    ) -> Option<StateAddr> {
+        let mut inserted = true;
+        let entity = state.entity(key, &mut inserted);
+
+        match inserted {
+            true => {
+                let place = state.alloc_layout(params)?;
+                entity.set_state_value(place.addr());
+                if let Some(first_seen) = first_seen {
+                    first_seen.push(place.addr());
+                }
+                Some(place)
+            }
+            false => Some((*entity.get_state_value()).into()),
+        }
+    }
+
     #[inline(always)]
     fn aggregate_arguments(
         block: &DataBlock,
@@ -183,14 +436,81 @@ impl<const HAS_AGG: bool, Method: HashMethod + PolymorphicKeysHelper<Method> + S
             .collect::<Vec<&ColumnRef>>()
     }
 
+    /// Generate a block per hash table bucket, so that the final aggregators can merge the
+    /// buckets in parallel. The first seen order spans the buckets, it's generated unbucketed.
+    ///
+    /// The spilled buckets are merged with the in-memory groups of the same bucket first.
     #[inline(always)]
     fn generate_data(&mut self) -> Result<Option<DataBlock>> {
-        if self.state.len() == 0 || self.is_generated {
-            return Ok(None);
+        if self.first_seen.is_some() {
+            let entities = self.state.next_entities(
+                &mut self.pending_entities,
+                self.first_seen.as_deref(),
+                self.max_block_size,
+            );
+            if entities.is_empty() {
+                return Ok(None);
+            }
+            return Ok(Some(self.build_block(&entities, -1)?));
+        }
+
+        let mut buckets = match self.pending_buckets.take() {
+            Some(buckets) => buckets,
+            None => {
+                let mut buckets = self.bucket_entities();
+                for bucket in self.spiller.spilled_buckets() {
+                    buckets.entry(bucket).or_default();
+                }
+
+                buckets
+                    .into_iter()
+                    .map(|(bucket, entities)| {
+                        let entities = Either::Right(entities.into_iter());
+                        (bucket, StateEntityIterator::create(entities))
+                    })
+                    .collect()
+            }
+        };
+
+        loop {
+            if let Some(block) = self.pending_blocks.pop_front() {
+                self.pending_buckets = Some(buckets);
+                return Ok(Some(block));
+            }
+
+            let (bucket, entities) = match buckets.front_mut() {
+                None => break,
+                Some((bucket, entities)) => (*bucket, entities),
+            };
+
+            if self.spiller.is_spilled(bucket) {
+                let spilled = self.spiller.restore_bucket(bucket)?;
+                let entities = entities.collect::<Vec<_>>();
+                self.pending_blocks = self.merge_bucket(bucket, spilled, &entities)?;
+                buckets.pop_front();
+                continue;
+            }
+
+            let entities = entities.take(self.max_block_size).collect::<Vec<_>>();
+            if entities.is_empty() {
+                buckets.pop_front();
+                continue;
+            }
+
+            self.pending_buckets = Some(buckets);
+            return Ok(Some(self.build_block(&entities, bucket as i64)?));
         }
 
-        self.is_generated = true;
-        let state_groups_len = self.state.len();
+        self.pending_buckets = Some(buckets);
+        Ok(None)
+    }
+
+    fn build_block(
+        &self,
+        entities: &[*mut <Method::State as AggregatorState<Method>>::Entity],
+        bucket: i64,
+    ) -> Result<DataBlock> {
+        let state_groups_len = entities.len();
         let aggregator_params = self.params.as_ref();
         let funcs = &aggregator_params.aggregate_functions;
         let aggr_len = funcs.len();
@@ -204,11 +524,12 @@ impl<const HAS_AGG: bool, Method: HashMethod + PolymorphicKeysHelper<Method> + S
         let mut group_key_builder = self.method.keys_column_builder(state_groups_len);
 
         let mut bytes = BytesMut::new();
-        for group_entity in self.state.iter() {
+        for group_entity in entities.iter().copied() {
             let place: StateAddr = (*group_entity.get_state_value()).into();
 
             for (idx, func) in funcs.iter().enumerate() {
                 let arg_place = place.next(offsets_aggregate_states[idx]);
+                serialize_state_version(&mut bytes);
                 func.serialize(arg_place, &mut bytes)?;
                 state_builders[idx].append_value(&bytes[..]);
                 bytes.clear();
@@ -224,7 +545,8 @@ impl<const HAS_AGG: bool, Method: HashMethod + PolymorphicKeysHelper<Method> + S
         }
 
         columns.push(group_key_builder.finish());
-        Ok(Some(DataBlock::create(schema.clone(), columns)))
+        columns.push(Series::from_data(vec![bucket; state_groups_len]));
+        Ok(DataBlock::create(schema.clone(), columns))
     }
 }
 
@@ -248,12 +570,44 @@ impl<Method: HashMethod + PolymorphicKeysHelper<Method> + Send> Aggregator
             self.state.convert_to_two_level();
         }
 
-        let places = Self::lookup_state(&self.params, group_keys_iter, &mut self.state);
-        Self::execute(&self.params, &block, &places)
+        let places = Self::lookup_state(
+            &self.params,
+            group_keys_iter,
+            &mut self.state,
+            self.first_seen.as_mut(),
+            self.fold_repeated_keys,
+        );
+        Self::execute(&self.params, &block, &places)?;
+
+        if let Some(reference) = self.reference.as_mut() {
+            reference.consume_input(&block)?;
+        }
+        self.spill_if_needed()
     }
 
     fn generate(&mut self) -> Result<Option<DataBlock>> {
-        self.generate_data()
+        let block = self.generate_data()?;
+        if let Some(reference) = self.reference.as_mut() {
+            match &block {
+                None => {
+                    reference.verify()?;
+                    self.reference = None;
+                }
+                Some(block) => {
+                    // Decode the keys column back to the group by values of the raw input.
+                    let keys_column = block.column(self.params.aggregate_functions.len());
+                    let keys_iter = self.method.keys_iter_from_column(keys_column)?;
+                    let mut group_columns_builder = self
+                        .method
+                        .group_columns_builder(block.num_rows(), &self.params);
+                    for key in keys_iter.get_slice() {
+                        group_columns_builder.append_value(key);
+                    }
+                    reference.verify_states(&group_columns_builder.finish()?, block)?;
+                }
+            }
+        }
+        Ok(block)
     }
 }
 
@@ -277,36 +631,37 @@ impl<Method: HashMethod + PolymorphicKeysHelper<Method> + Send> Aggregator
             self.state.convert_to_two_level();
         }
 
-        Self::lookup_key(group_keys_iter, &mut self.state);
-        Ok(())
+        Self::lookup_key(group_keys_iter, &mut self.state, self.first_seen.as_mut());
+        self.spill_if_needed()
     }
 
     fn generate(&mut self) -> Result<Option<DataBlock>> {
-        match self.state.len() == 0 || self.is_generated {
-            true => {
-                self.drop_states();
-                Ok(None)
-            }
-            false => {
-                self.is_generated = true;
-                let mut keys_column_builder = self.method.keys_column_builder(self.state.len());
-                for group_entity in self.state.iter() {
-                    keys_column_builder.append_value(group_entity.get_state_key());
-                }
-
-                let columns = keys_column_builder.finish();
-                Ok(Some(DataBlock::create(
-                    self.params.output_schema.clone(),
-                    vec![columns],
-                )))
-            }
+        // Without aggregate functions `generate_data` only builds the keys column.
+        let block = self.generate_data()?;
+        if block.is_none() {
+            self.drop_states();
         }
+        Ok(block)
     }
 }
 
 impl<const HAS_AGG: bool, Method: HashMethod + PolymorphicKeysHelper<Method>>
     PartialAggregator<HAS_AGG, Method>
 {
+    /// Drop the aggregate function states at `places`.
+    fn drop_places(params: &AggregatorParams, places: impl Iterator<Item = StateAddr>) {
+        let aggregate_functions = &params.aggregate_functions;
+        let offsets_aggregate_states = &params.offsets_aggregate_states;
+
+        for place in places {
+            for (function, offset) in aggregate_functions.iter().zip(offsets_aggregate_states) {
+                if function.need_manual_drop_state() {
+                    unsafe { function.drop_state(place.next(*offset)) }
+                }
+            }
+        }
+    }
+
     fn drop_states(&mut self) {
         if !self.states_dropped {
             let aggregator_params = self.params.as_ref();
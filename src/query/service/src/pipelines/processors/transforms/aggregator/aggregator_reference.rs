@@ -0,0 +1,301 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::btree_map::Entry;
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::sync::Arc;
+
+use bumpalo::Bump;
+use common_datablocks::DataBlock;
+use common_datavalues::ColumnRef;
+use common_datavalues::DataType;
+use common_datavalues::DataValue;
+use common_datavalues::Series;
+use common_datavalues::StringColumn;
+use common_exception::Result;
+use common_functions::aggregates::StateAddr;
+
+use crate::pipelines::processors::transforms::aggregator::deserialize_state_version;
+use crate::pipelines::processors::AggregatorParams;
+
+// Stop tracking once the input grows beyond this, the check is meant for small inputs.
+const SELF_CHECK_MAX_ROWS: usize = 65536;
+
+/// A naive group by aggregator used to cross-check the output of the partial and final
+/// aggregators, enabled by the `enable_aggregator_self_check` setting.
+///
+/// Groups are kept in a `BTreeMap` keyed by the group by values, or by the value of the
+/// partial keys column on the final side, so the only thing shared with the hash table
+/// based aggregator are the aggregate functions.
+pub struct ReferenceAggregator {
+    params: Arc<AggregatorParams>,
+    area: Bump,
+    groups: BTreeMap<DataValue, StateAddr>,
+    temp_place: StateAddr,
+    rows: usize,
+    states_dropped: bool,
+    // What the final aggregator generated so far, the output may span several blocks.
+    generated_keys: BTreeSet<DataValue>,
+    generated_rows: usize,
+    diffs: Vec<String>,
+}
+
+impl ReferenceAggregator {
+    /// Returns `None` if there is no aggregate function to check.
+    pub fn try_create(params: Arc<AggregatorParams>) -> Option<Self> {
+        let area = Bump::new();
+        let temp_place = Self::alloc_place(&area, &params)?;
+
+        Some(Self {
+            params,
+            area,
+            groups: BTreeMap::new(),
+            temp_place,
+            rows: 0,
+            states_dropped: false,
+            generated_keys: BTreeSet::new(),
+            generated_rows: 0,
+            diffs: vec![],
+        })
+    }
+
+    fn alloc_place(area: &Bump, params: &AggregatorParams) -> Option<StateAddr> {
+        let place: StateAddr = area.alloc_layout(params.layout?).into();
+        for (idx, function) in params.aggregate_functions.iter().enumerate() {
+            function.init_state(place.next(params.offsets_aggregate_states[idx]));
+        }
+        Some(place)
+    }
+
+    fn is_overflowed(&self) -> bool {
+        self.rows > SELF_CHECK_MAX_ROWS
+    }
+
+    fn group_key(columns: &[ColumnRef], row: usize) -> DataValue {
+        match columns.len() {
+            1 => columns[0].get(row),
+            _ => DataValue::Struct(columns.iter().map(|column| column.get(row)).collect()),
+        }
+    }
+
+    fn group_place(&mut self, key: DataValue) -> StateAddr {
+        match self.groups.entry(key) {
+            Entry::Occupied(entry) => *entry.get(),
+            Entry::Vacant(entry) => {
+                // layout is always present, otherwise try_create returns None.
+                let place = Self::alloc_place(&self.area, &self.params).unwrap();
+                *entry.insert(place)
+            }
+        }
+    }
+
+    /// Accumulate a raw input block row by row, keyed by the values of the group by columns.
+    pub fn consume_input(&mut self, block: &DataBlock) -> Result<()> {
+        self.rows += block.num_rows();
+        if self.is_overflowed() {
+            return Ok(());
+        }
+
+        let params = self.params.clone();
+        let group_columns = params
+            .group_columns
+            .iter()
+            .map(|index| block.column(*index).clone())
+            .collect::<Vec<_>>();
+
+        let mut arguments_columns = Vec::with_capacity(params.aggregate_functions.len());
+        for function_arguments in &params.aggregate_functions_arguments {
+            let arguments_column = function_arguments
+                .iter()
+                .map(|index| block.column(*index).clone())
+                .collect::<Vec<_>>();
+            arguments_columns.push(arguments_column);
+        }
+
+        for row in 0..block.num_rows() {
+            let place = self.group_place(Self::group_key(&group_columns, row));
+            for (idx, aggregate_function) in params.aggregate_functions.iter().enumerate() {
+                let arg_place = place.next(params.offsets_aggregate_states[idx]);
+                aggregate_function.accumulate_row(arg_place, &arguments_columns[idx], row)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Merge a partial aggregated block: `[state columns..., keys column, ...]`.
+    pub fn consume(&mut self, block: &DataBlock) -> Result<()> {
+        self.rows += block.num_rows();
+        if self.is_overflowed() {
+            return Ok(());
+        }
+
+        let params = self.params.clone();
+        let aggregate_functions = &params.aggregate_functions;
+        let offsets_aggregate_states = &params.offsets_aggregate_states;
+        let keys_column = block.column(aggregate_functions.len());
+
+        let mut states_binary_columns = Vec::with_capacity(aggregate_functions.len());
+        for idx in 0..aggregate_functions.len() {
+            let states_column: &StringColumn = Series::check_get(block.column(idx))?;
+            states_binary_columns.push(states_column);
+        }
+
+        for row in 0..block.num_rows() {
+            let place = self.group_place(keys_column.get(row));
+
+            for (idx, aggregate_function) in aggregate_functions.iter().enumerate() {
+                let final_place = place.next(offsets_aggregate_states[idx]);
+                let state_place = self.temp_place.next(offsets_aggregate_states[idx]);
+
+                let mut data = states_binary_columns[idx].get_data(row);
+                deserialize_state_version(&mut data)?;
+                aggregate_function.deserialize(state_place, &mut data)?;
+                aggregate_function.merge(final_place, state_place)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Compare a partial aggregated block with the reference, the states are finalized
+    /// and checked against the groups decoded from its keys column.
+    pub fn verify_states(&mut self, key_columns: &[ColumnRef], block: &DataBlock) -> Result<()> {
+        if self.is_overflowed() {
+            return Ok(());
+        }
+
+        let aggregate_functions = &self.params.aggregate_functions;
+        let offsets_aggregate_states = &self.params.offsets_aggregate_states;
+
+        let mut aggregate_columns = Vec::with_capacity(aggregate_functions.len());
+        for (idx, aggregate_function) in aggregate_functions.iter().enumerate() {
+            let states_column: &StringColumn = Series::check_get(block.column(idx))?;
+            let state_place = self.temp_place.next(offsets_aggregate_states[idx]);
+
+            let mut builder = aggregate_function
+                .return_type()?
+                .create_mutable(block.num_rows());
+            for row in 0..block.num_rows() {
+                let mut data = states_column.get_data(row);
+                deserialize_state_version(&mut data)?;
+                aggregate_function.deserialize(state_place, &mut data)?;
+                aggregate_function.merge_result(state_place, builder.as_mut())?;
+            }
+            aggregate_columns.push(builder.to_column());
+        }
+
+        self.verify_block(key_columns, &aggregate_columns)
+    }
+
+    /// Compare a generated block of keys and aggregate results with the reference ones,
+    /// the differences are reported by `verify` once the whole output is generated.
+    pub fn verify_block(
+        &mut self,
+        key_columns: &[ColumnRef],
+        aggregate_columns: &[ColumnRef],
+    ) -> Result<()> {
+        if self.is_overflowed() {
+            return Ok(());
+        }
+
+        let aggregate_functions = &self.params.aggregate_functions;
+        let offsets_aggregate_states = &self.params.offsets_aggregate_states;
+
+        let rows = key_columns.first().map(|column| column.len()).unwrap_or(0);
+        self.generated_rows += rows;
+        for row in 0..rows {
+            let key = Self::group_key(key_columns, row);
+            let place = match self.groups.get(&key) {
+                Some(place) => *place,
+                None => {
+                    self.diffs.push(format!("+ unexpected group {:?}", key));
+                    continue;
+                }
+            };
+
+            for (idx, aggregate_function) in aggregate_functions.iter().enumerate() {
+                let mut builder = aggregate_function.return_type()?.create_mutable(1);
+                let arg_place = place.next(offsets_aggregate_states[idx]);
+                aggregate_function.merge_result(arg_place, builder.as_mut())?;
+
+                let expected = builder.to_column().get(0);
+                let actual = aggregate_columns[idx].get(row);
+                if expected != actual {
+                    self.diffs.push(format!(
+                        "~ group {:?}, {}: expected {:?}, got {:?}",
+                        key, self.params.aggregate_functions_column_name[idx], expected, actual
+                    ));
+                }
+            }
+
+            if !self.generated_keys.insert(key.clone()) {
+                self.diffs.push(format!("+ duplicated group {:?}", key));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Check that every reference group was generated, panics with the differences
+    /// found so far on mismatch.
+    pub fn verify(&self) -> Result<()> {
+        if self.is_overflowed() {
+            return Ok(());
+        }
+
+        let mut diffs = self.diffs.clone();
+        for key in self.groups.keys() {
+            if !self.generated_keys.contains(key) {
+                diffs.push(format!("- missing group {:?}", key));
+            }
+        }
+
+        if !diffs.is_empty() {
+            panic!(
+                "Aggregator self check failed, {} groups expected, {} generated:\n{}",
+                self.groups.len(),
+                self.generated_rows,
+                diffs.join("\n")
+            );
+        }
+
+        Ok(())
+    }
+
+    fn drop_states(&mut self) {
+        if !self.states_dropped {
+            let aggregate_functions = &self.params.aggregate_functions;
+            let offsets_aggregate_states = &self.params.offsets_aggregate_states;
+
+            for (idx, function) in aggregate_functions.iter().enumerate() {
+                if function.need_manual_drop_state() {
+                    let offset = offsets_aggregate_states[idx];
+                    for place in self.groups.values() {
+                        unsafe { function.drop_state(place.next(offset)) }
+                    }
+                    unsafe { function.drop_state(self.temp_place.next(offset)) }
+                }
+            }
+            self.states_dropped = true;
+        }
+    }
+}
+
+impl Drop for ReferenceAggregator {
+    fn drop(&mut self) {
+        self.drop_states();
+    }
+}
@@ -32,6 +32,8 @@ use common_exception::Result;
 use common_functions::aggregates::AggregateFunctionRef;
 use common_functions::aggregates::StateAddr;
 
+use crate::pipelines::processors::transforms::aggregator::deserialize_state_version;
+use crate::pipelines::processors::transforms::aggregator::serialize_state_version;
 use crate::pipelines::processors::transforms::transform_aggregator::Aggregator;
 use crate::pipelines::processors::AggregatorParams;
 
@@ -118,6 +120,7 @@ impl Aggregator for SingleStateAggregator<true> {
             let mut data = binary_array.get_data(0);
 
             let temp_addr = self.temp_places[index];
+            deserialize_state_version(&mut data)?;
             func.deserialize(temp_addr, &mut data)?;
             func.merge(place, temp_addr)?;
         }
@@ -184,6 +187,7 @@ impl Aggregator for SingleStateAggregator<false> {
 
         for (idx, func) in self.funcs.iter().enumerate() {
             let place = self.places[idx];
+            serialize_state_version(&mut bytes);
             func.serialize(place, &mut bytes)?;
             let mut array_builder = MutableStringColumn::with_capacity(4);
             array_builder.append_value(&bytes[..]);
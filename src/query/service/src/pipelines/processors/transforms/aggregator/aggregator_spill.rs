@@ -0,0 +1,189 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::fs::File;
+use std::io::BufReader;
+use std::io::BufWriter;
+use std::path::Path;
+use std::path::PathBuf;
+
+use common_arrow::arrow::chunk::Chunk;
+use common_arrow::arrow::io::ipc::read::read_file_metadata;
+use common_arrow::arrow::io::ipc::read::FileReader;
+use common_arrow::arrow::io::ipc::write::FileWriter;
+use common_arrow::arrow::io::ipc::write::WriteOptions;
+use common_datablocks::DataBlock;
+use common_datavalues::DataSchemaRef;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use tracing::warn;
+use uuid::Uuid;
+
+/// Partial aggregation blocks spilled to temporary arrow IPC files under the `spill_dir`.
+///
+/// Each spill of the hash table goes to a new file, with its groups partitioned by the
+/// buckets of the two-level hash table and written in the order of the buckets. The blocks
+/// are read back bucket by bucket across all the files, and merged with the in-memory groups
+/// of the same bucket before they are generated. The files are removed once the spiller is
+/// dropped.
+pub struct AggregatorSpiller {
+    schema: DataSchemaRef,
+    dir: PathBuf,
+    files: Vec<SpillFile>,
+    writer: Option<FileWriter<BufWriter<File>>>,
+    // The last bucket read back, see `restore_bucket`.
+    restored_bucket: Option<usize>,
+    spilled_blocks: usize,
+}
+
+struct SpillFile {
+    path: PathBuf,
+    // The number of blocks of each bucket in the file.
+    buckets: BTreeMap<usize, usize>,
+    reader: Option<FileReader<BufReader<File>>>,
+}
+
+impl AggregatorSpiller {
+    pub fn create(schema: DataSchemaRef, dir: impl Into<PathBuf>) -> Self {
+        AggregatorSpiller {
+            schema,
+            dir: dir.into(),
+            files: vec![],
+            writer: None,
+            restored_bucket: None,
+            spilled_blocks: 0,
+        }
+    }
+
+    pub fn spilled_blocks(&self) -> usize {
+        self.spilled_blocks
+    }
+
+    pub fn spill_files(&self) -> Vec<&Path> {
+        self.files.iter().map(|file| file.path.as_path()).collect()
+    }
+
+    /// Append a block of the groups in `bucket` to the current spill file, and create one if
+    /// there is none. The blocks of a file must be spilled in the ascending order of buckets.
+    pub fn spill(&mut self, bucket: usize, block: DataBlock) -> Result<()> {
+        if self.writer.is_none() {
+            std::fs::create_dir_all(&self.dir)?;
+            let path = self
+                .dir
+                .join(format!("aggregator-{}", Uuid::new_v4().simple()));
+            let file = BufWriter::new(File::create(&path)?);
+
+            // Track the file as soon as it exists, it's removed on drop even if writing fails.
+            self.files.push(SpillFile {
+                path,
+                buckets: BTreeMap::new(),
+                reader: None,
+            });
+
+            let mut writer =
+                FileWriter::new(file, self.schema.to_arrow(), None, WriteOptions::default());
+            writer.start()?;
+            self.writer = Some(writer);
+        }
+
+        let spill_file = self.files.last_mut().unwrap();
+        debug_assert!(
+            spill_file
+                .buckets
+                .keys()
+                .next_back()
+                .map_or(true, |last| *last <= bucket)
+        );
+
+        let chunk = Chunk::try_from(block)?;
+        self.writer.as_mut().unwrap().write(&chunk, None)?;
+        *spill_file.buckets.entry(bucket).or_default() += 1;
+        self.spilled_blocks += 1;
+        Ok(())
+    }
+
+    /// Finish the current spill file, the next spilled block goes to a new one.
+    pub fn finish_file(&mut self) -> Result<()> {
+        if let Some(mut writer) = self.writer.take() {
+            writer.finish()?;
+        }
+        Ok(())
+    }
+
+    /// The buckets of the spilled blocks, in ascending order.
+    pub fn spilled_buckets(&self) -> BTreeSet<usize> {
+        self.files
+            .iter()
+            .flat_map(|file| file.buckets.keys().copied())
+            .collect()
+    }
+
+    pub fn is_spilled(&self, bucket: usize) -> bool {
+        self.files
+            .iter()
+            .any(|file| file.buckets.contains_key(&bucket))
+    }
+
+    /// Read back the spilled blocks of `bucket` from all the files. The files are read
+    /// sequentially, so the buckets must be restored in the ascending order.
+    pub fn restore_bucket(&mut self, bucket: usize) -> Result<Vec<DataBlock>> {
+        self.finish_file()?;
+        debug_assert!(self.restored_bucket.map_or(true, |last| last < bucket));
+        self.restored_bucket = Some(bucket);
+
+        let mut blocks = vec![];
+        for spill_file in self.files.iter_mut() {
+            let spilled_blocks = match spill_file.buckets.get(&bucket) {
+                None => continue,
+                Some(spilled_blocks) => *spilled_blocks,
+            };
+
+            if spill_file.reader.is_none() {
+                let mut file = BufReader::new(File::open(&spill_file.path)?);
+                let metadata = read_file_metadata(&mut file)?;
+                spill_file.reader = Some(FileReader::new(file, metadata, None, None));
+            }
+
+            let reader = spill_file.reader.as_mut().unwrap();
+            for _ in 0..spilled_blocks {
+                match reader.next() {
+                    None => {
+                        return Err(ErrorCode::LogicalError(format!(
+                            "Missing blocks in aggregator spill file {:?}",
+                            spill_file.path
+                        )));
+                    }
+                    Some(chunk) => blocks.push(DataBlock::from_chunk(&self.schema, &chunk?)?),
+                }
+            }
+        }
+        Ok(blocks)
+    }
+}
+
+impl Drop for AggregatorSpiller {
+    fn drop(&mut self) {
+        self.writer = None;
+        for file in self.files.drain(..) {
+            if let Err(cause) = std::fs::remove_file(&file.path) {
+                warn!(
+                    "Failed to remove aggregator spill file {:?}: {}",
+                    file.path, cause
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,48 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use bytes::BufMut;
+use bytes::BytesMut;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+/// Version of the serialized aggregate states sent from the partial aggregators to the
+/// final ones, which may run on other nodes of a different version during rolling upgrades.
+///
+/// Bump it whenever the serialized format of any aggregate function state changes.
+pub const AGGREGATE_STATE_VERSION: u8 = 1;
+
+/// Write the version header, it must be written before each serialized state.
+#[inline(always)]
+pub fn serialize_state_version(writer: &mut BytesMut) {
+    writer.put_u8(AGGREGATE_STATE_VERSION);
+}
+
+/// Read and check the version header, and advance the reader to the serialized state.
+#[inline(always)]
+pub fn deserialize_state_version(reader: &mut &[u8]) -> Result<()> {
+    match reader.split_first() {
+        Some((&AGGREGATE_STATE_VERSION, state)) => {
+            *reader = state;
+            Ok(())
+        }
+        Some((version, _)) => Err(ErrorCode::BadBytes(format!(
+            "Incompatible aggregate state version {}, expected {}",
+            version, AGGREGATE_STATE_VERSION
+        ))),
+        None => Err(ErrorCode::BadBytes(
+            "Missing aggregate state version".to_string(),
+        )),
+    }
+}
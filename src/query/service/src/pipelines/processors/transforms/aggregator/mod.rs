@@ -12,11 +12,16 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod aggregator_bucket_router;
 mod aggregator_final;
 mod aggregator_params;
 mod aggregator_partial;
+mod aggregator_reference;
 mod aggregator_single_key;
+mod aggregator_spill;
+mod aggregator_state_version;
 
+pub use aggregator_bucket_router::TransformBucketRouter;
 pub use aggregator_final::FinalAggregator;
 pub use aggregator_final::KeysU128FinalAggregator;
 pub use aggregator_final::KeysU16FinalAggregator;
@@ -37,6 +42,11 @@ pub use aggregator_partial::KeysU64PartialAggregator;
 pub use aggregator_partial::KeysU8PartialAggregator;
 pub use aggregator_partial::PartialAggregator;
 pub use aggregator_partial::SerializerPartialAggregator;
+pub use aggregator_reference::ReferenceAggregator;
 pub use aggregator_single_key::FinalSingleStateAggregator;
 pub use aggregator_single_key::PartialSingleStateAggregator;
 pub use aggregator_single_key::SingleStateAggregator;
+pub use aggregator_spill::AggregatorSpiller;
+pub use aggregator_state_version::deserialize_state_version;
+pub use aggregator_state_version::serialize_state_version;
+pub use aggregator_state_version::AGGREGATE_STATE_VERSION;
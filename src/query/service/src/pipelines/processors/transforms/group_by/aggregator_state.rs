@@ -14,6 +14,7 @@
 
 use std::alloc::GlobalAlloc;
 use std::alloc::Layout;
+use std::collections::HashMap;
 use std::intrinsics::likely;
 
 use bumpalo::Bump;
@@ -28,11 +29,13 @@ use common_hashtable::HashMapKind;
 use common_hashtable::HashTableEntity;
 use common_hashtable::HashTableKeyable;
 use common_hashtable::KeyValueEntity;
+use itertools::Either;
 
 use crate::pipelines::processors::transforms::group_by::aggregator_state_entity::ShortFixedKeyable;
 use crate::pipelines::processors::transforms::group_by::aggregator_state_entity::ShortFixedKeysStateEntity;
 use crate::pipelines::processors::transforms::group_by::aggregator_state_entity::StateEntity;
 use crate::pipelines::processors::transforms::group_by::aggregator_state_iterator::ShortFixedKeysStateIterator;
+use crate::pipelines::processors::transforms::group_by::aggregator_state_iterator::StateEntityIterator;
 use crate::pipelines::processors::transforms::group_by::keys_ref::KeysRef;
 use crate::pipelines::processors::AggregatorParams as NewAggregatorParams;
 
@@ -44,14 +47,52 @@ use crate::pipelines::processors::AggregatorParams as NewAggregatorParams;
 ///     - Group by key data memory pool (if necessary)
 #[allow(clippy::len_without_is_empty)]
 pub trait AggregatorState<Method: HashMethod>: Sync + Send {
-    type Key;
+    type Key: HashTableKeyable;
     type Entity: StateEntity<Self::Key>;
     type Iterator: Iterator<Item = *mut Self::Entity>;
 
     fn len(&self) -> usize;
 
+    /// Bytes taken by the hash table entities and the memory pools of keys and states.
+    fn allocated_bytes(&self) -> usize;
+
     fn iter(&self) -> Self::Iterator;
 
+    /// Iterate the entities in the order given by `first_seen`, the state values recorded
+    /// when the keys were inserted. Falls back to the hash table order if it's `None`.
+    fn iter_first_seen(&self, first_seen: Option<&[usize]>) -> StateEntityIterator<Self::Iterator> {
+        StateEntityIterator::create(match first_seen {
+            None => Either::Left(self.iter()),
+            Some(first_seen) => {
+                let mut entities = self
+                    .iter()
+                    .map(|entity| (*entity.get_state_value(), entity))
+                    .collect::<HashMap<_, _>>();
+
+                let ordered = first_seen
+                    .iter()
+                    .filter_map(|value| entities.remove(value))
+                    .collect::<Vec<_>>();
+                Either::Right(ordered.into_iter())
+            }
+        })
+    }
+
+    /// Take the next entities to generate, at most `max_block_size` of them, so that a large
+    /// hash table is generated as several bounded blocks. `pending` is set by `iter_first_seen`
+    /// on the first call and kept for the next ones. Empty once all entities are taken.
+    fn next_entities(
+        &self,
+        pending: &mut Option<StateEntityIterator<Self::Iterator>>,
+        first_seen: Option<&[usize]>,
+        max_block_size: usize,
+    ) -> Vec<*mut Self::Entity> {
+        pending
+            .get_or_insert_with(|| self.iter_first_seen(first_seen))
+            .take(max_block_size)
+            .collect()
+    }
+
     fn alloc_place(&self, layout: Layout) -> StateAddr;
 
     fn alloc_layout(&self, params: &NewAggregatorParams) -> Option<StateAddr> {
@@ -129,7 +170,7 @@ impl<T: ShortFixedKeyable> Drop for ShortFixedKeysAggregatorState<T> {
 
 impl<T> AggregatorState<HashMethodFixedKeys<T>> for ShortFixedKeysAggregatorState<T>
 where
-    T: PrimitiveType + ShortFixedKeyable,
+    T: PrimitiveType + ShortFixedKeyable + HashTableKeyable,
     for<'a> HashMethodFixedKeys<T>: HashMethod<HashKey = T, HashKeyRef<'a> = T>,
     for<'a> <HashMethodFixedKeys<T> as HashMethod>::HashKey: HashTableKeyable,
 {
@@ -142,6 +183,10 @@ where
         self.size
     }
 
+    fn allocated_bytes(&self) -> usize {
+        self.max_size * std::mem::size_of::<Self::Entity>() + self.area.allocated_bytes()
+    }
+
     #[inline(always)]
     fn iter(&self) -> Self::Iterator {
         Self::Iterator::create(self.data, self.max_size as isize)
@@ -214,8 +259,8 @@ unsafe impl<T: HashTableKeyable + Send> Send for LongerFixedKeysAggregatorState<
 // will not be used multiple async, so KeyValueEntity is Sync
 unsafe impl<T: HashTableKeyable + Sync> Sync for LongerFixedKeysAggregatorState<T> {}
 
-impl<T: Copy + Send + Sync + Sized + 'static> AggregatorState<HashMethodFixedKeys<T>>
-    for LongerFixedKeysAggregatorState<T>
+impl<T: HashTableKeyable + Copy + Send + Sync + Sized + 'static>
+    AggregatorState<HashMethodFixedKeys<T>> for LongerFixedKeysAggregatorState<T>
 where
     for<'a> HashMethodFixedKeys<T>: HashMethod<HashKey = T, HashKeyRef<'a> = T>,
     for<'a> <HashMethodFixedKeys<T> as HashMethod>::HashKey: HashTableKeyable,
@@ -229,6 +274,10 @@ where
         self.data.len()
     }
 
+    fn allocated_bytes(&self) -> usize {
+        self.data.len() * std::mem::size_of::<Self::Entity>() + self.area.allocated_bytes()
+    }
+
     #[inline(always)]
     fn iter(&self) -> Self::Iterator {
         self.data.iter()
@@ -289,6 +338,13 @@ impl AggregatorState<HashMethodSerializer> for SerializedKeysAggregatorState {
     fn len(&self) -> usize {
         self.data_state_map.len()
     }
+
+    fn allocated_bytes(&self) -> usize {
+        self.data_state_map.len() * std::mem::size_of::<Self::Entity>()
+            + self.keys_area.allocated_bytes()
+            + self.state_area.allocated_bytes()
+    }
+
     fn iter(&self) -> Self::Iterator {
         self.data_state_map.iter()
     }
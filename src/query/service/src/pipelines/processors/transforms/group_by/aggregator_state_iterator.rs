@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use itertools::Either;
+
 use crate::pipelines::processors::transforms::group_by::aggregator_state_entity::ShortFixedKeyable;
 use crate::pipelines::processors::transforms::group_by::aggregator_state_entity::ShortFixedKeysStateEntity;
 
@@ -51,3 +53,28 @@ impl<Key: ShortFixedKeyable> Iterator for ShortFixedKeysStateIterator<Key> {
         }
     }
 }
+
+/// Iterator of the entities of an aggregator state, in the hash table order or in the
+/// first seen order, see `AggregatorState::iter_first_seen`.
+///
+/// It only holds the pointers to the entities, so it can be kept to generate the
+/// entities in several blocks, as long as the state is neither modified nor dropped.
+pub struct StateEntityIterator<Iter: Iterator> {
+    inner: Either<Iter, std::vec::IntoIter<Iter::Item>>,
+}
+
+unsafe impl<Iter: Iterator> Send for StateEntityIterator<Iter> {}
+
+impl<Iter: Iterator> StateEntityIterator<Iter> {
+    pub fn create(inner: Either<Iter, std::vec::IntoIter<Iter::Item>>) -> Self {
+        StateEntityIterator { inner }
+    }
+}
+
+impl<Iter: Iterator> Iterator for StateEntityIterator<Iter> {
+    type Item = Iter::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+}
@@ -27,3 +27,4 @@ pub use aggregator_keys_iter::KeysColumnIter;
 pub use aggregator_polymorphic_keys::PolymorphicKeysHelper;
 pub use aggregator_state::AggregatorState;
 pub use aggregator_state_entity::StateEntity;
+pub use aggregator_state_iterator::StateEntityIterator;
@@ -28,15 +28,22 @@ mod transform_create_sets;
 mod transform_dummy;
 mod transform_hash_join;
 mod transform_limit;
+mod transform_limit_with_ties;
 mod transform_mark_join;
+mod transform_max_result_rows;
 
 pub mod group_by;
 mod transform_merge_block;
 mod transform_right_join;
 mod transform_right_semi_anti_join;
 
+pub use aggregator::deserialize_state_version;
+pub use aggregator::serialize_state_version;
 pub use aggregator::AggregatorParams;
+pub use aggregator::AggregatorSpiller;
 pub use aggregator::AggregatorTransformParams;
+pub use aggregator::TransformBucketRouter;
+pub use aggregator::AGGREGATE_STATE_VERSION;
 pub use chunk_operator::ChunkOperator;
 pub use chunk_operator::CompoundChunkOperator;
 pub use common_pipeline_transforms::processors::ExpressionExecutor;
@@ -67,8 +74,10 @@ pub use transform_expression::ProjectionTransform;
 pub use transform_hash_join::SinkBuildHashTable;
 pub use transform_hash_join::TransformHashJoinProbe;
 pub use transform_limit::TransformLimit;
+pub use transform_limit_with_ties::TransformLimitWithTies;
 pub use transform_mark_join::MarkJoinCompactor;
 pub use transform_mark_join::TransformMarkJoin;
+pub use transform_max_result_rows::TransformMaxResultRows;
 pub use transform_merge_block::TransformMergeBlock;
 pub use transform_right_join::RightJoinCompactor;
 pub use transform_right_join::TransformRightJoin;
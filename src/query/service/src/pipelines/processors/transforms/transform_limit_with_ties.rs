@@ -0,0 +1,173 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datavalues::DataValue;
+use common_exception::Result;
+
+use crate::pipelines::processors::port::InputPort;
+use crate::pipelines::processors::port::OutputPort;
+use crate::pipelines::processors::processor::Event;
+use crate::pipelines::processors::processor::ProcessorPtr;
+use crate::pipelines::processors::Processor;
+
+/// Limit for `FETCH FIRST n ROWS WITH TIES`, the input must be sorted by `sort_columns`.
+///
+/// After the first `limit` rows are taken, the following rows with the same sort key
+/// as the last taken row are also returned.
+pub struct TransformLimitWithTies {
+    take_remaining: usize,
+    skip_remaining: usize,
+    sort_columns: Vec<String>,
+    // Sort key of the last row within the limit, set once `take_remaining` reaches zero.
+    last_key: Option<Vec<DataValue>>,
+    finished: bool,
+
+    input: Arc<InputPort>,
+    output: Arc<OutputPort>,
+
+    input_data_block: Option<DataBlock>,
+    output_data_block: Option<DataBlock>,
+}
+
+impl TransformLimitWithTies {
+    pub fn try_create(
+        limit: usize,
+        offset: usize,
+        sort_columns: Vec<String>,
+        input: Arc<InputPort>,
+        output: Arc<OutputPort>,
+    ) -> Result<ProcessorPtr> {
+        Ok(ProcessorPtr::create(Box::new(Self {
+            take_remaining: limit,
+            skip_remaining: offset,
+            sort_columns,
+            last_key: None,
+            finished: limit == 0,
+            input,
+            output,
+            input_data_block: None,
+            output_data_block: None,
+        })))
+    }
+
+    fn sort_key(&self, data_block: &DataBlock, row: usize) -> Result<Vec<DataValue>> {
+        self.sort_columns
+            .iter()
+            .map(|name| Ok(data_block.try_column_by_name(name)?.get(row)))
+            .collect()
+    }
+
+    fn take_rows(&mut self, data_block: DataBlock) -> Result<Option<DataBlock>> {
+        let mut data_block = data_block;
+        let rows = data_block.num_rows();
+        if self.skip_remaining >= rows {
+            self.skip_remaining -= rows;
+            return Ok(None);
+        }
+        if self.skip_remaining != 0 {
+            data_block = data_block.slice(self.skip_remaining, rows - self.skip_remaining);
+            self.skip_remaining = 0;
+        }
+
+        let rows = data_block.num_rows();
+        let mut taken = 0;
+        if self.last_key.is_none() {
+            taken = std::cmp::min(self.take_remaining, rows);
+            self.take_remaining -= taken;
+            if self.take_remaining != 0 {
+                return Ok(Some(data_block));
+            }
+            self.last_key = Some(self.sort_key(&data_block, taken - 1)?);
+        }
+
+        // Take the following rows tying with the last row within the limit.
+        if let Some(last_key) = &self.last_key {
+            while taken < rows && self.sort_key(&data_block, taken)? == *last_key {
+                taken += 1;
+            }
+        }
+        if taken < rows {
+            self.finished = true;
+        }
+
+        Ok(match taken {
+            0 => None,
+            taken if taken == rows => Some(data_block),
+            taken => Some(data_block.slice(0, taken)),
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl Processor for TransformLimitWithTies {
+    fn name(&self) -> &'static str {
+        "LimitWithTiesTransform"
+    }
+
+    fn as_any(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn event(&mut self) -> Result<Event> {
+        if self.output.is_finished() {
+            self.input.finish();
+            return Ok(Event::Finished);
+        }
+
+        if !self.output.can_push() {
+            self.input.set_not_need_data();
+            return Ok(Event::NeedConsume);
+        }
+
+        if let Some(data_block) = self.output_data_block.take() {
+            self.output.push_data(Ok(data_block));
+            return Ok(Event::NeedConsume);
+        }
+
+        if self.finished {
+            self.input.finish();
+            self.output.finish();
+            return Ok(Event::Finished);
+        }
+
+        if self.input_data_block.is_some() {
+            return Ok(Event::Sync);
+        }
+
+        if self.input.is_finished() {
+            self.output.finish();
+            return Ok(Event::Finished);
+        }
+
+        if !self.input.has_data() {
+            self.input.set_need_data();
+            return Ok(Event::NeedData);
+        }
+
+        self.input_data_block = Some(self.input.pull_data().unwrap()?);
+        Ok(Event::Sync)
+    }
+
+    fn process(&mut self) -> Result<()> {
+        if let Some(data_block) = self.input_data_block.take() {
+            self.output_data_block = self.take_rows(data_block)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,63 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::pipelines::processors::port::InputPort;
+use crate::pipelines::processors::port::OutputPort;
+use crate::pipelines::processors::processor::ProcessorPtr;
+use crate::pipelines::processors::transforms::transform::Transform;
+use crate::pipelines::processors::transforms::transform::Transformer;
+
+/// Abort the query once the result set exceeds `max_result_rows`.
+///
+/// The row counter is shared by all the parallel transforms at the end of the pipeline.
+pub struct TransformMaxResultRows {
+    max_rows: usize,
+    rows: Arc<AtomicUsize>,
+}
+
+impl TransformMaxResultRows {
+    pub fn create(
+        input: Arc<InputPort>,
+        output: Arc<OutputPort>,
+        max_rows: usize,
+        rows: Arc<AtomicUsize>,
+    ) -> ProcessorPtr {
+        Transformer::create(input, output, TransformMaxResultRows { max_rows, rows })
+    }
+}
+
+impl Transform for TransformMaxResultRows {
+    const NAME: &'static str = "MaxResultRowsTransform";
+
+    fn transform(&mut self, data: DataBlock) -> Result<DataBlock> {
+        let num_rows = data.num_rows();
+        let rows = self.rows.fetch_add(num_rows, Ordering::Relaxed) + num_rows;
+        if rows > self.max_rows {
+            return Err(ErrorCode::AbortedQuery(format!(
+                "Aborted query, because the result exceeds max_result_rows ({})",
+                self.max_rows
+            )));
+        }
+
+        Ok(data)
+    }
+}
@@ -219,7 +219,6 @@ impl MySQLFederated {
             ("(?i)^(SHOW MASTER STATUS)", None),
             ("(?i)^(SHOW ALL SLAVES STATUS)", None),
             // DBeaver.
-            ("(?i)^(SHOW WARNINGS)", None),
             ("(?i)^(/\\* ApplicationName=(.*)SHOW WARNINGS)", None),
             ("(?i)^(/\\* ApplicationName=(.*)SHOW PLUGINS)", None),
             ("(?i)^(/\\* ApplicationName=(.*)SHOW COLLATION)", None),
@@ -365,6 +365,18 @@ impl TableContext for QueryContext {
     fn get_processes_info(&self) -> Vec<ProcessInfo> {
         SessionManager::instance().processes_info()
     }
+
+    fn push_warning(&self, warning: String) {
+        self.shared.session.push_warning(warning)
+    }
+
+    fn get_warnings(&self) -> Vec<String> {
+        self.shared.session.get_warnings()
+    }
+
+    fn clear_warnings(&self) {
+        self.shared.session.clear_warnings()
+    }
 }
 
 impl TrySpawn for QueryContext {
@@ -186,6 +186,18 @@ impl Session {
         self.session_ctx.get_current_database()
     }
 
+    pub fn push_warning(self: &Arc<Self>, warning: String) {
+        self.session_ctx.push_warning(warning);
+    }
+
+    pub fn get_warnings(self: &Arc<Self>) -> Vec<String> {
+        self.session_ctx.get_warnings()
+    }
+
+    pub fn clear_warnings(self: &Arc<Self>) {
+        self.session_ctx.clear_warnings();
+    }
+
     pub fn get_current_catalog(self: &Arc<Self>) -> String {
         self.session_ctx.get_current_catalog()
     }
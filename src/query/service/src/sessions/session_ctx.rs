@@ -39,6 +39,8 @@ pub struct SessionContext {
     client_host: RwLock<Option<SocketAddr>>,
     io_shutdown_tx: RwLock<Option<Sender<Sender<()>>>>,
     query_context_shared: RwLock<Weak<QueryContextShared>>,
+    // Warnings of the last statement, for `SHOW WARNINGS`.
+    warnings: RwLock<Vec<String>>,
 }
 
 impl SessionContext {
@@ -55,6 +57,7 @@ impl SessionContext {
             current_database: RwLock::new("default".to_string()),
             io_shutdown_tx: Default::default(),
             query_context_shared: Default::default(),
+            warnings: Default::default(),
         }))
     }
 
@@ -179,4 +182,19 @@ impl SessionContext {
         let mut lock = self.query_context_shared.write();
         *lock = ctx
     }
+
+    pub fn push_warning(&self, warning: String) {
+        let mut lock = self.warnings.write();
+        lock.push(warning)
+    }
+
+    pub fn get_warnings(&self) -> Vec<String> {
+        let lock = self.warnings.read();
+        lock.clone()
+    }
+
+    pub fn clear_warnings(&self) {
+        let mut lock = self.warnings.write();
+        lock.clear()
+    }
 }
@@ -23,6 +23,7 @@ use itertools::Itertools;
 
 use super::AggregateFinal;
 use super::AggregatePartial;
+use super::ConstantTableScan;
 use super::EvalScalar;
 use super::Exchange;
 use super::Filter;
@@ -53,6 +54,7 @@ fn to_format_tree(plan: &PhysicalPlan, metadata: &MetadataRef) -> Result<FormatT
         PhysicalPlan::HashJoin(plan) => hash_join_to_format_tree(plan, metadata),
         PhysicalPlan::Exchange(plan) => exchange_to_format_tree(plan, metadata),
         PhysicalPlan::UnionAll(plan) => union_all_to_format_tree(plan, metadata),
+        PhysicalPlan::ConstantTableScan(plan) => Ok(constant_table_scan_to_format_tree(plan)),
         PhysicalPlan::ExchangeSource(_)
         | PhysicalPlan::ExchangeSink(_)
         | PhysicalPlan::DistributedInsertSelect(_) => {
@@ -184,13 +186,21 @@ fn aggregate_partial_to_format_tree(
         .map(|agg| agg.pretty_display(metadata))
         .collect::<Result<Vec<_>>>()?
         .join(", ");
+    let mut children = vec![
+        FormatTreeNode::new(format!("group by: [{group_by}]")),
+        FormatTreeNode::new(format!("aggregate functions: [{agg_funcs}]")),
+    ];
+    // Only a forced hash method is shown, the chosen one depends on the key types
+    if plan.hash_method != "auto" {
+        children.push(FormatTreeNode::new(format!(
+            "hash method: {}",
+            plan.hash_method
+        )));
+    }
+    children.push(to_format_tree(&plan.input, metadata)?);
     Ok(FormatTreeNode::with_children(
         "AggregatePartial".to_string(),
-        vec![
-            FormatTreeNode::new(format!("group by: [{group_by}]")),
-            FormatTreeNode::new(format!("aggregate functions: [{agg_funcs}]")),
-            to_format_tree(&plan.input, metadata)?,
-        ],
+        children,
     ))
 }
 
@@ -215,13 +225,21 @@ fn aggregate_final_to_format_tree(
         .map(|agg| agg.pretty_display(metadata))
         .collect::<Result<Vec<_>>>()?
         .join(", ");
+    let mut children = vec![
+        FormatTreeNode::new(format!("group by: [{group_by}]")),
+        FormatTreeNode::new(format!("aggregate functions: [{agg_funcs}]")),
+    ];
+    // Only a forced hash method is shown, the chosen one depends on the key types
+    if plan.hash_method != "auto" {
+        children.push(FormatTreeNode::new(format!(
+            "hash method: {}",
+            plan.hash_method
+        )));
+    }
+    children.push(to_format_tree(&plan.input, metadata)?);
     Ok(FormatTreeNode::with_children(
         "AggregateFinal".to_string(),
-        vec![
-            FormatTreeNode::new(format!("group by: [{group_by}]")),
-            FormatTreeNode::new(format!("aggregate functions: [{agg_funcs}]")),
-            to_format_tree(&plan.input, metadata)?,
-        ],
+        children,
     ))
 }
 
@@ -252,15 +270,19 @@ fn sort_to_format_tree(plan: &Sort, metadata: &MetadataRef) -> Result<FormatTree
 }
 
 fn limit_to_format_tree(plan: &Limit, metadata: &MetadataRef) -> Result<FormatTreeNode<String>> {
-    Ok(FormatTreeNode::with_children("Limit".to_string(), vec![
+    let mut children = vec![
         FormatTreeNode::new(format!(
             "limit: {}",
             plan.limit
                 .map_or("NONE".to_string(), |limit| limit.to_string())
         )),
         FormatTreeNode::new(format!("offset: {}", plan.offset)),
-        to_format_tree(&plan.input, metadata)?,
-    ]))
+    ];
+    if !plan.with_ties.is_empty() {
+        children.push(FormatTreeNode::new("with ties: true".to_string()));
+    }
+    children.push(to_format_tree(&plan.input, metadata)?);
+    Ok(FormatTreeNode::with_children("Limit".to_string(), children))
 }
 
 fn hash_join_to_format_tree(
@@ -332,3 +354,9 @@ fn union_all_to_format_tree(
         to_format_tree(&plan.right, metadata)?,
     ]))
 }
+
+fn constant_table_scan_to_format_tree(plan: &ConstantTableScan) -> FormatTreeNode<String> {
+    FormatTreeNode::with_children("ConstantTableScan".to_string(), vec![FormatTreeNode::new(
+        format!("rows: {}", plan.rows.len()),
+    )])
+}
@@ -20,6 +20,7 @@ use common_datavalues::BooleanType;
 use common_datavalues::DataField;
 use common_datavalues::DataSchemaRef;
 use common_datavalues::DataSchemaRefExt;
+use common_datavalues::DataValue;
 use common_datavalues::NullableType;
 use common_datavalues::ToDataType;
 use common_datavalues::Vu8;
@@ -115,6 +116,8 @@ pub struct AggregatePartial {
     pub input: Box<PhysicalPlan>,
     pub group_by: Vec<ColumnID>,
     pub agg_funcs: Vec<AggregateFunctionDesc>,
+    /// Hash method of the group keys, see setting `group_by_hash_method`.
+    pub hash_method: String,
 }
 
 impl AggregatePartial {
@@ -125,16 +128,15 @@ impl AggregatePartial {
             fields.push(DataField::new(agg.column_id.as_str(), Vu8::to_data_type()));
         }
         if !self.group_by.is_empty() {
-            let sample_block = DataBlock::empty_with_schema(input_schema.clone());
-            let method = DataBlock::choose_hash_method(
-                &sample_block,
-                &self
-                    .group_by
-                    .iter()
-                    .map(|name| input_schema.index_of(name))
-                    .collect::<Result<Vec<_>>>()?,
-            )?;
+            let group_types = self
+                .group_by
+                .iter()
+                .map(|name| Ok(input_schema.field_with_name(name)?.data_type().clone()))
+                .collect::<Result<Vec<_>>>()?;
+            let method = DataBlock::choose_hash_method_with_name(&group_types, &self.hash_method)?;
             fields.push(DataField::new("_group_by_key", method.data_type()));
+            // The hash table bucket of the groups of the block, -1 if it mixes buckets.
+            fields.push(DataField::new("_group_by_bucket", i64::to_data_type()));
         }
         Ok(DataSchemaRefExt::create(fields))
     }
@@ -146,6 +148,8 @@ pub struct AggregateFinal {
     pub group_by: Vec<ColumnID>,
     pub agg_funcs: Vec<AggregateFunctionDesc>,
     pub before_group_by_schema: DataSchemaRef,
+    /// Hash method of the group keys, the same as the one of `AggregatePartial`.
+    pub hash_method: String,
 }
 
 impl AggregateFinal {
@@ -186,6 +190,8 @@ pub struct Limit {
     pub input: Box<PhysicalPlan>,
     pub limit: Option<usize>,
     pub offset: usize,
+    // Sort keys of the input to find the ties of the last row, empty if not `WITH TIES`
+    pub with_ties: Vec<SortDesc>,
 }
 
 impl Limit {
@@ -346,6 +352,18 @@ impl UnionAll {
     }
 }
 
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct ConstantTableScan {
+    pub rows: Vec<Vec<DataValue>>,
+    pub schema: DataSchemaRef,
+}
+
+impl ConstantTableScan {
+    pub fn output_schema(&self) -> Result<DataSchemaRef> {
+        Ok(self.schema.clone())
+    }
+}
+
 #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
 pub struct DistributedInsertSelect {
     pub input: Box<PhysicalPlan>,
@@ -376,6 +394,7 @@ pub enum PhysicalPlan {
     HashJoin(HashJoin),
     Exchange(Exchange),
     UnionAll(UnionAll),
+    ConstantTableScan(ConstantTableScan),
 
     /// For insert into ... select ... in cluster
     DistributedInsertSelect(Box<DistributedInsertSelect>),
@@ -409,6 +428,7 @@ impl PhysicalPlan {
             PhysicalPlan::ExchangeSource(plan) => plan.output_schema(),
             PhysicalPlan::ExchangeSink(plan) => plan.output_schema(),
             PhysicalPlan::UnionAll(plan) => plan.output_schema(),
+            PhysicalPlan::ConstantTableScan(plan) => plan.output_schema(),
             PhysicalPlan::DistributedInsertSelect(plan) => plan.output_schema(),
         }
     }
@@ -432,6 +452,7 @@ impl PhysicalPlan {
             PhysicalPlan::UnionAll(plan) => Box::new(
                 std::iter::once(plan.left.as_ref()).chain(std::iter::once(plan.right.as_ref())),
             ),
+            PhysicalPlan::ConstantTableScan(_) => Box::new(std::iter::empty()),
             PhysicalPlan::DistributedInsertSelect(plan) => {
                 Box::new(std::iter::once(plan.input.as_ref()))
             }
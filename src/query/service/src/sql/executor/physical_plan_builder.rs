@@ -18,6 +18,7 @@ use std::sync::Arc;
 
 use common_catalog::catalog::CatalogManager;
 use common_catalog::catalog::CATALOG_DEFAULT;
+use common_datavalues::DataField;
 use common_datavalues::DataSchemaRef;
 use common_datavalues::DataSchemaRefExt;
 use common_exception::ErrorCode;
@@ -48,6 +49,7 @@ use crate::sql::executor::util::check_physical;
 use crate::sql::executor::AggregateFunctionDesc;
 use crate::sql::executor::AggregateFunctionSignature;
 use crate::sql::executor::ColumnID;
+use crate::sql::executor::ConstantTableScan;
 use crate::sql::executor::EvalScalar;
 use crate::sql::executor::ExpressionBuilderWithoutRenaming;
 use crate::sql::executor::PhysicalPlan;
@@ -257,6 +259,7 @@ impl PhysicalPlanBuilder {
                 let result = match &agg.mode {
                     AggregateMode::Partial => {
                         let input_schema = input.output_schema()?;
+                        let hash_method = self.ctx.get_settings().get_group_by_hash_method()?;
                         let agg_funcs: Vec<AggregateFunctionDesc> = agg.aggregate_functions.iter().map(|v| {
                                 if let Scalar::AggregateFunction(agg) = &v.scalar {
                                     Ok(AggregateFunctionDesc {
@@ -299,6 +302,7 @@ impl PhysicalPlanBuilder {
                                     input,
                                     agg_funcs,
                                     group_by: group_items,
+                                    hash_method,
                                 };
 
                                 let output_schema = aggregate_partial.output_schema()?;
@@ -323,6 +327,7 @@ impl PhysicalPlanBuilder {
                                 agg_funcs,
                                 group_by: group_items,
                                 input: Box::new(input),
+                                hash_method,
                             }),
                         }
                     }
@@ -384,6 +389,7 @@ impl PhysicalPlanBuilder {
                                     group_by: group_items,
                                     agg_funcs,
                                     before_group_by_schema,
+                                    hash_method: agg.hash_method.clone(),
                                 })
                             }
 
@@ -397,6 +403,7 @@ impl PhysicalPlanBuilder {
                                     group_by: group_items,
                                     agg_funcs,
                                     before_group_by_schema,
+                                    hash_method: agg.hash_method.clone(),
                                 })
                             }
 
@@ -421,11 +428,23 @@ impl PhysicalPlanBuilder {
                     .collect(),
                 limit: sort.limit,
             })),
-            RelOperator::Limit(limit) => Ok(PhysicalPlan::Limit(Limit {
-                input: Box::new(self.build(s_expr.child(0)?).await?),
-                limit: limit.limit,
-                offset: limit.offset,
-            })),
+            RelOperator::Limit(limit) => {
+                let input = self.build(s_expr.child(0)?).await?;
+                Ok(PhysicalPlan::Limit(Limit {
+                    input: Box::new(input),
+                    limit: limit.limit,
+                    offset: limit.offset,
+                    with_ties: limit
+                        .with_ties
+                        .iter()
+                        .map(|v| SortDesc {
+                            asc: v.asc,
+                            nulls_first: v.nulls_first,
+                            order_by: v.index.to_string(),
+                        })
+                        .collect(),
+                }))
+            }
             RelOperator::Exchange(exchange) => {
                 let input = Box::new(self.build(s_expr.child(0)?).await?);
                 let input_schema = input.output_schema()?;
@@ -466,6 +485,30 @@ impl PhysicalPlanBuilder {
                     schema: DataSchemaRefExt::create(fields),
                 }))
             }
+            RelOperator::ConstantTableScan(scan) => {
+                let metadata = self.metadata.read();
+                let fields = scan
+                    .columns
+                    .iter()
+                    .map(|index| {
+                        DataField::new(
+                            &index.to_string(),
+                            metadata.column(*index).data_type().clone(),
+                        )
+                    })
+                    .collect::<Vec<_>>();
+                Ok(PhysicalPlan::ConstantTableScan(ConstantTableScan {
+                    rows: scan.rows.clone(),
+                    schema: DataSchemaRefExt::create(fields),
+                }))
+            }
+            RelOperator::Window(_) => Err(ErrorCode::UnImplement(
+                "Window function is not supported yet",
+            )),
+            RelOperator::RecursiveCte(recursive_cte) => Err(ErrorCode::UnImplement(format!(
+                "Executing recursive query \"{}\" is not supported yet",
+                recursive_cte.table_name
+            ))),
             _ => Err(ErrorCode::LogicalError(format!(
                 "Unsupported physical plan: {:?}",
                 s_expr.plan()
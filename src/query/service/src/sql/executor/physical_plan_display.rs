@@ -21,6 +21,7 @@ use itertools::Itertools;
 use super::DistributedInsertSelect;
 use crate::sql::executor::AggregateFinal;
 use crate::sql::executor::AggregatePartial;
+use crate::sql::executor::ConstantTableScan;
 use crate::sql::executor::EvalScalar;
 use crate::sql::executor::Exchange;
 use crate::sql::executor::ExchangeSink;
@@ -65,6 +66,7 @@ impl<'a> Display for PhysicalPlanIndentFormatDisplay<'a> {
             PhysicalPlan::ExchangeSource(source) => write!(f, "{}", source)?,
             PhysicalPlan::ExchangeSink(sink) => write!(f, "{}", sink)?,
             PhysicalPlan::UnionAll(union_all) => write!(f, "{}", union_all)?,
+            PhysicalPlan::ConstantTableScan(scan) => write!(f, "{}", scan)?,
             PhysicalPlan::DistributedInsertSelect(insert_select) => write!(f, "{}", insert_select)?,
         }
 
@@ -316,6 +318,12 @@ impl Display for UnionAll {
     }
 }
 
+impl Display for ConstantTableScan {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "ConstantTableScan: rows: {}", self.rows.len())
+    }
+}
+
 impl Display for DistributedInsertSelect {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(f, "DistributedInsertSelect")
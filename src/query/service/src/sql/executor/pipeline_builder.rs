@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::VecDeque;
+use std::sync::atomic::AtomicUsize;
 use std::sync::Arc;
 
 use async_channel::Receiver;
@@ -30,9 +32,11 @@ use common_functions::scalars::FunctionContext;
 use common_functions::scalars::FunctionFactory;
 use common_pipeline_core::Pipe;
 use common_pipeline_sinks::processors::sinks::UnionReceiveSink;
+use parking_lot::Mutex;
 
 use super::AggregateFinal;
 use super::AggregatePartial;
+use super::ConstantTableScan;
 use super::DistributedInsertSelect;
 use super::EvalScalar;
 use super::ExchangeSink;
@@ -46,6 +50,8 @@ use super::TableScan;
 use crate::evaluator::Evaluator;
 use crate::interpreters::fill_missing_columns;
 use crate::pipelines::processors::port::InputPort;
+use crate::pipelines::processors::port::OutputPort;
+use crate::pipelines::processors::processor::ProcessorPtr;
 use crate::pipelines::processors::transforms::ChunkOperator;
 use crate::pipelines::processors::transforms::CompoundChunkOperator;
 use crate::pipelines::processors::transforms::HashJoinDesc;
@@ -56,6 +62,7 @@ use crate::pipelines::processors::transforms::TransformRightJoin;
 use crate::pipelines::processors::transforms::TransformRightSemiAntiJoin;
 use crate::pipelines::processors::AggregatorParams;
 use crate::pipelines::processors::AggregatorTransformParams;
+use crate::pipelines::processors::BlocksSource;
 use crate::pipelines::processors::JoinHashTable;
 use crate::pipelines::processors::MarkJoinCompactor;
 use crate::pipelines::processors::RightJoinCompactor;
@@ -63,14 +70,18 @@ use crate::pipelines::processors::SinkBuildHashTable;
 use crate::pipelines::processors::Sinker;
 use crate::pipelines::processors::SortMergeCompactor;
 use crate::pipelines::processors::TransformAggregator;
+use crate::pipelines::processors::TransformBucketRouter;
 use crate::pipelines::processors::TransformCastSchema;
 use crate::pipelines::processors::TransformHashJoinProbe;
 use crate::pipelines::processors::TransformLimit;
+use crate::pipelines::processors::TransformLimitWithTies;
+use crate::pipelines::processors::TransformMaxResultRows;
 use crate::pipelines::processors::TransformSortMerge;
 use crate::pipelines::processors::TransformSortPartial;
 use crate::pipelines::Pipeline;
 use crate::pipelines::PipelineBuildResult;
 use crate::pipelines::SinkPipeBuilder;
+use crate::pipelines::SourcePipeBuilder;
 use crate::sessions::QueryContext;
 use crate::sessions::TableContext;
 use crate::sql::executor::physical_plan::ColumnID;
@@ -127,6 +138,7 @@ impl PipelineBuilder {
             PhysicalPlan::ExchangeSink(sink) => self.build_exchange_sink(sink),
             PhysicalPlan::ExchangeSource(source) => self.build_exchange_source(source),
             PhysicalPlan::UnionAll(union_all) => self.build_union_all(union_all),
+            PhysicalPlan::ConstantTableScan(scan) => self.build_constant_table_scan(scan),
             PhysicalPlan::DistributedInsertSelect(insert_select) => {
                 self.build_distributed_insert_select(insert_select)
             }
@@ -227,6 +239,36 @@ impl PipelineBuilder {
         Ok(())
     }
 
+    /// Cap the rows of the result set with `max_result_rows`, the query is aborted
+    /// or the result is truncated once exceeded, according to `result_overflow_mode`.
+    pub fn limit_result_rows(ctx: &QueryContext, pipeline: &mut Pipeline) -> Result<()> {
+        let settings = ctx.get_settings();
+        let max_result_rows = settings.get_max_result_rows()? as usize;
+        if max_result_rows == 0 {
+            return Ok(());
+        }
+
+        match settings.get_result_overflow_mode()?.as_str() {
+            "break" => {
+                pipeline.resize(1)?;
+                pipeline.add_transform(|input, output| {
+                    TransformLimit::try_create(Some(max_result_rows), 0, input, output)
+                })
+            }
+            _ => {
+                let rows = Arc::new(AtomicUsize::new(0));
+                pipeline.add_transform(|input, output| {
+                    Ok(TransformMaxResultRows::create(
+                        input,
+                        output,
+                        max_result_rows,
+                        rows.clone(),
+                    ))
+                })
+            }
+        }
+    }
+
     fn build_table_scan(&mut self, scan: &TableScan) -> Result<()> {
         let table = self.ctx.build_table_from_source_plan(&scan.source)?;
         self.ctx.try_set_partitions(scan.source.parts.clone())?;
@@ -262,6 +304,35 @@ impl PipelineBuilder {
         })
     }
 
+    fn build_constant_table_scan(&mut self, scan: &ConstantTableScan) -> Result<()> {
+        let schema = scan.output_schema()?;
+        let columns = schema
+            .fields()
+            .iter()
+            .enumerate()
+            .map(|(index, field)| {
+                let values = scan
+                    .rows
+                    .iter()
+                    .map(|row| row[index].clone())
+                    .collect::<Vec<_>>();
+                field.data_type().create_column(&values)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let block = DataBlock::create(schema, columns);
+
+        let output = OutputPort::create();
+        let source = BlocksSource::create(
+            self.ctx.clone(),
+            output.clone(),
+            Arc::new(Mutex::new(VecDeque::from(vec![block]))),
+        )?;
+        let mut source_builder = SourcePipeBuilder::create();
+        source_builder.add_source(output, source);
+        self.main_pipeline.add_pipe(source_builder.finalize());
+        Ok(())
+    }
+
     fn build_filter(&mut self, filter: &Filter) -> Result<()> {
         self.build_pipeline(&filter.input)?;
 
@@ -346,13 +417,30 @@ impl PipelineBuilder {
 
     fn build_aggregate_partial(&mut self, aggregate: &AggregatePartial) -> Result<()> {
         self.build_pipeline(&aggregate.input)?;
+        let settings = self.ctx.get_settings();
+        let first_seen_order = settings.get_group_by_first_seen_order()?;
+        let spill_threshold_bytes = settings.get_group_by_spill_threshold_bytes()? as usize;
+        // The spilled groups are read back by hash buckets, which loses the first seen order.
+        if first_seen_order && spill_threshold_bytes > 0 {
+            return Err(ErrorCode::BadArguments(
+                "Setting group_by_spill_threshold_bytes cannot be used with group_by_first_seen_order",
+            ));
+        }
         let params = Self::build_aggregator_params(
             aggregate.input.output_schema()?,
             aggregate.output_schema()?,
             &aggregate.group_by,
             &aggregate.agg_funcs,
+            &aggregate.hash_method,
+            spill_threshold_bytes,
         )?;
 
+        // The final aggregator merges the partial outputs in whatever order they arrive,
+        // so the first seen order is only kept with a single partial aggregator.
+        if first_seen_order {
+            self.main_pipeline.resize(1)?;
+        }
+
         self.main_pipeline.add_transform(|input, output| {
             TransformAggregator::try_create_partial(
                 input.clone(),
@@ -373,9 +461,28 @@ impl PipelineBuilder {
             aggregate.output_schema()?,
             &aggregate.group_by,
             &aggregate.agg_funcs,
+            &aggregate.hash_method,
+            0,
         )?;
 
-        self.main_pipeline.resize(1)?;
+        // The partial aggregators generate a block per hash table bucket, unless the first seen
+        // order is kept, so the buckets are routed to several final aggregators.
+        let settings = self.ctx.get_settings();
+        if aggregate.group_by.is_empty() || settings.get_group_by_first_seen_order()? {
+            self.main_pipeline.resize(1)?;
+        } else {
+            let max_threads = settings.get_max_threads()? as usize;
+            let router =
+                TransformBucketRouter::create(self.main_pipeline.output_len(), max_threads.max(1));
+            let inputs_port = router.get_inputs().to_vec();
+            let outputs_port = router.get_outputs().to_vec();
+            self.main_pipeline.add_pipe(Pipe::ResizePipe {
+                inputs_port,
+                outputs_port,
+                processor: ProcessorPtr::create(Box::new(router)),
+            });
+        }
+
         self.main_pipeline.add_transform(|input, output| {
             TransformAggregator::try_create_final(
                 input.clone(),
@@ -393,6 +500,8 @@ impl PipelineBuilder {
         output_schema: DataSchemaRef,
         group_by: &[ColumnID],
         agg_funcs: &[AggregateFunctionDesc],
+        hash_method: &str,
+        spill_threshold_bytes: usize,
     ) -> Result<Arc<AggregatorParams>> {
         let before_schema = input_schema.clone();
         let group_columns = group_by
@@ -428,6 +537,8 @@ impl PipelineBuilder {
             &aggs,
             &output_names,
             &agg_args,
+            hash_method,
+            spill_threshold_bytes,
         )?;
 
         Ok(params)
@@ -475,6 +586,23 @@ impl PipelineBuilder {
         self.build_pipeline(&limit.input)?;
 
         self.main_pipeline.resize(1)?;
+        if !limit.with_ties.is_empty() {
+            let sort_columns: Vec<ColumnID> = limit
+                .with_ties
+                .iter()
+                .map(|desc| desc.order_by.clone())
+                .collect();
+            return self.main_pipeline.add_transform(|input, output| {
+                TransformLimitWithTies::try_create(
+                    limit.limit.unwrap_or(0),
+                    limit.offset,
+                    sort_columns.clone(),
+                    input,
+                    output,
+                )
+            });
+        }
+
         self.main_pipeline.add_transform(|input, output| {
             TransformLimit::try_create(limit.limit, limit.offset, input, output)
         })
@@ -23,8 +23,11 @@ use common_datavalues::DataTypeImpl;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_planner::MetadataRef;
+use tracing::warn;
 
 use crate::sql::binder::scalar::ScalarBinder;
+use crate::sql::binder::scalar_common::contain_aggregate;
+use crate::sql::binder::scalar_common::scalar_equivalence_key;
 use crate::sql::binder::select::SelectList;
 use crate::sql::binder::Binder;
 use crate::sql::binder::ColumnBinding;
@@ -63,14 +66,11 @@ pub struct AggregateInfo {
     /// This is used to find a aggregate function in current context.
     pub aggregate_functions_map: HashMap<String, usize>,
 
-    /// Mapping: (group item display name) -> (index of group item in `group_items`)
+    /// Mapping: (group item equivalence key) -> (index of group item in `group_items`)
     /// This is used to check if a scalar expression is a group item.
     /// For example, `SELECT count(*) FROM t GROUP BY a+1 HAVING a+1+1`.
     /// The group item `a+1` is involved in `a+1+1`, so it's a valid `HAVING`.
-    /// We will check the validity by lookup this map with display name.
-    ///
-    /// TODO(leiysky): so far we are using `Debug` string of `Scalar` as identifier,
-    /// maybe a more reasonable way is needed
+    /// We will check the validity by lookup this map with `scalar_equivalence_key`.
     pub group_items_map: HashMap<String, usize>,
 }
 
@@ -144,6 +144,16 @@ impl<'a> AggregateRewriter<'a> {
     /// add the replaced aggregate function and the arguments into `AggregateInfo`.
     fn replace_aggregate_function(&mut self, aggregate: &AggregateFunction) -> Result<Scalar> {
         let agg_info = &mut self.bind_context.aggregate_info;
+
+        // The same aggregate function may appear more than once, e.g. in both
+        // `SELECT` and `HAVING`, reuse the one we have rewritten before.
+        if let Some(index) = agg_info
+            .aggregate_functions_map
+            .get(&aggregate.display_name)
+        {
+            return Ok(agg_info.aggregate_functions[*index].scalar.clone());
+        }
+
         let mut replaced_args: Vec<Scalar> = Vec::with_capacity(aggregate.args.len());
 
         for (i, arg) in aggregate.args.iter().enumerate() {
@@ -171,6 +181,7 @@ impl<'a> AggregateRewriter<'a> {
                     index,
                     data_type: Box::new(arg.data_type()),
                     visibility: Visibility::Visible,
+                    lineage: None,
                 };
                 replaced_args.push(
                     BoundColumnRef {
@@ -325,7 +336,8 @@ impl<'a> Binder {
             } = expr
             {
                 let (scalar, alias) = Self::resolve_index_item(expr, *index, select_list)?;
-                let key = format!("{:?}", &scalar);
+                Self::check_group_item(bind_context, expr, &scalar)?;
+                let key = scalar_equivalence_key(&scalar);
                 if let Entry::Vacant(entry) = bind_context.aggregate_info.group_items_map.entry(key)
                 {
                     // Add group item if it's not duplicated
@@ -351,15 +363,19 @@ impl<'a> Binder {
                 self.metadata.clone(),
                 &[],
             );
-            let (scalar_expr, data_type) = scalar_binder
-                .bind(expr)
-                .await
-                .or_else(|e| Self::resolve_alias_item(bind_context, expr, available_aliases, e))?;
+            let (scalar_expr, data_type) = match scalar_binder.bind(expr).await {
+                Ok((scalar_expr, data_type)) => {
+                    self.check_shadowed_alias_item(expr, &scalar_expr, available_aliases);
+                    (scalar_expr, data_type)
+                }
+                Err(e) => Self::resolve_alias_item(bind_context, expr, available_aliases, e)?,
+            };
+            Self::check_group_item(bind_context, expr, &scalar_expr)?;
 
             if bind_context
                 .aggregate_info
                 .group_items_map
-                .get(&format!("{:?}", &scalar_expr))
+                .get(&scalar_equivalence_key(&scalar_expr))
                 .is_some()
             {
                 // The group key is duplicated
@@ -386,29 +402,48 @@ impl<'a> Binder {
                 index,
             });
             bind_context.aggregate_info.group_items_map.insert(
-                format!("{:?}", &scalar_expr),
+                scalar_equivalence_key(&scalar_expr),
                 bind_context.aggregate_info.group_items.len() - 1,
             );
         }
         Ok(())
     }
 
+    /// Window functions are evaluated after aggregation, they can't be referenced
+    /// by group items through aliases or positions.
+    fn check_group_item(
+        bind_context: &BindContext,
+        expr: &Expr<'a>,
+        scalar: &Scalar,
+    ) -> Result<()> {
+        if bind_context.window_info.contains_window_function(scalar) {
+            return Err(ErrorCode::SemanticError(expr.span().display_error(
+                "window functions are not allowed in GROUP BY".to_string(),
+            )));
+        }
+        Ok(())
+    }
+
     fn resolve_index_item(
         expr: &Expr<'a>,
         index: u64,
         select_list: &SelectList<'a>,
     ) -> Result<(Scalar, String)> {
-        // Convert to zero-based index
-        let index = index as usize - 1;
-        if index >= select_list.items.len() {
+        if index == 0 || index as usize > select_list.items.len() {
             return Err(ErrorCode::SemanticError(expr.span().display_error(
-                format!("GROUP BY position {} is not in select list", index + 1),
+                format!("GROUP BY position {} is not in select list", index),
             )));
         }
+        // Convert to zero-based index
         let item = select_list
             .items
-            .get(index)
+            .get(index as usize - 1)
             .ok_or_else(|| ErrorCode::LogicalError("Should not fail"))?;
+        if contain_aggregate(&item.scalar)? {
+            return Err(ErrorCode::SemanticError(expr.span().display_error(
+                format!("GROUP BY position {} is an aggregate function", index),
+            )));
+        }
 
         let scalar = item.scalar.clone();
         let alias = item.alias.clone();
@@ -416,6 +451,46 @@ impl<'a> Binder {
         Ok((scalar, alias))
     }
 
+    /// Warn if a GROUP BY item resolved to a column also matches an alias of the select list,
+    /// e.g. `y` in `SELECT x + 1 AS y FROM t GROUP BY y` where `t.y` exists. The column is
+    /// preferred over the alias.
+    fn check_shadowed_alias_item(
+        &self,
+        expr: &Expr<'a>,
+        scalar: &Scalar,
+        available_aliases: &[(ColumnBinding, Scalar)],
+    ) {
+        let column = match (expr, scalar) {
+            (
+                Expr::ColumnRef {
+                    database: None,
+                    table: None,
+                    ..
+                },
+                Scalar::BoundColumnRef(BoundColumnRef { column }),
+            ) => column,
+            _ => return,
+        };
+        let shadowed = available_aliases.iter().any(|(column_binding, alias_scalar)| {
+            column_binding
+                .column_name
+                .eq_ignore_ascii_case(&column.column_name)
+                && !matches!(alias_scalar, Scalar::BoundColumnRef(BoundColumnRef { column: aliased })
+                    if aliased.index == column.index)
+        });
+        if shadowed {
+            let column_name = match &column.table_name {
+                Some(table_name) => format!("{table_name}.{}", column.column_name),
+                None => column.column_name.clone(),
+            };
+            let message = format!(
+                "GROUP BY \"{expr}\" refers to column {column_name}, not the alias of the select list"
+            );
+            warn!("{message}");
+            self.ctx.push_warning(message);
+        }
+    }
+
     fn resolve_alias_item(
         bind_context: &mut BindContext,
         expr: &Expr<'a>,
@@ -448,6 +523,11 @@ impl<'a> Binder {
             )))
         } else {
             let (column_binding, scalar) = available_aliases[result[0]].clone();
+            if contain_aggregate(&scalar)? {
+                return Err(ErrorCode::SemanticError(expr.span().display_error(
+                    format!("GROUP BY \"{}\" refers to an aggregate function", expr),
+                )));
+            }
             // We will add the alias to BindContext, so we can reference it
             // in `HAVING` and `ORDER BY` clause.
             bind_context.columns.push(column_binding.clone());
@@ -458,7 +538,7 @@ impl<'a> Binder {
                 index,
             });
             bind_context.aggregate_info.group_items_map.insert(
-                format!("{:?}", &scalar),
+                scalar_equivalence_key(&scalar),
                 bind_context.aggregate_info.group_items.len() - 1,
             );
 
@@ -468,7 +548,7 @@ impl<'a> Binder {
             }
             .into();
             bind_context.aggregate_info.group_items_map.insert(
-                format!("{:?}", &column_ref),
+                scalar_equivalence_key(&column_ref),
                 bind_context.aggregate_info.group_items.len() - 1,
             );
 
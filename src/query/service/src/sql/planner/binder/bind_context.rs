@@ -13,7 +13,11 @@
 // limitations under the License.
 
 use std::collections::HashMap;
+use std::collections::HashSet;
+use std::fmt::Display;
+use std::fmt::Formatter;
 use std::hash::Hash;
+use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 
 use common_ast::ast::TableAlias;
@@ -28,17 +32,21 @@ use common_exception::Result;
 use common_planner::IndexType;
 use parking_lot::RwLock;
 
+use super::scalar_equivalence_key;
 use super::AggregateInfo;
+use super::WindowInfo;
 use crate::sql::normalize_identifier;
 use crate::sql::optimizer::SExpr;
 use crate::sql::plans::Scalar;
+use crate::sql::plans::ScalarExpr;
 use crate::sql::NameResolutionContext;
 
 #[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
 pub enum Visibility {
     // Default for a column
     Visible,
-    // Inner column of struct
+    // Hidden column, e.g. inner column of struct. It can be referenced by name,
+    // but never appears in wildcard expansion or the result set.
     InVisible,
     // Consider the sql: `select * from t join t1 using(a)`.
     // The result should only contain one `a` column.
@@ -60,6 +68,29 @@ pub struct ColumnBinding {
     pub data_type: Box<DataTypeImpl>,
 
     pub visibility: Visibility,
+
+    /// Where the column comes from, which is kept when the column is renamed by aliases
+    /// or passed through derived tables. `None` if it's unknown.
+    pub lineage: Option<ColumnLineage>,
+}
+
+impl ColumnBinding {
+    /// Describe where the column comes from for error messages, if it can't be told by
+    /// the name of the column, e.g. a column renamed by alias or defined in a subquery.
+    pub fn lineage_hint(&self) -> Option<String> {
+        match &self.lineage {
+            Some(ColumnLineage::Table { table, column, .. })
+                if self.table_name.as_ref() == Some(table) && &self.column_name == column =>
+            {
+                None
+            }
+            Some(lineage) => Some(format!(
+                "\"{}\" is derived from {}",
+                self.column_name, lineage
+            )),
+            None => None,
+        }
+    }
 }
 
 impl PartialEq for ColumnBinding {
@@ -76,12 +107,50 @@ impl Hash for ColumnBinding {
     }
 }
 
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub enum ColumnLineage {
+    /// Column of a base table.
+    Table {
+        database: String,
+        table: String,
+        column: String,
+    },
+    /// Column defined by an expression, e.g. `a + 1` in `SELECT a + 1 AS b FROM t`.
+    Expression(String),
+}
+
+impl Display for ColumnLineage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ColumnLineage::Table {
+                database,
+                table,
+                column,
+            } => write!(f, "column {database}.{table}.{column}"),
+            ColumnLineage::Expression(expr) => write!(f, "expression {expr}"),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum NameResolutionResult {
     Column(ColumnBinding),
     Alias { alias: String, scalar: Scalar },
 }
 
+/// The clause of a `SELECT` statement whose expressions are being bound.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ExprContext {
+    SelectClause,
+    WhereClause,
+    GroupClause,
+    HavingClause,
+    OrderByClause,
+
+    #[default]
+    Unknown,
+}
+
 /// `BindContext` stores all the free variables in a query and tracks the context of binding procedure.
 #[derive(Clone, Debug)]
 pub struct BindContext {
@@ -91,11 +160,26 @@ pub struct BindContext {
 
     pub aggregate_info: AggregateInfo,
 
+    pub window_info: WindowInfo,
+
     /// True if there is aggregation in current context, which means
     /// non-grouping columns cannot be referenced outside aggregation
     /// functions, otherwise a grouping error will be raised.
     pub in_grouping: bool,
 
+    /// The clause being bound, which restricts the expressions allowed in it,
+    /// e.g. aggregate functions are not allowed in `WHERE` clause.
+    pub expr_context: ExprContext,
+
+    /// Database to resolve unqualified table names in, it's inherited by child scopes.
+    /// `None` means the current database of session. When a view is expanded, it's set
+    /// to the database where the view is defined.
+    pub default_database: Option<String>,
+
+    /// Qualified names of the views being expanded, from the outermost one. It's used to
+    /// detect the recursive reference of views.
+    pub view_path: Vec<String>,
+
     /// Format type of query output.
     pub format: Option<String>,
 
@@ -105,8 +189,12 @@ pub struct BindContext {
 #[derive(Clone, Debug)]
 pub struct CteInfo {
     pub columns_alias: Vec<String>,
+    /// The query of CTE bound when it's defined, which is copied with new column indexes
+    /// for each reference but the first one.
     pub s_expr: SExpr,
     pub bind_context: BindContext,
+    /// Whether the bound query has been taken by a reference, it's shared by the clones.
+    pub referenced: Arc<AtomicBool>,
 }
 
 impl BindContext {
@@ -115,7 +203,11 @@ impl BindContext {
             parent: None,
             columns: Vec::new(),
             aggregate_info: AggregateInfo::default(),
+            window_info: WindowInfo::default(),
             in_grouping: false,
+            expr_context: ExprContext::default(),
+            default_database: None,
+            view_path: vec![],
             format: None,
             ctes_map: Arc::new(RwLock::new(HashMap::new())),
         }
@@ -126,7 +218,11 @@ impl BindContext {
             parent: Some(parent.clone()),
             columns: vec![],
             aggregate_info: Default::default(),
+            window_info: Default::default(),
             in_grouping: false,
+            expr_context: ExprContext::default(),
+            default_database: parent.default_database.clone(),
+            view_path: parent.view_path.clone(),
             format: None,
             ctes_map: parent.ctes_map.clone(),
         }
@@ -137,6 +233,8 @@ impl BindContext {
         let mut bind_context = BindContext::new();
         bind_context.parent = self.parent.clone();
         bind_context.ctes_map = self.ctes_map.clone();
+        bind_context.default_database = self.default_database.clone();
+        bind_context.view_path = self.view_path.clone();
         bind_context
     }
 
@@ -150,10 +248,84 @@ impl BindContext {
         &self.columns
     }
 
+    /// Returns the column bindings expanded from a wildcard, which may be qualified by
+    /// table name and database name, e.g. `t.*`. Hidden columns are always excluded,
+    /// while the columns hidden from unqualified wildcard only, e.g. the right side
+    /// column of `USING`, are still visible to qualified wildcard.
+    pub fn wildcard_column_bindings<'a>(
+        &'a self,
+        database: Option<&'a str>,
+        table: Option<&'a str>,
+    ) -> impl Iterator<Item = &'a ColumnBinding> + 'a {
+        self.columns
+            .iter()
+            .filter(move |column_binding| match table {
+                None => column_binding.visibility == Visibility::Visible,
+                Some(table) => {
+                    column_binding.visibility != Visibility::InVisible
+                        && column_binding.table_name.as_deref() == Some(table)
+                        && (database.is_none()
+                            || column_binding.database_name.as_deref() == database)
+                }
+            })
+    }
+
+    /// Returns the column bindings of the result set in order, hidden columns are excluded.
+    pub fn result_column_bindings(&self) -> Vec<ColumnBinding> {
+        self.columns
+            .iter()
+            .filter(|column_binding| column_binding.visibility != Visibility::InVisible)
+            .cloned()
+            .collect()
+    }
+
     pub fn add_column_binding(&mut self, column_binding: ColumnBinding) {
         self.columns.push(column_binding);
     }
 
+    /// Find the column binding of an aggregate function or a group item which has been
+    /// bound in current context with the same expression as `scalar`.
+    ///
+    /// For example, `count(a)` in `ORDER BY` of `SELECT count(a) FROM t ORDER BY count(a)`
+    /// is resolved to the output column of the `count(a)` in select list.
+    pub fn find_by_scalar(&self, scalar: &Scalar) -> Option<ColumnBinding> {
+        let agg_info = &self.aggregate_info;
+        let (column_name, item, lineage) = match scalar {
+            Scalar::AggregateFunction(agg) => {
+                let index = agg_info.aggregate_functions_map.get(&agg.display_name)?;
+                (
+                    agg.display_name.clone(),
+                    &agg_info.aggregate_functions[*index],
+                    Some(ColumnLineage::Expression(agg.display_name.clone())),
+                )
+            }
+            _ => {
+                let index = agg_info
+                    .group_items_map
+                    .get(&scalar_equivalence_key(scalar))?;
+                let lineage = match scalar {
+                    Scalar::BoundColumnRef(column_ref) => column_ref.column.lineage.clone(),
+                    _ => None,
+                };
+                (
+                    "group_item".to_string(),
+                    &agg_info.group_items[*index],
+                    lineage,
+                )
+            }
+        };
+
+        Some(ColumnBinding {
+            database_name: None,
+            table_name: None,
+            column_name,
+            index: item.index,
+            data_type: Box::new(item.scalar.data_type()),
+            visibility: Visibility::Visible,
+            lineage,
+        })
+    }
+
     /// Apply table alias like `SELECT * FROM t AS t1(a, b, c)`.
     /// This method will rename column bindings according to table alias.
     pub fn apply_table_alias(
@@ -161,25 +333,39 @@ impl BindContext {
         alias: &TableAlias,
         name_resolution_ctx: &NameResolutionContext,
     ) -> Result<()> {
-        for column in self.columns.iter_mut() {
-            column.database_name = None;
-            column.table_name = Some(normalize_identifier(&alias.name, name_resolution_ctx).name);
-        }
+        let table_name = normalize_identifier(&alias.name, name_resolution_ctx).name;
+        let column_aliases = alias
+            .columns
+            .iter()
+            .map(|ident| normalize_identifier(ident, name_resolution_ctx).name)
+            .collect::<Vec<_>>();
+        self.rename_columns(table_name, &column_aliases)
+    }
 
-        if alias.columns.len() > self.columns.len() {
+    /// Qualify the columns with `table_name` and rename them with `column_aliases` in order.
+    /// If there are fewer aliases than columns, the remaining columns keep their names.
+    pub fn rename_columns(&mut self, table_name: String, column_aliases: &[String]) -> Result<()> {
+        // Inner columns of struct are not the output of a table, so they can't be renamed.
+        let mut columns = self
+            .columns
+            .iter_mut()
+            .filter(|column| column.visibility != Visibility::InVisible)
+            .collect::<Vec<_>>();
+        if column_aliases.len() > columns.len() {
             return Err(ErrorCode::SemanticError(format!(
-                "table has {} columns available but {} columns specified",
-                self.columns.len(),
-                alias.columns.len()
+                "table \"{}\" has {} columns available but {} columns specified",
+                table_name,
+                columns.len(),
+                column_aliases.len()
             )));
         }
-        for (index, column_name) in alias
-            .columns
-            .iter()
-            .map(|ident| normalize_identifier(ident, name_resolution_ctx).name)
-            .enumerate()
-        {
-            self.columns[index].column_name = column_name;
+        for (column, column_name) in columns.iter_mut().zip(column_aliases.iter()) {
+            column.column_name = column_name.clone();
+        }
+
+        for column in self.columns.iter_mut() {
+            column.database_name = None;
+            column.table_name = Some(table_name.clone());
         }
         Ok(())
     }
@@ -232,18 +418,91 @@ impl BindContext {
         }
 
         if result.is_empty() {
-            Err(ErrorCode::SemanticError(
-                span.display_error("column doesn't exist".to_string()),
-            ))
+            Err(ErrorCode::SemanticError(span.display_error(
+                self.unresolved_name_message(database, table, column, available_aliases),
+            )))
         } else if result.len() > 1 {
-            Err(ErrorCode::SemanticError(
-                span.display_error("column reference is ambiguous".to_string()),
-            ))
+            // List the candidates to help qualifying the reference.
+            let candidates = ambiguous_candidates(&result);
+            Err(ErrorCode::SemanticError(span.display_error(format!(
+                "column reference \"{column}\" is ambiguous, it could refer to {candidates}"
+            ))))
         } else {
             Ok(result.remove(0))
         }
     }
 
+    /// Build the error message of an unresolvable name, with suggestions of the
+    /// similar names that can be resolved in current context.
+    ///
+    /// For example, `column "usre_id" doesn't exist, did you mean "user_id" (table t1)?`
+    fn unresolved_name_message(
+        &self,
+        database: Option<&str>,
+        table: Option<&str>,
+        column: &str,
+        available_aliases: &[(String, Scalar)],
+    ) -> String {
+        let bindings = || {
+            std::iter::successors(Some(self), |bind_context| bind_context.parent.as_deref())
+                .flat_map(|bind_context| bind_context.columns.iter())
+                .filter(|column_binding| column_binding.visibility != Visibility::InVisible)
+        };
+
+        if let Some(table) = table {
+            let table_exists = bindings().any(|column_binding| {
+                column_binding.table_name.as_deref() == Some(table)
+                    && (database.is_none() || column_binding.database_name.as_deref() == database)
+            });
+            if !table_exists {
+                let qualified_table = match database {
+                    Some(database) => format!("{database}.{table}"),
+                    None => table.to_string(),
+                };
+                let candidates = bindings().filter_map(|column_binding| {
+                    let table_name = column_binding.table_name.as_deref()?;
+                    Some((table_name, format!("\"{table_name}\"")))
+                });
+                return format!(
+                    "table \"{qualified_table}\" doesn't exist{}",
+                    did_you_mean(table, candidates)
+                );
+            }
+        }
+
+        let aliases = available_aliases
+            .iter()
+            .filter(|_| database.is_none() && table.is_none())
+            .map(|(alias, _)| (alias.as_str(), format!("\"{alias}\"")));
+        let columns = bindings()
+            .filter(|column_binding| {
+                column_binding.visibility != Visibility::UnqualifiedWildcardInVisible
+                    || table.is_some()
+            })
+            .filter(|column_binding| {
+                table.is_none() || column_binding.table_name.as_deref() == table
+            })
+            .map(|column_binding| {
+                let column_name = column_binding.column_name.as_str();
+                match &column_binding.table_name {
+                    Some(table_name) => (
+                        column_name,
+                        format!("\"{column_name}\" (table {table_name})"),
+                    ),
+                    None => (column_name, format!("\"{column_name}\"")),
+                }
+            });
+        let qualified_column = [database, table, Some(column)]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>()
+            .join(".");
+        format!(
+            "column \"{qualified_column}\" doesn't exist{}",
+            did_you_mean(column, aliases.chain(columns))
+        )
+    }
+
     pub fn match_column_binding(
         database: Option<&str>,
         table: Option<&str>,
@@ -292,31 +551,174 @@ impl BindContext {
     ///
     /// This method is used to retrieve the physical representation of result set of
     /// a query.
+    ///
+    /// Duplicated names are disambiguated with `unique_column_names`, while the column
+    /// bindings of the context keep the names written by user.
     pub fn result_columns(&self) -> Vec<(IndexType, String)> {
-        self.columns
-            .iter()
-            .map(|col| (col.index, col.column_name.clone()))
-            .collect()
+        let columns = self.result_column_bindings();
+        let names = unique_column_names(columns.iter().map(|col| col.column_name.as_str()));
+        columns.iter().map(|col| col.index).zip(names).collect()
     }
 
-    /// Return data scheme.
+    /// Return data scheme, hidden columns are excluded and duplicated column names
+    /// are disambiguated.
     pub fn output_schema(&self) -> DataSchemaRef {
-        let fields = self
-            .columns
+        let columns = self.result_column_bindings();
+        let names = unique_column_names(columns.iter().map(|col| col.column_name.as_str()));
+        let fields = columns
             .iter()
-            .map(|column_binding| {
-                DataField::new(
-                    &column_binding.column_name,
-                    *column_binding.data_type.clone(),
-                )
-            })
+            .zip(names)
+            .map(|(column_binding, name)| DataField::new(&name, *column_binding.data_type.clone()))
             .collect();
         DataSchemaRefExt::create(fields)
     }
 }
 
+/// Make the names of output columns unique. The first occurrence of a name is kept
+/// as is, and the following duplicates are suffixed with `_1`, `_2`, ... skipping the
+/// names which are already used by other columns.
+///
+/// For example, `SELECT a, a, a + 1, a + 1 FROM t` produces `a, a_1, a + 1, a + 1_1`.
+fn unique_column_names<'a>(names: impl IntoIterator<Item = &'a str>) -> Vec<String> {
+    let names: Vec<&str> = names.into_iter().collect();
+    let reserved: HashSet<&str> = names.iter().copied().collect();
+    let mut used: HashSet<String> = HashSet::with_capacity(names.len());
+    let mut result = Vec::with_capacity(names.len());
+    for name in names {
+        if used.insert(name.to_string()) {
+            result.push(name.to_string());
+            continue;
+        }
+        let mut suffix = 1;
+        loop {
+            let candidate = format!("{name}_{suffix}");
+            if !reserved.contains(candidate.as_str()) && used.insert(candidate.clone()) {
+                result.push(candidate);
+                break;
+            }
+            suffix += 1;
+        }
+    }
+    result
+}
+
 impl Default for BindContext {
     fn default() -> Self {
         BindContext::new()
     }
 }
+
+/// The maximum number of candidates listed in the error of an ambiguous column reference.
+const MAX_AMBIGUOUS_CANDIDATES: usize = 5;
+
+/// Format the candidates of an ambiguous column reference as `table.column`. The source
+/// table of a column without table name, e.g. the merged column of `USING`, is taken from
+/// its lineage, and the database is prepended to the candidates with the same table name.
+fn ambiguous_candidates(result: &[NameResolutionResult]) -> String {
+    let qualified_names = result
+        .iter()
+        .map(|candidate| match candidate {
+            NameResolutionResult::Column(column_binding) => {
+                match (&column_binding.table_name, &column_binding.lineage) {
+                    (Some(table_name), _) => (
+                        column_binding.database_name.clone(),
+                        Some(table_name.clone()),
+                        column_binding.column_name.clone(),
+                    ),
+                    (
+                        None,
+                        Some(ColumnLineage::Table {
+                            database, table, ..
+                        }),
+                    ) => (
+                        Some(database.clone()),
+                        Some(table.clone()),
+                        column_binding.column_name.clone(),
+                    ),
+                    (None, _) => (None, None, column_binding.column_name.clone()),
+                }
+            }
+            NameResolutionResult::Alias { alias, .. } => (None, None, alias.clone()),
+        })
+        .collect::<Vec<_>>();
+
+    let mut candidates = Vec::with_capacity(qualified_names.len());
+    for (database, table, column) in qualified_names.iter() {
+        let candidate = match (database, table) {
+            (Some(database), Some(table))
+                if qualified_names
+                    .iter()
+                    .filter(|(_, other_table, other_column)| {
+                        other_table.as_ref() == Some(table) && other_column == column
+                    })
+                    .count()
+                    > 1 =>
+            {
+                format!("{database}.{table}.{column}")
+            }
+            (_, Some(table)) => format!("{table}.{column}"),
+            (_, None) => column.clone(),
+        };
+        if !candidates.contains(&candidate) {
+            candidates.push(candidate);
+        }
+    }
+
+    if candidates.len() > MAX_AMBIGUOUS_CANDIDATES {
+        let more = candidates.len() - MAX_AMBIGUOUS_CANDIDATES;
+        candidates.truncate(MAX_AMBIGUOUS_CANDIDATES);
+        format!("{} and {more} more", candidates.join(", "))
+    } else {
+        candidates.join(", ")
+    }
+}
+
+/// Format up to three candidates which are similar to `name` as a suggestion, the
+/// candidates are pairs of the name to compare and the text to display.
+fn did_you_mean<'a>(name: &str, candidates: impl Iterator<Item = (&'a str, String)>) -> String {
+    // Names too different from the requested one are not helpful.
+    let name_length = name.chars().count();
+    let max_distance = std::cmp::max(name_length, 3) / 3;
+    let mut suggestions: Vec<(usize, String)> = vec![];
+    for (candidate, display) in candidates {
+        let distance = edit_distance(name, candidate);
+        if distance <= max_distance
+            && distance < name_length
+            && !suggestions.iter().any(|(_, s)| s == &display)
+        {
+            suggestions.push((distance, display));
+        }
+    }
+    if suggestions.is_empty() {
+        return String::new();
+    }
+
+    // Stable sort keeps the candidates with the same distance in binding order.
+    suggestions.sort_by_key(|(distance, _)| *distance);
+    let suggestions = suggestions
+        .into_iter()
+        .take(3)
+        .map(|(_, display)| display)
+        .collect::<Vec<_>>();
+    format!(", did you mean {}?", suggestions.join(", "))
+}
+
+/// Levenshtein distance between two strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut distances = (0..=b.len()).collect::<Vec<_>>();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = distances[0];
+        distances[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let above = distances[j + 1];
+            distances[j + 1] = if ca == *cb {
+                diagonal
+            } else {
+                1 + diagonal.min(above).min(distances[j])
+            };
+            diagonal = above;
+        }
+    }
+    distances[b.len()]
+}
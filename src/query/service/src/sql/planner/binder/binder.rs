@@ -17,13 +17,17 @@ use std::sync::Arc;
 use common_ast::ast::ExplainKind;
 use common_ast::ast::Statement;
 use common_ast::parser::parse_sql;
+use common_ast::parser::token::Token;
 use common_ast::parser::tokenize_sql;
 use common_ast::Backtrace;
 use common_ast::Dialect;
+use common_ast::DisplayError;
 use common_ast::UDFValidator;
+use common_ast::VisitorMut;
 use common_catalog::catalog::CatalogManager;
 use common_catalog::table_context::TableContext;
 use common_datavalues::DataTypeImpl;
+use common_exception::ErrorCode;
 use common_exception::Result;
 use common_meta_types::UserDefinedFunction;
 use common_planner::plans::AlterUDFPlan;
@@ -36,7 +40,9 @@ use common_planner::plans::DropUDFPlan;
 use common_planner::plans::DropUserPlan;
 use common_planner::plans::ShowGrantsPlan;
 use common_planner::plans::UseDatabasePlan;
+use common_planner::Metadata;
 use common_planner::MetadataRef;
+use tracing::warn;
 
 use crate::sql::plans::Plan;
 use crate::sql::plans::RewriteKind;
@@ -75,8 +81,66 @@ impl<'a> Binder {
     }
 
     pub async fn bind(mut self, stmt: &Statement<'a>) -> Result<Plan> {
+        let mut stmt = stmt.clone();
+        let mut numbering = PlaceholderNumbering::default();
+        numbering.visit_statement(&mut stmt);
+        if numbering.anonymous > 0 && numbering.numbered {
+            return Err(ErrorCode::SemanticError(
+                "Placeholders `?` and `$n` cannot be mixed in a statement",
+            ));
+        }
+        let stmt = &stmt;
+
         let init_bind_context = BindContext::new();
-        self.bind_statement(&init_bind_context, stmt).await
+        let mut plan = self.bind_statement(&init_bind_context, stmt).await?;
+
+        // A placeholder used before its type is inferred is bound as `NULL`, so bind
+        // the statement again with the inferred types of the placeholders.
+        if self.metadata.read().has_pending_placeholder_usage() {
+            let placeholders = self.placeholder_types()?;
+            *self.metadata.write() = Metadata::default();
+            for (index, data_type) in placeholders.into_iter().enumerate() {
+                self.metadata
+                    .write()
+                    .add_placeholder(index + 1, Some(data_type));
+            }
+            plan = self.bind_statement(&BindContext::new(), stmt).await?;
+        }
+
+        if !self.metadata.read().placeholders().is_empty() {
+            self.placeholder_types()?;
+            if !matches!(plan, Plan::Query { .. } | Plan::Explain { .. }) {
+                return Err(ErrorCode::UnImplement(
+                    "Placeholders are only supported in query statements",
+                ));
+            }
+        }
+        Ok(plan)
+    }
+
+    /// Get the inferred types of the placeholders ordered by their indexes, the placeholders
+    /// must be numbered consecutively from `$1`.
+    fn placeholder_types(&self) -> Result<Vec<DataTypeImpl>> {
+        let metadata = self.metadata.read();
+        let mut data_types = Vec::with_capacity(metadata.placeholders().len());
+        for (expected, (index, column_index)) in (1..).zip(metadata.placeholders()) {
+            if *index != expected {
+                return Err(ErrorCode::SemanticError(format!(
+                    "Placeholder ${expected} is not referenced, placeholders must be numbered consecutively from $1"
+                )));
+            }
+            match column_index {
+                Some(column_index) => {
+                    data_types.push(metadata.column(*column_index).data_type().clone());
+                }
+                None => {
+                    return Err(ErrorCode::SemanticError(format!(
+                        "Could not determine data type of placeholder ${index}"
+                    )));
+                }
+            }
+        }
+        Ok(data_types)
     }
 
     #[async_recursion::async_recursion]
@@ -126,6 +190,10 @@ impl<'a> Binder {
                  self.bind_rewrite_to_query(bind_context, "SELECT \"Engine\", \"Comment\" FROM system.engines ORDER BY \"Engine\" ASC", RewriteKind::ShowEngines)
                     .await?
             },
+            Statement::ShowWarnings => {
+                self.bind_rewrite_to_query(bind_context, "SELECT level, message FROM system.warnings", RewriteKind::ShowWarnings)
+                    .await?
+            }
             Statement::ShowSettings { like } => self.bind_show_settings(bind_context, like).await?,
 
             // Databases
@@ -345,6 +413,19 @@ impl<'a> Binder {
         Ok(plan)
     }
 
+    /// Report an alias or a CTE which shadows a column or a table, the reference to the
+    /// name may be resolved to either of them depending on where it appears. The query
+    /// fails if `strict_alias_shadowing` is enabled, otherwise a warning is reported to the
+    /// session, which can be checked with `SHOW WARNINGS`.
+    pub(super) fn report_shadowing(&self, span: &Token<'a>, message: String) -> Result<()> {
+        if self.ctx.get_settings().get_strict_alias_shadowing()? {
+            return Err(ErrorCode::SemanticError(span.display_error(message)));
+        }
+        warn!("{message}");
+        self.ctx.push_warning(message);
+        Ok(())
+    }
+
     /// Create a new ColumnBinding with assigned index
     pub(crate) fn create_column_binding(
         &mut self,
@@ -364,6 +445,29 @@ impl<'a> Binder {
             index,
             data_type: Box::new(data_type),
             visibility: Visibility::Visible,
+            lineage: None,
+        }
+    }
+}
+
+/// Number the `?` placeholders by their positions in the statement, the n-th `?` is `$n`.
+#[derive(Default)]
+struct PlaceholderNumbering {
+    anonymous: usize,
+    numbered: bool,
+}
+
+impl VisitorMut for PlaceholderNumbering {
+    fn visit_placeholder(&mut self, span: &mut &[Token<'_>], index: &mut usize) {
+        if span.first().map(|token| token.text()) == Some("?") {
+            self.anonymous += 1;
+            *index = self.anonymous;
+        } else {
+            self.numbered = true;
         }
     }
+
+    fn visit_explain(&mut self, _kind: &mut ExplainKind, query: &mut Statement<'_>) {
+        self.visit_statement(query);
+    }
 }
@@ -0,0 +1,295 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::sync::atomic::Ordering;
+
+use common_planner::IndexType;
+use common_planner::Metadata;
+
+use crate::sql::binder::CteInfo;
+use crate::sql::optimizer::SExpr;
+use crate::sql::planner::binder::Binder;
+use crate::sql::plans::AggregateFunction;
+use crate::sql::plans::AndExpr;
+use crate::sql::plans::BoundColumnRef;
+use crate::sql::plans::CastExpr;
+use crate::sql::plans::ComparisonExpr;
+use crate::sql::plans::Exchange;
+use crate::sql::plans::FunctionCall;
+use crate::sql::plans::OrExpr;
+use crate::sql::plans::RelOperator;
+use crate::sql::plans::Scalar;
+use crate::sql::plans::SubqueryExpr;
+use crate::sql::BindContext;
+
+impl Binder {
+    /// Get an instance of the query of CTE bound at definition. The first reference takes
+    /// the bound query as is, and each of the others takes a copy of it with new tables and
+    /// columns, otherwise the instances of a CTE referenced more than once, e.g. in a self
+    /// join, will have conflicting column indexes.
+    pub(super) fn instantiate_cte(&mut self, cte_info: &CteInfo) -> (SExpr, BindContext) {
+        let mut s_expr = cte_info.s_expr.clone();
+        let mut bind_context = cte_info.bind_context.clone();
+        if !cte_info.referenced.swap(true, Ordering::SeqCst) {
+            return (s_expr, bind_context);
+        }
+
+        let mut metadata = self.metadata.write();
+        let mut remapper = ColumnRemapper {
+            metadata: &mut metadata,
+            tables: HashMap::new(),
+            columns: HashMap::new(),
+        };
+        remapper.remap_s_expr(&mut s_expr);
+        for column in bind_context.columns.iter_mut() {
+            column.index = remapper.column(column.index);
+        }
+        (s_expr, bind_context)
+    }
+}
+
+/// Copy the tables and columns produced by a plan to new ones in metadata, and replace
+/// their indexes in the plan. The columns not produced by the plan, such as the outer
+/// columns and placeholders, are kept as is.
+struct ColumnRemapper<'a> {
+    metadata: &'a mut Metadata,
+    tables: HashMap<IndexType, IndexType>,
+    columns: HashMap<IndexType, IndexType>,
+}
+
+impl<'a> ColumnRemapper<'a> {
+    fn column(&self, index: IndexType) -> IndexType {
+        self.columns.get(&index).cloned().unwrap_or(index)
+    }
+
+    fn add_table(&mut self, index: IndexType) -> IndexType {
+        if let Some(new_index) = self.tables.get(&index) {
+            return *new_index;
+        }
+        let table = self.metadata.table(index).clone();
+        let new_index = self.metadata.add_table(
+            table.catalog().to_string(),
+            table.database().to_string(),
+            table.table(),
+        );
+        // The columns of a table are added in the same order as the fields of its schema.
+        let columns = self.metadata.columns_by_table_index(index);
+        let new_columns = self.metadata.columns_by_table_index(new_index);
+        for (column, new_column) in columns.iter().zip(new_columns.iter()) {
+            self.columns.insert(column.index(), new_column.index());
+        }
+        self.tables.insert(index, new_index);
+        new_index
+    }
+
+    fn add_column(&mut self, index: &mut IndexType) {
+        let new_index = match self.columns.get(index) {
+            Some(new_index) => *new_index,
+            None => {
+                let column = self.metadata.column(*index).clone();
+                let new_index = self.metadata.add_column(
+                    column.name().to_string(),
+                    column.data_type().clone(),
+                    None,
+                    column
+                        .path_indices()
+                        .map(|path_indices| path_indices.to_vec()),
+                );
+                self.columns.insert(*index, new_index);
+                new_index
+            }
+        };
+        *index = new_index;
+    }
+
+    fn remap_index(&self, index: &mut IndexType) {
+        *index = self.column(*index);
+    }
+
+    // The children are remapped first, as the columns produced by them are referenced by
+    // the parent.
+    fn remap_s_expr(&mut self, s_expr: &mut SExpr) {
+        for child in s_expr.children.iter_mut() {
+            self.remap_s_expr(child);
+        }
+
+        match &mut s_expr.plan {
+            RelOperator::LogicalGet(get) => {
+                get.table_index = self.add_table(get.table_index);
+                get.columns = get
+                    .columns
+                    .iter()
+                    .map(|index| self.column(*index))
+                    .collect();
+                for scalar in get.push_down_predicates.iter_mut().flatten() {
+                    self.remap_scalar(scalar);
+                }
+                for item in get.order_by.iter_mut().flatten() {
+                    self.remap_index(&mut item.index);
+                }
+                if let Some(prewhere) = &mut get.prewhere {
+                    prewhere.output_columns = prewhere
+                        .output_columns
+                        .iter()
+                        .map(|index| self.column(*index))
+                        .collect();
+                    prewhere.prewhere_columns = prewhere
+                        .prewhere_columns
+                        .iter()
+                        .map(|index| self.column(*index))
+                        .collect();
+                    for scalar in prewhere.predicates.iter_mut() {
+                        self.remap_scalar(scalar);
+                    }
+                }
+            }
+            RelOperator::LogicalInnerJoin(join) => {
+                for scalar in join
+                    .left_conditions
+                    .iter_mut()
+                    .chain(join.right_conditions.iter_mut())
+                    .chain(join.other_conditions.iter_mut())
+                {
+                    self.remap_scalar(scalar);
+                }
+                if let Some(marker_index) = &mut join.marker_index {
+                    self.add_column(marker_index);
+                }
+            }
+            RelOperator::EvalScalar(eval_scalar) => {
+                for item in eval_scalar.items.iter_mut() {
+                    self.remap_scalar(&mut item.scalar);
+                    self.add_column(&mut item.index);
+                }
+            }
+            RelOperator::Filter(filter) => {
+                for scalar in filter.predicates.iter_mut() {
+                    self.remap_scalar(scalar);
+                }
+            }
+            RelOperator::Aggregate(aggregate) => {
+                for item in aggregate
+                    .group_items
+                    .iter_mut()
+                    .chain(aggregate.aggregate_functions.iter_mut())
+                {
+                    self.remap_scalar(&mut item.scalar);
+                    self.add_column(&mut item.index);
+                }
+            }
+            RelOperator::Sort(sort) => {
+                for item in sort.items.iter_mut() {
+                    self.remap_index(&mut item.index);
+                }
+            }
+            RelOperator::Limit(limit) => {
+                for item in limit.with_ties.iter_mut() {
+                    self.remap_index(&mut item.index);
+                }
+            }
+            RelOperator::Exchange(Exchange::Hash(scalars)) => {
+                for scalar in scalars.iter_mut() {
+                    self.remap_scalar(scalar);
+                }
+            }
+            RelOperator::UnionAll(union_all) => {
+                for (left, right) in union_all.pairs.iter_mut() {
+                    self.remap_index(left);
+                    self.remap_index(right);
+                }
+            }
+            RelOperator::RecursiveCte(recursive_cte) => {
+                for index in recursive_cte
+                    .columns
+                    .iter_mut()
+                    .chain(recursive_cte.recursive_columns.iter_mut())
+                {
+                    self.remap_index(index);
+                }
+            }
+            RelOperator::ConstantTableScan(scan) => {
+                for index in scan.columns.iter_mut() {
+                    self.add_column(index);
+                }
+            }
+            RelOperator::Window(window) => {
+                for func in window.window_functions.iter_mut() {
+                    for scalar in func
+                        .args
+                        .iter_mut()
+                        .chain(func.partition_by.iter_mut())
+                        .chain(func.order_by.iter_mut().map(|order_by| &mut order_by.expr))
+                    {
+                        self.remap_scalar(scalar);
+                    }
+                    self.add_column(&mut func.index);
+                }
+            }
+            // The physical operators are not produced by binder.
+            RelOperator::PhysicalScan(_)
+            | RelOperator::PhysicalHashJoin(_)
+            | RelOperator::Exchange(_)
+            | RelOperator::DummyTableScan(_)
+            | RelOperator::Pattern(_) => {}
+        }
+    }
+
+    fn remap_scalar(&mut self, scalar: &mut Scalar) {
+        match scalar {
+            Scalar::BoundColumnRef(BoundColumnRef { column }) => {
+                self.remap_index(&mut column.index);
+            }
+            Scalar::ConstantExpr(_) => {}
+            Scalar::AndExpr(AndExpr { left, right, .. })
+            | Scalar::OrExpr(OrExpr { left, right, .. })
+            | Scalar::ComparisonExpr(ComparisonExpr { left, right, .. }) => {
+                self.remap_scalar(left);
+                self.remap_scalar(right);
+            }
+            Scalar::AggregateFunction(AggregateFunction { args, .. })
+            | Scalar::FunctionCall(FunctionCall {
+                arguments: args, ..
+            }) => {
+                for arg in args.iter_mut() {
+                    self.remap_scalar(arg);
+                }
+            }
+            Scalar::CastExpr(CastExpr { argument, .. }) => {
+                self.remap_scalar(argument);
+            }
+            Scalar::SubqueryExpr(SubqueryExpr {
+                subquery,
+                child_expr,
+                output_column,
+                projection_index,
+                outer_columns,
+                ..
+            }) => {
+                self.remap_s_expr(subquery);
+                if let Some(child_expr) = child_expr {
+                    self.remap_scalar(child_expr);
+                }
+                self.remap_index(output_column);
+                if let Some(projection_index) = projection_index {
+                    self.remap_index(projection_index);
+                }
+                *outer_columns = outer_columns
+                    .iter()
+                    .map(|index| self.column(*index))
+                    .collect();
+            }
+        }
+    }
+}
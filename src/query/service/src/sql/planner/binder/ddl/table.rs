@@ -378,17 +378,7 @@ impl<'a> Binder {
                 // `CREATE TABLE AS SELECT ...` without column definitions
                 let init_bind_context = BindContext::new();
                 let (_s_expr, bind_context) = self.bind_query(&init_bind_context, query).await?;
-                let fields = bind_context
-                    .columns
-                    .iter()
-                    .map(|column_binding| {
-                        DataField::new(
-                            &column_binding.column_name,
-                            *column_binding.data_type.clone(),
-                        )
-                    })
-                    .collect();
-                let schema = DataSchemaRefExt::create(fields);
+                let schema = bind_context.output_schema();
                 Self::validate_create_table_schema(&schema)?;
                 (schema, vec![], vec![])
             }
@@ -398,16 +388,7 @@ impl<'a> Binder {
                     self.analyze_create_table_schema(source).await?;
                 let init_bind_context = BindContext::new();
                 let (_s_expr, bind_context) = self.bind_query(&init_bind_context, query).await?;
-                let query_fields: Vec<DataField> = bind_context
-                    .columns
-                    .iter()
-                    .map(|column_binding| {
-                        DataField::new(
-                            &column_binding.column_name,
-                            *column_binding.data_type.clone(),
-                        )
-                    })
-                    .collect();
+                let query_fields = bind_context.output_schema().fields().clone();
                 let source_fields = source_schema.fields().clone();
                 let source_fields = self.concat_fields(source_fields, query_fields);
                 let schema = DataSchemaRefExt::create(source_fields);
@@ -914,6 +895,7 @@ impl<'a> Binder {
                 index: 0,
                 data_type: Box::new(field.data_type().clone()),
                 visibility: Visibility::Visible,
+                lineage: None,
             };
             bind_context.columns.push(column);
         }
@@ -15,6 +15,9 @@
 use common_ast::ast::AlterViewStmt;
 use common_ast::ast::CreateViewStmt;
 use common_ast::ast::DropViewStmt;
+use common_ast::ast::Identifier;
+use common_ast::ast::Query;
+use common_exception::ErrorCode;
 use common_exception::Result;
 use common_planner::plans::AlterViewPlan;
 use common_planner::plans::CreateViewPlan;
@@ -23,6 +26,7 @@ use common_planner::plans::DropViewPlan;
 use crate::sql::binder::Binder;
 use crate::sql::planner::semantic::normalize_identifier;
 use crate::sql::plans::Plan;
+use crate::sql::BindContext;
 
 impl<'a> Binder {
     pub(in crate::sql::planner::binder) async fn bind_create_view(
@@ -34,6 +38,7 @@ impl<'a> Binder {
             catalog,
             database,
             view,
+            columns,
             query,
         } = stmt;
 
@@ -47,6 +52,9 @@ impl<'a> Binder {
             .map(|ident| normalize_identifier(ident, &self.name_resolution_ctx).name)
             .unwrap_or_else(|| self.ctx.get_current_database());
         let viewname = normalize_identifier(view, &self.name_resolution_ctx).name;
+        let column_names = self
+            .analyze_view_columns(&database, &viewname, columns, query)
+            .await?;
         let subquery = format!("{}", query);
 
         let plan = CreateViewPlan {
@@ -55,6 +63,7 @@ impl<'a> Binder {
             catalog,
             database,
             viewname,
+            column_names,
             subquery,
         };
         Ok(Plan::CreateView(Box::new(plan)))
@@ -68,6 +77,7 @@ impl<'a> Binder {
             catalog,
             database,
             view,
+            columns,
             query,
         } = stmt;
 
@@ -81,6 +91,9 @@ impl<'a> Binder {
             .map(|ident| normalize_identifier(ident, &self.name_resolution_ctx).name)
             .unwrap_or_else(|| self.ctx.get_current_database());
         let viewname = normalize_identifier(view, &self.name_resolution_ctx).name;
+        let column_names = self
+            .analyze_view_columns(&database, &viewname, columns, query)
+            .await?;
         let subquery = format!("{}", query);
 
         let plan = AlterViewPlan {
@@ -88,11 +101,42 @@ impl<'a> Binder {
             catalog,
             database,
             viewname,
+            column_names,
             subquery,
         };
         Ok(Plan::AlterView(Box::new(plan)))
     }
 
+    /// Normalize the column names declared by a view, the query of view is bound to
+    /// check that it returns enough columns to be named.
+    async fn analyze_view_columns(
+        &mut self,
+        database: &str,
+        viewname: &str,
+        columns: &[Identifier<'a>],
+        query: &Query<'a>,
+    ) -> Result<Vec<String>> {
+        if columns.is_empty() {
+            return Ok(vec![]);
+        }
+        let column_names = columns
+            .iter()
+            .map(|ident| normalize_identifier(ident, &self.name_resolution_ctx).name)
+            .collect::<Vec<_>>();
+
+        let mut bind_context = BindContext::new();
+        bind_context.default_database = Some(database.to_string());
+        let (_, bind_context) = self.bind_query(&bind_context, query).await?;
+        let num_columns = bind_context.result_columns().len();
+        if column_names.len() > num_columns {
+            return Err(ErrorCode::SemanticError(format!(
+                "view \"{viewname}\" specifies {} column names, but its query returns {num_columns} columns",
+                column_names.len()
+            )));
+        }
+        Ok(column_names)
+    }
+
     pub(in crate::sql::planner::binder) async fn bind_drop_view(
         &mut self,
         stmt: &DropViewStmt<'a>,
@@ -14,6 +14,8 @@
 
 use common_ast::ast::Expr;
 use common_ast::parser::token::Token;
+use common_ast::DisplayError;
+use common_exception::ErrorCode;
 use common_exception::Result;
 
 use super::select::SelectList;
@@ -49,6 +51,11 @@ impl<'a> Binder {
             &aliases,
         );
         let (scalar, _) = scalar_binder.bind(having).await?;
+        if bind_context.window_info.contains_window_function(&scalar) {
+            return Err(ErrorCode::SemanticError(having.span().display_error(
+                "window functions are not allowed in HAVING".to_string(),
+            )));
+        }
         let mut rewriter = AggregateRewriter::new(bind_context, self.metadata.clone());
         Ok((rewriter.visit(&scalar)?, having.span()))
     }
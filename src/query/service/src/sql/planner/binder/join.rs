@@ -23,12 +23,16 @@ use common_datavalues::type_coercion::compare_coercion;
 use common_datavalues::wrap_nullable;
 use common_exception::ErrorCode;
 use common_exception::Result;
+use common_functions::scalars::FunctionFactory;
 use common_planner::MetadataRef;
+use tracing::warn;
 
 use crate::sessions::TableContext;
 use crate::sql::binder::scalar_common::split_conjunctions;
 use crate::sql::binder::scalar_common::split_equivalent_predicate;
 use crate::sql::binder::wrap_cast;
+use crate::sql::binder::ColumnBinding;
+use crate::sql::binder::ColumnLineage;
 use crate::sql::binder::Visibility;
 use crate::sql::normalize_identifier;
 use crate::sql::optimizer::ColumnSet;
@@ -37,10 +41,13 @@ use crate::sql::planner::binder::scalar::ScalarBinder;
 use crate::sql::planner::binder::Binder;
 use crate::sql::planner::semantic::NameResolutionContext;
 use crate::sql::plans::BoundColumnRef;
+use crate::sql::plans::EvalScalar;
+use crate::sql::plans::FunctionCall;
 use crate::sql::plans::JoinType;
 use crate::sql::plans::LogicalInnerJoin;
 use crate::sql::plans::Scalar;
 use crate::sql::plans::ScalarExpr;
+use crate::sql::plans::ScalarItem;
 use crate::sql::BindContext;
 
 impl<'a> Binder {
@@ -59,49 +66,19 @@ impl<'a> Binder {
 
         let mut bind_context = bind_context.replace();
 
-        match &join.op {
-            JoinOperator::LeftOuter => {
-                for column in left_context.all_column_bindings() {
-                    bind_context.add_column_binding(column.clone());
-                }
-                for column in right_context.all_column_bindings().iter() {
-                    let mut nullable_column = column.clone();
-                    nullable_column.data_type = Box::new(wrap_nullable(&column.data_type));
-                    bind_context.add_column_binding(nullable_column);
-                }
-            }
-            JoinOperator::RightOuter => {
-                for column in left_context.all_column_bindings() {
-                    let mut nullable_column = column.clone();
-                    nullable_column.data_type = Box::new(wrap_nullable(&column.data_type));
-                    bind_context.add_column_binding(nullable_column);
-                }
-
-                for column in right_context.all_column_bindings().iter() {
-                    bind_context.add_column_binding(column.clone());
-                }
-            }
-            JoinOperator::FullOuter => {
-                for column in left_context.all_column_bindings() {
-                    let mut nullable_column = column.clone();
-                    nullable_column.data_type = Box::new(wrap_nullable(&column.data_type));
-                    bind_context.add_column_binding(nullable_column);
-                }
-
-                for column in right_context.all_column_bindings().iter() {
-                    let mut nullable_column = column.clone();
-                    nullable_column.data_type = Box::new(wrap_nullable(&column.data_type));
-                    bind_context.add_column_binding(nullable_column);
-                }
-            }
-            _ => {
-                for column in left_context.all_column_bindings() {
-                    bind_context.add_column_binding(column.clone());
-                }
-                for column in right_context.all_column_bindings() {
-                    bind_context.add_column_binding(column.clone());
-                }
-            }
+        // Columns of the inner side of outer join can be NULL even if they are not nullable
+        // in the table schema, so they are bound with the nullable version of their types.
+        let (left_nullable, right_nullable) = match &join.op {
+            JoinOperator::LeftOuter => (false, true),
+            JoinOperator::RightOuter => (true, false),
+            JoinOperator::FullOuter => (true, true),
+            _ => (false, false),
+        };
+        for column in left_context.all_column_bindings() {
+            bind_context.add_column_binding(join_column_binding(column, left_nullable));
+        }
+        for column in right_context.all_column_bindings() {
+            bind_context.add_column_binding(join_column_binding(column, right_nullable));
         }
 
         match &join.op {
@@ -123,6 +100,7 @@ impl<'a> Binder {
         let mut left_join_conditions: Vec<Scalar> = vec![];
         let mut right_join_conditions: Vec<Scalar> = vec![];
         let mut other_conditions: Vec<Scalar> = vec![];
+        let mut coalesced_columns: Vec<ScalarItem> = vec![];
         let mut join_condition_resolver = JoinConditionResolver::new(
             self.ctx.clone(),
             &self.name_resolution_ctx,
@@ -137,11 +115,25 @@ impl<'a> Binder {
                 &mut left_join_conditions,
                 &mut right_join_conditions,
                 &mut other_conditions,
+                &mut coalesced_columns,
                 &join.op,
             )
             .await?;
 
+        // NATURAL JOIN without common columns is a cross join.
+        let is_cross_join = join.condition == JoinCondition::Natural
+            && left_join_conditions.is_empty()
+            && other_conditions.is_empty();
+
         let s_expr = match &join.op {
+            JoinOperator::Inner if is_cross_join => self.bind_join_with_type(
+                JoinType::Cross,
+                left_join_conditions,
+                right_join_conditions,
+                other_conditions,
+                left_child,
+                right_child,
+            ),
             JoinOperator::Inner => self.bind_join_with_type(
                 JoinType::Inner,
                 left_join_conditions,
@@ -228,6 +220,16 @@ impl<'a> Binder {
             }
         }?;
 
+        // Evaluate the merged `USING` columns of full outer join on top of the join.
+        let s_expr = if coalesced_columns.is_empty() {
+            s_expr
+        } else {
+            let eval_scalar = EvalScalar {
+                items: coalesced_columns,
+            };
+            SExpr::create_unary(eval_scalar.into(), s_expr)
+        };
+
         Ok((s_expr, bind_context))
     }
 
@@ -267,26 +269,17 @@ pub fn check_duplicate_join_tables(
     left_context: &BindContext,
     right_context: &BindContext,
 ) -> Result<()> {
-    let left_column_bindings = left_context.all_column_bindings();
-    let left_table_name = if left_column_bindings.is_empty() {
-        None
-    } else {
-        left_column_bindings[0].table_name.as_ref()
-    };
-
-    let right_column_bindings = right_context.all_column_bindings();
-    let right_table_name = if right_column_bindings.is_empty() {
-        None
-    } else {
-        right_column_bindings[0].table_name.as_ref()
-    };
+    // Tables with the same name from different databases are not duplicated,
+    // e.g. `SELECT * FROM db1.t JOIN db2.t`.
+    let left_table_name = first_table_name(left_context);
+    let right_table_name = first_table_name(right_context);
 
     if let Some(left) = left_table_name {
         if let Some(right) = right_table_name {
-            if left.eq(right) {
+            if left.eq(&right) {
                 return Err(ErrorCode::SemanticError(format!(
                     "Duplicated table name {} in the same FROM clause",
-                    left
+                    left.1
                 )));
             }
         }
@@ -331,6 +324,7 @@ impl<'a> JoinConditionResolver<'a> {
         left_join_conditions: &mut Vec<Scalar>,
         right_join_conditions: &mut Vec<Scalar>,
         other_join_conditions: &mut Vec<Scalar>,
+        coalesced_columns: &mut Vec<ScalarItem>,
         join_op: &JoinOperator,
     ) -> Result<()> {
         match &self.join_condition {
@@ -352,6 +346,7 @@ impl<'a> JoinConditionResolver<'a> {
                     using_columns,
                     left_join_conditions,
                     right_join_conditions,
+                    coalesced_columns,
                     join_op,
                 )
                 .await?;
@@ -359,14 +354,19 @@ impl<'a> JoinConditionResolver<'a> {
             JoinCondition::Natural => {
                 // NATURAL is a shorthand form of USING: it forms a USING list consisting of all column names that appear in both input tables
                 // As with USING, these columns appear only once in the output table
-                // Todo(xudong963) If there are no common column names, NATURAL JOIN behaves like JOIN ... ON TRUE, producing a cross-product join.
                 let mut using_columns = vec![];
                 // Find common columns in both input tables
                 self.find_using_columns(&mut using_columns)?;
+                if using_columns.is_empty() {
+                    // If there are no common column names, NATURAL JOIN behaves like JOIN ... ON TRUE,
+                    // producing a cross-product join.
+                    warn!("NATURAL JOIN without common columns is treated as a cross join");
+                }
                 self.resolve_using(
                     using_columns,
                     left_join_conditions,
                     right_join_conditions,
+                    coalesced_columns,
                     join_op,
                 )
                 .await?
@@ -428,69 +428,102 @@ impl<'a> JoinConditionResolver<'a> {
         Ok(())
     }
 
+    /// Resolve `USING` columns of join, each of them is merged into a single column binding,
+    /// which is placed before the other columns and used to resolve unqualified references
+    /// of the column. The original columns are only visible to qualified references,
+    /// e.g. `SELECT id, t1.id, t2.id FROM t1 JOIN t2 USING (id)`.
+    ///
+    /// The merged column is the column of left table for inner and left outer join, and
+    /// the column of right table for right outer join. For full outer join, it's a new
+    /// column coalesced from the both sides, which will be evaluated on top of the join.
     async fn resolve_using(
         &mut self,
         using_columns: Vec<String>,
         left_join_conditions: &mut Vec<Scalar>,
         right_join_conditions: &mut Vec<Scalar>,
+        coalesced_columns: &mut Vec<ScalarItem>,
         join_op: &JoinOperator,
     ) -> Result<()> {
-        for join_key in using_columns.iter() {
+        let mut merged_columns = Vec::with_capacity(using_columns.len());
+        for (i, join_key) in using_columns.iter().enumerate() {
             let join_key_name = join_key.as_str();
-            let left_scalar = if let Some(col_binding) = self
-                .left_context
-                .columns
-                .iter()
-                .find(|col_binding| col_binding.column_name == join_key_name)
-            {
-                Scalar::BoundColumnRef(BoundColumnRef {
-                    column: col_binding.clone(),
-                })
-            } else {
+            if using_columns[..i].contains(join_key) {
                 return Err(ErrorCode::SemanticError(format!(
-                    "column {} specified in USING clause does not exist in left table",
+                    "column {} appears more than once in USING clause",
                     join_key_name
                 )));
-            };
+            }
 
-            let right_scalar = if let Some(col_binding) = self
-                .right_context
-                .columns
-                .iter()
-                .find(|col_binding| col_binding.column_name == join_key_name)
-            {
-                Scalar::BoundColumnRef(BoundColumnRef {
-                    column: col_binding.clone(),
-                })
-            } else {
-                return Err(ErrorCode::SemanticError(format!(
-                    "column {} specified in USING clause does not exist in right table",
-                    join_key_name
-                )));
-            };
-            let idx = if let JoinOperator::RightOuter = join_op {
-                0
-            } else {
-                1
-            };
-            if let Some(col_binding) = self
-                .join_context
-                .columns
-                .iter_mut()
-                .filter(|col_binding| col_binding.column_name == join_key_name)
-                .nth(idx)
-            {
-                // Always make the second using column in the join_context invisible in unqualified wildcard.
-                col_binding.visibility = Visibility::UnqualifiedWildcardInVisible;
+            let left_column = find_using_column(self.left_context, join_key_name, "left")?;
+            let right_column = find_using_column(self.right_context, join_key_name, "right")?;
+
+            // The columns of the nullable side have been wrapped nullable in join context.
+            let mut left_join_column = None;
+            let mut right_join_column = None;
+            for column in self.join_context.columns.iter_mut() {
+                if column.index == left_column.index {
+                    column.visibility = Visibility::UnqualifiedWildcardInVisible;
+                    left_join_column = Some(column.clone());
+                } else if column.index == right_column.index {
+                    column.visibility = Visibility::UnqualifiedWildcardInVisible;
+                    right_join_column = Some(column.clone());
+                }
             }
 
+            let mut merged_column = match (join_op, left_join_column, right_join_column) {
+                (JoinOperator::FullOuter, Some(left), Some(right)) => {
+                    let lineage = ColumnLineage::Expression(format!(
+                        "COALESCE({}, {})",
+                        qualified_column_name(&left),
+                        qualified_column_name(&right)
+                    ));
+                    let scalar = coalesce_columns(left, right)?;
+                    let data_type = scalar.data_type();
+                    let index = self.metadata.write().add_column(
+                        join_key.clone(),
+                        data_type.clone(),
+                        None,
+                        None,
+                    );
+                    coalesced_columns.push(ScalarItem { scalar, index });
+                    ColumnBinding {
+                        database_name: None,
+                        table_name: None,
+                        column_name: join_key.clone(),
+                        index,
+                        data_type: Box::new(data_type),
+                        visibility: Visibility::Visible,
+                        lineage: Some(lineage),
+                    }
+                }
+                (JoinOperator::RightOuter, _, Some(right)) => right,
+                (_, Some(left), _) => left,
+                _ => left_column.clone(),
+            };
+            merged_column.database_name = None;
+            merged_column.table_name = None;
+            merged_column.visibility = Visibility::Visible;
+            merged_columns.push(merged_column);
+
             self.add_conditions(
-                left_scalar,
-                right_scalar,
+                BoundColumnRef {
+                    column: left_column,
+                }
+                .into(),
+                BoundColumnRef {
+                    column: right_column,
+                }
+                .into(),
                 left_join_conditions,
                 right_join_conditions,
             )?;
         }
+
+        // The merged columns come first in the output, followed by the rest columns of
+        // left table and right table.
+        merged_columns.append(&mut self.join_context.columns);
+        self.join_context.columns = merged_columns;
+
         Ok(())
     }
 
@@ -545,14 +578,124 @@ impl<'a> JoinConditionResolver<'a> {
         Ok(())
     }
 
+    /// Find the common column names of both input tables in the order of left table.
+    /// A common column name appears more than once in one table will be reported as
+    /// ambiguous by `resolve_using`.
     fn find_using_columns(&self, using_columns: &mut Vec<String>) -> Result<()> {
         for left_column in self.left_context.all_column_bindings().iter() {
-            for right_column in self.right_context.all_column_bindings().iter() {
-                if left_column.column_name == right_column.column_name {
-                    using_columns.push(left_column.column_name.clone());
-                }
+            if left_column.visibility != Visibility::Visible
+                || using_columns.contains(&left_column.column_name)
+            {
+                continue;
+            }
+            if self
+                .right_context
+                .all_column_bindings()
+                .iter()
+                .any(|right_column| {
+                    right_column.visibility == Visibility::Visible
+                        && right_column.column_name == left_column.column_name
+                })
+            {
+                using_columns.push(left_column.column_name.clone());
             }
         }
         Ok(())
     }
 }
+
+/// Get the column binding of a join input column in the join context, wrapped nullable
+/// if it's from the inner side of an outer join.
+fn join_column_binding(column: &ColumnBinding, nullable: bool) -> ColumnBinding {
+    let mut column = column.clone();
+    if nullable {
+        column.data_type = Box::new(wrap_nullable(&column.data_type));
+    }
+    column
+}
+
+/// Get the table name of the first column, the merged columns of `USING` placed before the
+/// columns of tables are skipped.
+fn first_table_name(bind_context: &BindContext) -> Option<(Option<&String>, &String)> {
+    let columns = bind_context.all_column_bindings();
+    columns
+        .iter()
+        .find(|column| {
+            column.table_name.is_some()
+                || !columns.iter().any(|other| {
+                    other.visibility == Visibility::UnqualifiedWildcardInVisible
+                        && other.column_name == column.column_name
+                })
+        })
+        .and_then(|column| {
+            column
+                .table_name
+                .as_ref()
+                .map(|table_name| (column.database_name.as_ref(), table_name))
+        })
+}
+
+/// Find the column referenced by `USING` in one side of join, which must be unique.
+fn find_using_column(
+    bind_context: &BindContext,
+    column_name: &str,
+    side: &str,
+) -> Result<ColumnBinding> {
+    let mut columns = bind_context.columns.iter().filter(|column| {
+        column.column_name == column_name
+            && column.visibility != Visibility::UnqualifiedWildcardInVisible
+    });
+    match (columns.next(), columns.next()) {
+        (Some(column), None) => Ok(column.clone()),
+        (None, _) => Err(ErrorCode::SemanticError(format!(
+            "column {} specified in USING clause does not exist in {} table",
+            column_name, side
+        ))),
+        (Some(_), Some(_)) => Err(ErrorCode::SemanticError(format!(
+            "common column name {} appears more than once in {} table",
+            column_name, side
+        ))),
+    }
+}
+
+/// Name of the column qualified by its table name, if any.
+fn qualified_column_name(column: &ColumnBinding) -> String {
+    match &column.table_name {
+        Some(table_name) => format!("{}.{}", table_name, column.column_name),
+        None => column.column_name.clone(),
+    }
+}
+
+/// Build `if(is_not_null(left), left, right)` to merge the `USING` columns of full outer join.
+fn coalesce_columns(left: ColumnBinding, right: ColumnBinding) -> Result<Scalar> {
+    let mut left: Scalar = BoundColumnRef { column: left }.into();
+    let mut right: Scalar = BoundColumnRef { column: right }.into();
+    let left_type = left.data_type();
+    let right_type = right.data_type();
+    if left_type.ne(&right_type) {
+        let least_super_type = compare_coercion(&left_type, &right_type)?;
+        left = wrap_cast(left, &least_super_type);
+        right = wrap_cast(right, &least_super_type);
+    }
+    let data_type = left.data_type();
+
+    let is_not_null = FunctionFactory::instance().get("is_not_null", &[&data_type])?;
+    let condition = FunctionCall {
+        arguments: vec![left.clone()],
+        func_name: "is_not_null".to_string(),
+        arg_types: vec![data_type.clone()],
+        return_type: Box::new(is_not_null.return_type()),
+    };
+    let if_func = FunctionFactory::instance().get("if", &[
+        &is_not_null.return_type(),
+        &data_type,
+        &data_type,
+    ])?;
+    Ok(FunctionCall {
+        arguments: vec![condition.into(), left, right],
+        func_name: "if".to_string(),
+        arg_types: vec![is_not_null.return_type(), data_type.clone(), data_type],
+        return_type: Box::new(if_func.return_type()),
+    }
+    .into())
+}
@@ -21,6 +21,8 @@ use crate::sql::binder::Binder;
 use crate::sql::optimizer::SExpr;
 use crate::sql::planner::semantic::TypeChecker;
 use crate::sql::plans::Limit;
+use crate::sql::plans::RelOperator;
+use crate::sql::plans::SortItem;
 use crate::sql::BindContext;
 
 impl<'a> Binder {
@@ -30,6 +32,7 @@ impl<'a> Binder {
         child: SExpr,
         limit: Option<&Expr<'a>>,
         offset: &Option<Expr<'a>>,
+        with_ties: bool,
     ) -> Result<SExpr> {
         let type_checker = TypeChecker::new(
             bind_context,
@@ -63,11 +66,30 @@ impl<'a> Binder {
             0
         };
 
+        let with_ties = match with_ties {
+            true => Self::input_sort_items(&child)?,
+            false => vec![],
+        };
+
         let limit_plan = Limit {
             limit: limit_cnt,
             offset: offset_cnt,
+            with_ties,
         };
         let new_expr = SExpr::create_unary(limit_plan.into(), child);
         Ok(new_expr)
     }
+
+    /// Find the sort keys of the `ORDER BY` the limit applies to, the columns are kept by the
+    /// projections on top of the sort. They are carried by the limit so that the operators
+    /// the optimizer may put in between, e.g. exchanges in cluster mode, don't matter.
+    fn input_sort_items(s_expr: &SExpr) -> Result<Vec<SortItem>> {
+        match s_expr.plan() {
+            RelOperator::Sort(sort) => Ok(sort.items.clone()),
+            RelOperator::EvalScalar(_) => Self::input_sort_items(s_expr.child(0)?),
+            _ => Err(ErrorCode::LogicalError(
+                "Input of limit with ties must be sorted",
+            )),
+        }
+    }
 }
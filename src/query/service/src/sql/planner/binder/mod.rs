@@ -17,6 +17,7 @@ mod bind_context;
 #[allow(clippy::module_inception)]
 mod binder;
 mod copy;
+mod cte;
 mod ddl;
 mod delete;
 mod distinct;
@@ -27,6 +28,7 @@ mod kill;
 mod limit;
 mod presign;
 mod project;
+mod recursive_cte;
 mod scalar;
 mod scalar_common;
 mod scalar_visitor;
@@ -35,9 +37,11 @@ mod setting;
 mod show;
 mod sort;
 mod table;
+mod window;
 
 pub use aggregate::AggregateInfo;
 pub use bind_context::*;
 pub use binder::Binder;
 pub use scalar::ScalarBinder;
 pub use scalar_common::*;
+pub use window::WindowInfo;
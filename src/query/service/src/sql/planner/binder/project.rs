@@ -13,9 +13,18 @@
 // limitations under the License.
 
 use std::collections::HashMap;
+use std::collections::HashSet;
 
+use common_ast::ast::Expr;
+use common_ast::ast::Identifier;
 use common_ast::ast::Indirection;
+use common_ast::ast::Query;
 use common_ast::ast::SelectTarget;
+use common_ast::ast::SubqueryModifier;
+use common_ast::parser::token::Token;
+use common_ast::walk_expr;
+use common_ast::DisplayError;
+use common_ast::Visitor;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_planner::IndexType;
@@ -23,12 +32,12 @@ use common_planner::IndexType;
 use super::bind_context::NameResolutionResult;
 use crate::sql::binder::select::SelectItem;
 use crate::sql::binder::select::SelectList;
-use crate::sql::binder::Visibility;
 use crate::sql::optimizer::SExpr;
 use crate::sql::planner::binder::scalar::ScalarBinder;
 use crate::sql::planner::binder::BindContext;
 use crate::sql::planner::binder::Binder;
 use crate::sql::planner::binder::ColumnBinding;
+use crate::sql::planner::binder::ColumnLineage;
 use crate::sql::planner::semantic::normalize_identifier;
 use crate::sql::planner::semantic::GroupingChecker;
 use crate::sql::plans::BoundColumnRef;
@@ -53,7 +62,20 @@ impl<'a> Binder {
                 column_binding.column_name = item.alias.clone();
                 column_binding
             } else {
-                self.create_column_binding(None, None, item.alias.clone(), item.scalar.data_type())
+                let mut column_binding = self.create_column_binding(
+                    None,
+                    None,
+                    item.alias.clone(),
+                    item.scalar.data_type(),
+                );
+                // Record the expression defining the column, which is kept through aliases
+                column_binding.lineage = match item.select_target {
+                    SelectTarget::AliasedExpr { expr, .. } => {
+                        Some(ColumnLineage::Expression(format!("{:#}", expr)))
+                    }
+                    _ => None,
+                };
+                column_binding
             };
             let scalar = if let Scalar::SubqueryExpr(SubqueryExpr {
                 typ,
@@ -149,10 +171,18 @@ impl<'a> Binder {
         select_list: &'a [SelectTarget<'a>],
     ) -> Result<SelectList<'a>> {
         let mut output = SelectList::<'a>::default();
-        for select_target in select_list {
+        let enable_lateral_alias = self.ctx.get_settings().get_enable_lateral_column_alias()?;
+        // Aliases of the preceding select items, which can be referenced by the following
+        // items, e.g. `SELECT a + 1 AS x, x * 2 FROM t`.
+        let mut lateral_aliases: Vec<(String, Scalar)> = vec![];
+        for (index, select_target) in select_list.iter().enumerate() {
             match select_target {
-                SelectTarget::QualifiedName(names) => {
+                SelectTarget::QualifiedName {
+                    qualified: names,
+                    exclude,
+                } => {
                     // Handle qualified name as select target
+                    let start = output.items.len();
                     if names.len() == 1 {
                         let indirection = &names[0];
                         match indirection {
@@ -179,10 +209,9 @@ impl<'a> Binder {
                             Indirection::Star => {
                                 // Expands wildcard star, for example we have a table `t(a INT, b INT)`:
                                 // The query `SELECT * FROM t` will be expanded into `SELECT t.a, t.b FROM t`
-                                for column_binding in input_context.all_column_bindings() {
-                                    if column_binding.visibility != Visibility::Visible {
-                                        continue;
-                                    }
+                                for column_binding in
+                                    input_context.wildcard_column_bindings(None, None)
+                                {
                                     output.items.push(SelectItem {
                                         select_target,
                                         scalar: BoundColumnRef {
@@ -194,18 +223,92 @@ impl<'a> Binder {
                                 }
                             }
                         }
+                    } else if let Some((Indirection::Star, qualifier)) = names.split_last() {
+                        // Expands qualified wildcard star, for example `SELECT t.* FROM t, t1`
+                        // will be expanded into `SELECT t.a, t.b FROM t, t1`
+                        let (database, table) = match qualifier {
+                            [Indirection::Identifier(table)] => (None, table),
+                            [
+                                Indirection::Identifier(database),
+                                Indirection::Identifier(table),
+                            ] => (Some(database), table),
+                            _ => {
+                                return Err(ErrorCode::SemanticError(
+                                    "Unsupported indirection type",
+                                ));
+                            }
+                        };
+                        let database = database.map(|ident| {
+                            normalize_identifier(ident, &self.name_resolution_ctx).name
+                        });
+                        let table_name =
+                            normalize_identifier(table, &self.name_resolution_ctx).name;
+
+                        let mut found = false;
+                        for column_binding in input_context
+                            .wildcard_column_bindings(database.as_deref(), Some(&table_name))
+                        {
+                            found = true;
+                            output.items.push(SelectItem {
+                                select_target,
+                                scalar: BoundColumnRef {
+                                    column: column_binding.clone(),
+                                }
+                                .into(),
+                                alias: column_binding.column_name.clone(),
+                            });
+                        }
+
+                        if !found {
+                            return Err(ErrorCode::SemanticError(table.span.display_error(
+                                format!("table \"{}\" doesn't exist", table_name),
+                            )));
+                        }
                     } else {
-                        // TODO: Support indirection like `a.b`, `a.*`
+                        // TODO: Support indirection like `a.b`
                         return Err(ErrorCode::SemanticError("Unsupported indirection type"));
                     }
+
+                    if let Some(exclude) = exclude {
+                        // Remove the excluded columns from expanded wildcard, for example
+                        // `SELECT * EXCLUDE (b) FROM t` will be expanded into `SELECT t.a FROM t`
+                        let mut expanded = output.items.split_off(start);
+                        let mut exclude_cols = HashSet::with_capacity(exclude.len());
+                        for ident in exclude {
+                            let name = normalize_identifier(ident, &self.name_resolution_ctx).name;
+                            if !expanded.iter().any(|item| item.alias == name) {
+                                return Err(ErrorCode::SemanticError(
+                                    ident.span.display_error(format!(
+                                        "column \"{}\" doesn't exist",
+                                        name
+                                    )),
+                                ));
+                            }
+                            if !exclude_cols.insert(name.clone()) {
+                                return Err(ErrorCode::SemanticError(ident.span.display_error(
+                                    format!("column \"{}\" is excluded more than once", name),
+                                )));
+                            }
+                        }
+                        expanded.retain(|item| !exclude_cols.contains(&item.alias));
+                        output.items.extend(expanded);
+                    }
                 }
                 SelectTarget::AliasedExpr { expr, alias } => {
+                    if enable_lateral_alias {
+                        self.check_lateral_alias_references(
+                            input_context,
+                            &select_list[index..],
+                            expr,
+                            &lateral_aliases,
+                        )?;
+                    }
                     let mut scalar_binder = ScalarBinder::new(
                         input_context,
                         self.ctx.clone(),
                         &self.name_resolution_ctx,
                         self.metadata.clone(),
-                        &[],
+                        &lateral_aliases,
                     );
                     let (bound_expr, _) = scalar_binder.bind(expr).await?;
 
@@ -215,6 +318,32 @@ impl<'a> Binder {
                         None => format!("{:#}", expr).to_lowercase(),
                     };
 
+                    if let Some(alias) = alias {
+                        self.check_alias_shadowing(
+                            input_context,
+                            alias,
+                            &expr_name,
+                            expr,
+                            &bound_expr,
+                        )?;
+                    }
+
+                    // The columns of FROM clause take precedence over the aliases, so an
+                    // alias shadowed by a column is not visible to the following items.
+                    if enable_lateral_alias
+                        && alias.is_some()
+                        && !input_context.columns.iter().any(|column_binding| {
+                            BindContext::match_column_binding(
+                                None,
+                                None,
+                                &expr_name,
+                                column_binding,
+                            )
+                        })
+                    {
+                        lateral_aliases.push((expr_name.clone(), bound_expr.clone()));
+                    }
+
                     output.items.push(SelectItem {
                         select_target,
                         scalar: bound_expr,
@@ -223,6 +352,123 @@ impl<'a> Binder {
                 }
             }
         }
+
+        if output.items.is_empty() {
+            return Err(ErrorCode::SemanticError(
+                "SELECT with no columns is not valid, all columns are excluded",
+            ));
+        }
         Ok(output)
     }
+
+    /// Check if the alias of a select item shadows a column of FROM clause, e.g. `y` in
+    /// `SELECT x + 1 AS y FROM t` where `t.y` exists. Aliasing a column with its own name
+    /// like `SELECT t.y AS y FROM t` is not shadowing.
+    fn check_alias_shadowing(
+        &self,
+        input_context: &BindContext,
+        alias: &Identifier<'a>,
+        alias_name: &str,
+        expr: &Expr<'a>,
+        scalar: &Scalar,
+    ) -> Result<()> {
+        let shadowed = input_context.columns.iter().find(|column_binding| {
+            BindContext::match_column_binding(None, None, alias_name, column_binding)
+                && !matches!(scalar, Scalar::BoundColumnRef(BoundColumnRef { column })
+                    if column.index == column_binding.index)
+        });
+        if let Some(column_binding) = shadowed {
+            let column_name = match &column_binding.table_name {
+                Some(table_name) => format!("{table_name}.{}", column_binding.column_name),
+                None => column_binding.column_name.clone(),
+            };
+            self.report_shadowing(
+                &alias.span,
+                format!("alias \"{alias_name}\" of expression {expr} shadows column {column_name}"),
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Check that the lateral column aliases referenced by a select item are defined by
+    /// the preceding items. `following` starts with the select item itself.
+    fn check_lateral_alias_references(
+        &self,
+        input_context: &BindContext,
+        following: &'a [SelectTarget<'a>],
+        expr: &'a Expr<'a>,
+        lateral_aliases: &[(String, Scalar)],
+    ) -> Result<()> {
+        let mut finder = UnqualifiedColumnFinder::default();
+        walk_expr(&mut finder, expr);
+        for column in finder.columns {
+            let name = normalize_identifier(column, &self.name_resolution_ctx).name;
+            if lateral_aliases.iter().any(|(alias, _)| alias == &name)
+                || Self::is_column_in_scope(input_context, &name)
+            {
+                continue;
+            }
+            for (offset, select_target) in following.iter().enumerate() {
+                if let SelectTarget::AliasedExpr {
+                    alias: Some(alias), ..
+                } = select_target
+                {
+                    if normalize_identifier(alias, &self.name_resolution_ctx).name != name {
+                        continue;
+                    }
+                    let message = if offset == 0 {
+                        format!("circular reference to column alias \"{name}\"")
+                    } else {
+                        format!(
+                            "column alias \"{name}\" is referenced before it is defined in the select list"
+                        )
+                    };
+                    return Err(ErrorCode::SemanticError(column.span.display_error(message)));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns true if the unqualified column can be resolved in the context or its parents.
+    fn is_column_in_scope(bind_context: &BindContext, column: &str) -> bool {
+        let mut bind_context = Some(bind_context);
+        while let Some(context) = bind_context {
+            if context.columns.iter().any(|column_binding| {
+                BindContext::match_column_binding(None, None, column, column_binding)
+            }) {
+                return true;
+            }
+            bind_context = context.parent.as_deref();
+        }
+        false
+    }
+}
+
+/// Collect the unqualified column references of an expression, without looking into subqueries.
+#[derive(Default)]
+struct UnqualifiedColumnFinder<'ast> {
+    columns: Vec<&'ast Identifier<'ast>>,
+}
+
+impl<'ast> Visitor<'ast> for UnqualifiedColumnFinder<'ast> {
+    fn visit_column_ref(
+        &mut self,
+        _span: &'ast [Token<'ast>],
+        database: &'ast Option<Identifier<'ast>>,
+        table: &'ast Option<Identifier<'ast>>,
+        column: &'ast Identifier<'ast>,
+    ) {
+        if database.is_none() && table.is_none() {
+            self.columns.push(column);
+        }
+    }
+
+    fn visit_subquery(
+        &mut self,
+        _span: &'ast [Token<'ast>],
+        _modifier: &'ast Option<SubqueryModifier>,
+        _subquery: &'ast Query<'ast>,
+    ) {
+    }
 }
@@ -0,0 +1,326 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+
+use common_ast::ast::Expr;
+use common_ast::ast::Identifier;
+use common_ast::ast::JoinOperator;
+use common_ast::ast::Literal;
+use common_ast::ast::Query;
+use common_ast::ast::SelectStmt;
+use common_ast::ast::SelectTarget;
+use common_ast::ast::SetExpr;
+use common_ast::ast::SetOperator;
+use common_ast::ast::SubqueryModifier;
+use common_ast::ast::TableReference;
+use common_ast::ast::Window;
+use common_ast::ast::CTE;
+use common_ast::parser::token::Token;
+use common_ast::walk_expr;
+use common_ast::walk_query;
+use common_ast::walk_table_reference;
+use common_ast::Visitor;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_functions::aggregates::AggregateFunctionFactory;
+
+use crate::sql::binder::CteInfo;
+use crate::sql::normalize_identifier;
+use crate::sql::optimizer::SExpr;
+use crate::sql::planner::binder::Binder;
+use crate::sql::planner::semantic::NameResolutionContext;
+use crate::sql::plans::RecursiveCte;
+use crate::sql::BindContext;
+
+impl<'a> Binder {
+    /// Returns true if the common table expression references itself, which
+    /// is only allowed in `WITH RECURSIVE`.
+    pub(super) fn is_recursive_cte(&self, cte: &CTE<'a>) -> bool {
+        let mut finder = CteReferenceFinder::new(&cte.alias.name, &self.name_resolution_ctx);
+        walk_query(&mut finder, &cte.query);
+        finder.references > 0
+    }
+
+    /// Bind a recursive common table expression, which must be in the form of
+    /// `non-recursive-term UNION ALL recursive-term`, e.g.
+    ///
+    /// ```sql
+    /// WITH RECURSIVE t(n) AS (
+    ///     SELECT 1
+    ///     UNION ALL
+    ///     SELECT n + 1 FROM t WHERE n < 10
+    /// )
+    /// SELECT * FROM t;
+    /// ```
+    ///
+    /// The non-recursive term is bound as a normal query, and then registered as the
+    /// CTE so that the recursive term can be bound with the column types of it. The
+    /// bound `RecursiveCte` replaces it for the references in the rest of the query.
+    pub(super) async fn bind_recursive_cte(
+        &mut self,
+        bind_context: &BindContext,
+        cte: &CTE<'a>,
+    ) -> Result<()> {
+        let table_name = cte.alias.name.name.clone();
+        let operation = match &cte.query.body {
+            SetExpr::SetOperation(operation)
+                if operation.op == SetOperator::Union && operation.all =>
+            {
+                operation
+            }
+            _ => {
+                return Err(ErrorCode::SemanticError(format!(
+                    "recursive query \"{table_name}\" does not have the form non-recursive-term UNION ALL recursive-term"
+                )));
+            }
+        };
+        if !cte.query.order_by.is_empty()
+            || !cte.query.limit.is_empty()
+            || cte.query.offset.is_some()
+        {
+            return Err(ErrorCode::SemanticError(format!(
+                "ORDER BY, LIMIT or OFFSET is not allowed in recursive query \"{table_name}\""
+            )));
+        }
+
+        let mut finder = CteReferenceFinder::new(&cte.alias.name, &self.name_resolution_ctx);
+        finder.visit_set_expr(&operation.left);
+        if finder.references > 0 {
+            return Err(ErrorCode::SemanticError(format!(
+                "recursive reference to query \"{table_name}\" must not appear within its non-recursive term"
+            )));
+        }
+
+        match operation.right.as_ref() {
+            SetExpr::Select(stmt) => self.check_recursive_term(&cte.alias.name, stmt)?,
+            _ => {
+                return Err(ErrorCode::SemanticError(format!(
+                    "recursive query \"{table_name}\" does not have the form non-recursive-term UNION ALL recursive-term"
+                )));
+            }
+        }
+
+        // Bind the non-recursive term, the recursive reference will be resolved with it.
+        let columns_alias: Vec<String> = cte.alias.columns.iter().map(|c| c.name.clone()).collect();
+        let (anchor_s_expr, anchor_bind_context) = self
+            .bind_set_expr(bind_context, &operation.left, &[])
+            .await?;
+        let cte_info = CteInfo {
+            columns_alias: columns_alias.clone(),
+            s_expr: anchor_s_expr.clone(),
+            bind_context: anchor_bind_context.clone(),
+            referenced: Arc::new(AtomicBool::new(false)),
+        };
+        bind_context
+            .ctes_map
+            .write()
+            .insert(table_name.clone(), cte_info);
+
+        let (recursive_s_expr, recursive_bind_context) = self
+            .bind_set_expr(bind_context, &operation.right, &[])
+            .await?;
+        let (columns, recursive_columns, anchor_s_expr, recursive_s_expr) = self
+            .reconcile_set_operation(
+                &SetOperator::Union,
+                &anchor_bind_context,
+                &recursive_bind_context,
+                anchor_s_expr,
+                recursive_s_expr,
+            )?;
+
+        let recursive_cte = RecursiveCte {
+            table_name: table_name.clone(),
+            columns: columns.iter().map(|column| column.index).collect(),
+            recursive_columns: recursive_columns
+                .iter()
+                .map(|column| column.index)
+                .collect(),
+            max_iterations: self.ctx.get_settings().get_max_recursive_cte_iterations()?,
+        };
+        let mut output_context = anchor_bind_context.replace();
+        output_context.columns = columns;
+
+        let cte_info = CteInfo {
+            columns_alias,
+            s_expr: SExpr::create_binary(recursive_cte.into(), anchor_s_expr, recursive_s_expr),
+            bind_context: output_context,
+            referenced: Arc::new(AtomicBool::new(false)),
+        };
+        bind_context.ctes_map.write().insert(table_name, cte_info);
+
+        Ok(())
+    }
+
+    fn check_recursive_term(&self, table: &Identifier<'a>, stmt: &SelectStmt<'a>) -> Result<()> {
+        let table_name = &table.name;
+
+        let mut finder = CteReferenceFinder::new(table, &self.name_resolution_ctx);
+        for table_ref in stmt.from.iter() {
+            finder.visit_from_item(table_ref, false)?;
+        }
+        let from_references = finder.references;
+
+        let mut finder = CteReferenceFinder::new(table, &self.name_resolution_ctx);
+        finder.visit_select_stmt(stmt);
+        if finder.references != from_references {
+            return Err(ErrorCode::SemanticError(format!(
+                "recursive reference to query \"{table_name}\" must not appear within a subquery"
+            )));
+        }
+        if from_references > 1 {
+            return Err(ErrorCode::SemanticError(format!(
+                "recursive reference to query \"{table_name}\" must not appear more than once"
+            )));
+        }
+
+        let mut finder = AggregateFinder::default();
+        for target in stmt.select_list.iter() {
+            if let SelectTarget::AliasedExpr { expr, .. } = target {
+                walk_expr(&mut finder, expr);
+            }
+        }
+        if let Some(having) = &stmt.having {
+            walk_expr(&mut finder, having);
+        }
+        if finder.found || !stmt.group_by.is_empty() || stmt.having.is_some() {
+            return Err(ErrorCode::SemanticError(format!(
+                "aggregation is not allowed in the recursive term of query \"{table_name}\""
+            )));
+        }
+        if stmt.distinct {
+            return Err(ErrorCode::SemanticError(format!(
+                "DISTINCT is not allowed in the recursive term of query \"{table_name}\""
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+/// Count the references to a common table expression.
+struct CteReferenceFinder<'a> {
+    table_name: String,
+    name_resolution_ctx: &'a NameResolutionContext,
+    references: usize,
+}
+
+impl<'a> CteReferenceFinder<'a> {
+    fn new(table: &Identifier, name_resolution_ctx: &'a NameResolutionContext) -> Self {
+        Self {
+            table_name: normalize_identifier(table, name_resolution_ctx).name,
+            name_resolution_ctx,
+            references: 0,
+        }
+    }
+
+    fn is_reference(&self, table_ref: &TableReference) -> bool {
+        match table_ref {
+            TableReference::Table {
+                catalog: None,
+                database: None,
+                table,
+                ..
+            } => normalize_identifier(table, self.name_resolution_ctx).name == self.table_name,
+            _ => false,
+        }
+    }
+
+    fn visit_set_expr<'ast>(&mut self, set_expr: &'ast SetExpr<'ast>) {
+        match set_expr {
+            SetExpr::Select(stmt) => self.visit_select_stmt(stmt),
+            SetExpr::Query(query) => walk_query(self, query),
+            SetExpr::SetOperation(operation) => {
+                self.visit_set_expr(&operation.left);
+                self.visit_set_expr(&operation.right);
+            }
+        }
+    }
+
+    /// Count the references in the top level `FROM` clause, without looking into subqueries.
+    /// The references on the nullable side of an outer join are not allowed.
+    fn visit_from_item(&mut self, table_ref: &TableReference, nullable: bool) -> Result<()> {
+        match table_ref {
+            TableReference::Join { join, .. } => {
+                let (left_nullable, right_nullable) = match join.op {
+                    JoinOperator::LeftOuter => (false, true),
+                    JoinOperator::RightOuter => (true, false),
+                    JoinOperator::FullOuter => (true, true),
+                    _ => (false, false),
+                };
+                self.visit_from_item(&join.left, nullable || left_nullable)?;
+                self.visit_from_item(&join.right, nullable || right_nullable)
+            }
+            table_ref if self.is_reference(table_ref) => {
+                if nullable {
+                    return Err(ErrorCode::SemanticError(format!(
+                        "recursive reference to query \"{}\" must not appear within an outer join",
+                        self.table_name
+                    )));
+                }
+                self.references += 1;
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl<'a, 'ast> Visitor<'ast> for CteReferenceFinder<'a> {
+    fn visit_table_reference(&mut self, table_ref: &'ast TableReference<'ast>) {
+        if self.is_reference(table_ref) {
+            self.references += 1;
+        }
+        walk_table_reference(self, table_ref);
+    }
+}
+
+/// Find aggregate functions in an expression.
+#[derive(Default)]
+struct AggregateFinder {
+    found: bool,
+}
+
+impl<'ast> Visitor<'ast> for AggregateFinder {
+    fn visit_count_all(&mut self, _span: &'ast [Token<'ast>]) {
+        self.found = true;
+    }
+
+    fn visit_function_call(
+        &mut self,
+        _span: &'ast [Token<'ast>],
+        _distinct: bool,
+        name: &'ast Identifier<'ast>,
+        args: &'ast [Expr<'ast>],
+        _params: &'ast [Literal],
+        _window: &'ast Option<Window<'ast>>,
+    ) {
+        if AggregateFunctionFactory::instance().check(&name.name) {
+            self.found = true;
+        }
+        for arg in args {
+            walk_expr(self, arg);
+        }
+    }
+
+    // Aggregate functions in a subquery are computed by the subquery itself.
+    fn visit_subquery(
+        &mut self,
+        _span: &'ast [Token<'ast>],
+        _modifier: &'ast Option<SubqueryModifier>,
+        _subquery: &'ast Query<'ast>,
+    ) {
+    }
+}
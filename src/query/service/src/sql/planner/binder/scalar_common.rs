@@ -13,16 +13,20 @@
 // limitations under the License.
 
 use common_datavalues::DataTypeImpl;
+use common_datavalues::DataValue;
 use common_exception::Result;
 
 use crate::sql::binder::scalar_visitor::Recursion;
 use crate::sql::binder::scalar_visitor::ScalarVisitor;
+use crate::sql::binder::ColumnBinding;
+use crate::sql::binder::Visibility;
 use crate::sql::optimizer::RelationalProperty;
 use crate::sql::plans::AndExpr;
 use crate::sql::plans::BoundColumnRef;
 use crate::sql::plans::CastExpr;
 use crate::sql::plans::ComparisonExpr;
 use crate::sql::plans::ComparisonOp;
+use crate::sql::plans::ConstantExpr;
 use crate::sql::plans::FunctionCall;
 use crate::sql::plans::OrExpr;
 use crate::sql::plans::Scalar;
@@ -40,7 +44,6 @@ impl<'a, F> Finder<'a, F>
 where F: Fn(&Scalar) -> bool
 {
     /// Create a new finder with the `test_fn`
-    #[allow(dead_code)]
     fn new(find_fn: &'a F) -> Self {
         Self {
             find_fn,
@@ -166,6 +169,13 @@ impl<'a> JoinPredicate<'a> {
     }
 }
 
+/// Check if the scalar expression contains any aggregate function, e.g. `sum(a) + 1`.
+pub fn contain_aggregate(scalar: &Scalar) -> Result<bool> {
+    let find_fn = |scalar: &Scalar| matches!(scalar, Scalar::AggregateFunction(_));
+    let finder = scalar.accept(Finder::new(&find_fn))?;
+    Ok(!finder.scalars.is_empty())
+}
+
 pub fn contain_subquery(scalar: &Scalar) -> bool {
     match scalar {
         Scalar::BoundColumnRef(BoundColumnRef { column }) => {
@@ -190,3 +200,141 @@ pub fn contain_subquery(scalar: &Scalar) -> bool {
         _ => false,
     }
 }
+
+/// Build the key of a scalar expression to match the equivalent expressions, e.g. the
+/// expressions in select list and the group items. The names of columns and the types of
+/// literals are ignored, and the operands of commutative operators are put in order, so
+/// `1 + a` and `t.a + 1` have the same key.
+pub fn scalar_equivalence_key(scalar: &Scalar) -> String {
+    format!("{:?}", normalize_scalar(scalar))
+}
+
+fn normalize_scalar(scalar: &Scalar) -> Scalar {
+    match scalar {
+        Scalar::BoundColumnRef(BoundColumnRef { column }) => BoundColumnRef {
+            column: ColumnBinding {
+                database_name: None,
+                table_name: None,
+                column_name: String::new(),
+                index: column.index,
+                data_type: column.data_type.clone(),
+                visibility: Visibility::Visible,
+                lineage: None,
+            },
+        }
+        .into(),
+        Scalar::ConstantExpr(ConstantExpr { value, .. }) => {
+            let value = match value {
+                DataValue::Int64(v) if *v >= 0 => DataValue::UInt64(*v as u64),
+                value => value.clone(),
+            };
+            ConstantExpr {
+                data_type: Box::new(value.data_type()),
+                value,
+            }
+            .into()
+        }
+        Scalar::AndExpr(AndExpr {
+            left,
+            right,
+            return_type,
+        }) => {
+            let (left, right) = normalize_commutative_operands(left, right);
+            AndExpr {
+                left: Box::new(left),
+                right: Box::new(right),
+                return_type: return_type.clone(),
+            }
+            .into()
+        }
+        Scalar::OrExpr(OrExpr {
+            left,
+            right,
+            return_type,
+        }) => {
+            let (left, right) = normalize_commutative_operands(left, right);
+            OrExpr {
+                left: Box::new(left),
+                right: Box::new(right),
+                return_type: return_type.clone(),
+            }
+            .into()
+        }
+        Scalar::ComparisonExpr(ComparisonExpr {
+            op,
+            left,
+            right,
+            return_type,
+        }) => {
+            let (op, left, right) = match op {
+                ComparisonOp::Equal | ComparisonOp::NotEqual => {
+                    let (left, right) = normalize_commutative_operands(left, right);
+                    (op.clone(), left, right)
+                }
+                // `a < b` is the same as `b > a`
+                ComparisonOp::LT => (
+                    ComparisonOp::GT,
+                    normalize_scalar(right),
+                    normalize_scalar(left),
+                ),
+                ComparisonOp::LTE => (
+                    ComparisonOp::GTE,
+                    normalize_scalar(right),
+                    normalize_scalar(left),
+                ),
+                _ => (op.clone(), normalize_scalar(left), normalize_scalar(right)),
+            };
+            ComparisonExpr {
+                op,
+                left: Box::new(left),
+                right: Box::new(right),
+                return_type: return_type.clone(),
+            }
+            .into()
+        }
+        Scalar::FunctionCall(FunctionCall {
+            arguments,
+            func_name,
+            return_type,
+            ..
+        }) => {
+            let arguments = match (func_name.as_str(), arguments.as_slice()) {
+                ("+" | "*", [left, right]) => {
+                    let (left, right) = normalize_commutative_operands(left, right);
+                    vec![left, right]
+                }
+                _ => arguments.iter().map(normalize_scalar).collect(),
+            };
+            // The argument types are implied by the arguments, and they may differ
+            // only by the types of literals.
+            FunctionCall {
+                arguments,
+                func_name: func_name.clone(),
+                arg_types: vec![],
+                return_type: return_type.clone(),
+            }
+            .into()
+        }
+        Scalar::CastExpr(CastExpr {
+            argument,
+            from_type,
+            target_type,
+        }) => CastExpr {
+            argument: Box::new(normalize_scalar(argument)),
+            from_type: from_type.clone(),
+            target_type: target_type.clone(),
+        }
+        .into(),
+        Scalar::AggregateFunction(_) | Scalar::SubqueryExpr(_) => scalar.clone(),
+    }
+}
+
+fn normalize_commutative_operands(left: &Scalar, right: &Scalar) -> (Scalar, Scalar) {
+    let left = normalize_scalar(left);
+    let right = normalize_scalar(right);
+    if format!("{:?}", left) <= format!("{:?}", right) {
+        (left, right)
+    } else {
+        (right, left)
+    }
+}
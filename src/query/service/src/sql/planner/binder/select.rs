@@ -13,6 +13,8 @@
 // limitations under the License.
 
 use std::collections::HashMap;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
 
 use async_recursion::async_recursion;
 use common_ast::ast::Expr;
@@ -26,21 +28,26 @@ use common_ast::ast::SelectTarget;
 use common_ast::ast::SetExpr;
 use common_ast::ast::SetOperator;
 use common_ast::ast::TableReference;
+use common_ast::ast::CTE;
 use common_datavalues::type_coercion::compare_coercion;
-use common_datavalues::DataTypeImpl;
+use common_datavalues::type_coercion::merge_types;
+use common_datavalues::DataType;
 use common_exception::ErrorCode;
 use common_exception::Result;
-use common_planner::IndexType;
 
 use crate::sql::binder::scalar_common::split_conjunctions;
+use crate::sql::binder::wrap_cast;
+use crate::sql::binder::wrap_cast_if_needed;
+use crate::sql::binder::window::check_no_window_function;
 use crate::sql::binder::CteInfo;
+use crate::sql::binder::ExprContext;
 use crate::sql::binder::Visibility;
 use crate::sql::optimizer::SExpr;
 use crate::sql::planner::binder::scalar::ScalarBinder;
 use crate::sql::planner::binder::BindContext;
 use crate::sql::planner::binder::Binder;
+use crate::sql::planner::semantic::normalize_identifier;
 use crate::sql::plans::BoundColumnRef;
-use crate::sql::plans::CastExpr;
 use crate::sql::plans::EvalScalar;
 use crate::sql::plans::Filter;
 use crate::sql::plans::JoinType;
@@ -69,6 +76,13 @@ impl<'a> Binder {
         stmt: &SelectStmt<'a>,
         order_by: &[OrderByExpr<'a>],
     ) -> Result<(SExpr, BindContext)> {
+        if let Some(expr) = stmt.distinct_on.first() {
+            return Err(ErrorCode::UnImplement(
+                expr.span()
+                    .display_error("DISTINCT ON is not supported yet".to_string()),
+            ));
+        }
+
         let (mut s_expr, mut from_context) = if stmt.from.is_empty() {
             self.bind_one_table(bind_context, stmt).await?
         } else {
@@ -91,8 +105,22 @@ impl<'a> Binder {
         };
 
         if let Some(expr) = &stmt.selection {
+            check_no_window_function(expr, "WHERE")?;
+            from_context.expr_context = ExprContext::WhereClause;
             s_expr = self.bind_where(&from_context, expr, s_expr).await?;
         }
+        for expr in stmt.group_by.iter() {
+            check_no_window_function(expr, "GROUP BY")?;
+        }
+        if let Some(expr) = &stmt.having {
+            check_no_window_function(expr, "HAVING")?;
+        }
+
+        // Window functions should be registered before binding the select list,
+        // where they are referenced as columns.
+        from_context.expr_context = ExprContext::SelectClause;
+        self.analyze_window_select(&mut from_context, stmt, order_by)
+            .await?;
 
         // Generate a analyzed select list with from context
         let mut select_list = self
@@ -102,11 +130,13 @@ impl<'a> Binder {
         let (mut scalar_items, projections) = self.analyze_projection(&select_list)?;
 
         // This will potentially add some alias group items to `from_context` if find some.
+        from_context.expr_context = ExprContext::GroupClause;
         self.analyze_group_items(&mut from_context, &select_list, &stmt.group_by)
             .await?;
 
         self.analyze_aggregate_select(&mut from_context, &mut select_list)?;
 
+        from_context.expr_context = ExprContext::HavingClause;
         let having = if let Some(having) = &stmt.having {
             Some(
                 self.analyze_aggregate_having(&mut from_context, &select_list, having)
@@ -116,6 +146,7 @@ impl<'a> Binder {
             None
         };
 
+        from_context.expr_context = ExprContext::OrderByClause;
         let order_items = self
             .analyze_order_items(
                 &from_context,
@@ -137,6 +168,10 @@ impl<'a> Binder {
                 .await?;
         }
 
+        if !from_context.window_info.window_scalar_exprs.is_empty() {
+            s_expr = self.bind_window(&from_context, s_expr)?;
+        }
+
         if stmt.distinct {
             s_expr = self.bind_distinct(&from_context, &projections, &mut scalar_items, s_expr)?;
         }
@@ -159,6 +194,8 @@ impl<'a> Binder {
         output_context.parent = from_context.parent;
         output_context.columns = from_context.columns;
         output_context.ctes_map = from_context.ctes_map;
+        output_context.default_database = bind_context.default_database.clone();
+        output_context.view_path = bind_context.view_path.clone();
 
         Ok((s_expr, output_context))
     }
@@ -200,11 +237,17 @@ impl<'a> Binder {
                         "duplicate cte {table_name}"
                     )));
                 }
+                self.check_cte_shadowing(bind_context, cte).await?;
+                if with.recursive && self.is_recursive_cte(cte) {
+                    self.bind_recursive_cte(bind_context, cte).await?;
+                    continue;
+                }
                 let (s_expr, cte_bind_context) = self.bind_query(bind_context, &cte.query).await?;
                 let cte_info = CteInfo {
                     columns_alias: cte.alias.columns.iter().map(|c| c.name.clone()).collect(),
                     s_expr,
-                    bind_context: cte_bind_context.clone(),
+                    bind_context: cte_bind_context,
+                    referenced: Arc::new(AtomicBool::new(false)),
                 };
                 let mut ctes_map = bind_context.ctes_map.write();
                 ctes_map.insert(table_name, cte_info);
@@ -227,10 +270,22 @@ impl<'a> Binder {
             }
         };
 
+        if query.with_ties && query.order_by.is_empty() {
+            return Err(ErrorCode::SemanticError(
+                "WITH TIES cannot be specified without ORDER BY clause",
+            ));
+        }
+
         if !query.limit.is_empty() {
             if query.limit.len() == 1 {
                 s_expr = self
-                    .bind_limit(&bind_context, s_expr, Some(&query.limit[0]), &query.offset)
+                    .bind_limit(
+                        &bind_context,
+                        s_expr,
+                        Some(&query.limit[0]),
+                        &query.offset,
+                        query.with_ties,
+                    )
                     .await?;
             } else {
                 s_expr = self
@@ -239,12 +294,13 @@ impl<'a> Binder {
                         s_expr,
                         Some(&query.limit[1]),
                         &Some(query.limit[0].clone()),
+                        false,
                     )
                     .await?;
             }
         } else if query.offset.is_some() {
             s_expr = self
-                .bind_limit(&bind_context, s_expr, None, &query.offset)
+                .bind_limit(&bind_context, s_expr, None, &query.offset, false)
                 .await?;
         }
 
@@ -255,6 +311,29 @@ impl<'a> Binder {
         Ok((s_expr, bind_context))
     }
 
+    /// Check if a CTE shadows a table with the same name in current database, which makes
+    /// the table unreachable in the scope of the CTE.
+    async fn check_cte_shadowing(&self, bind_context: &BindContext, cte: &CTE<'a>) -> Result<()> {
+        let table_name = normalize_identifier(&cte.alias.name, &self.name_resolution_ctx).name;
+        let catalog = self.ctx.get_current_catalog();
+        let database = bind_context
+            .default_database
+            .clone()
+            .unwrap_or_else(|| self.ctx.get_current_database());
+        if self
+            .ctx
+            .get_table(&catalog, &database, &table_name)
+            .await
+            .is_ok()
+        {
+            self.report_shadowing(
+                &cte.alias.name.span,
+                format!("CTE \"{table_name}\" shadows table {database}.{table_name}"),
+            )?;
+        }
+        Ok(())
+    }
+
     pub(super) async fn bind_where(
         &mut self,
         bind_context: &BindContext,
@@ -285,51 +364,35 @@ impl<'a> Binder {
         op: &SetOperator,
         all: &bool,
     ) -> Result<(SExpr, BindContext)> {
-        let mut coercion_type = None;
         let (left_expr, left_bind_context) = self.bind_set_expr(bind_context, left, &[]).await?;
         let (right_expr, right_bind_context) = self.bind_set_expr(bind_context, right, &[]).await?;
-        if left_bind_context.columns.len() != right_bind_context.columns.len() {
-            return Err(ErrorCode::SemanticError(
-                "SetOperation must have the same number of columns",
-            ));
-        } else {
-            for (left_col, right_col) in left_bind_context
-                .columns
-                .iter()
-                .zip(right_bind_context.columns.iter())
-            {
-                if left_col.data_type != right_col.data_type {
-                    let data_type = compare_coercion(&left_col.data_type, &right_col.data_type)
-                        .expect("SetOperation's types cannot be matched");
-                    coercion_type = Some(data_type);
-                }
-            }
-        }
+        let (left_columns, right_columns, left_expr, right_expr) = self.reconcile_set_operation(
+            op,
+            &left_bind_context,
+            &right_bind_context,
+            left_expr,
+            right_expr,
+        )?;
+
+        // The output columns take the names from the left side
+        let mut output_context = left_bind_context.replace();
+        output_context.columns = left_columns;
+
         match (op, all) {
             (SetOperator::Intersect, false) => {
                 // Transfer Intersect to Semi join
-                self.bind_intersect(left_bind_context, right_bind_context, left_expr, right_expr)
+                self.bind_intersect(output_context, right_columns, left_expr, right_expr)
             }
             (SetOperator::Except, false) => {
                 // Transfer Except to Anti join
-                self.bind_except(left_bind_context, right_bind_context, left_expr, right_expr)
+                self.bind_except(output_context, right_columns, left_expr, right_expr)
+            }
+            (SetOperator::Union, true) => {
+                self.bind_union(output_context, right_columns, left_expr, right_expr, false)
+            }
+            (SetOperator::Union, false) => {
+                self.bind_union(output_context, right_columns, left_expr, right_expr, true)
             }
-            (SetOperator::Union, true) => self.bind_union(
-                left_bind_context,
-                right_bind_context,
-                coercion_type,
-                left_expr,
-                right_expr,
-                false,
-            ),
-            (SetOperator::Union, false) => self.bind_union(
-                left_bind_context,
-                right_bind_context,
-                coercion_type,
-                left_expr,
-                right_expr,
-                true,
-            ),
             _ => Err(ErrorCode::UnImplement(
                 "Unsupported query type, currently, databend only support intersect distinct and except distinct",
             )),
@@ -338,54 +401,41 @@ impl<'a> Binder {
 
     fn bind_union(
         &mut self,
-        left_context: BindContext,
-        right_context: BindContext,
-        coercion_type: Option<DataTypeImpl>,
+        output_context: BindContext,
+        right_columns: Vec<ColumnBinding>,
         left_expr: SExpr,
         right_expr: SExpr,
         distinct: bool,
     ) -> Result<(SExpr, BindContext)> {
-        let (new_bind_context, pairs, left_expr, right_expr) =
-            if let Some(coercion_type) = coercion_type {
-                self.coercion_union_type(
-                    left_context,
-                    right_context,
-                    left_expr,
-                    right_expr,
-                    coercion_type,
-                )?
-            } else {
-                let pairs = left_context
-                    .columns
-                    .iter()
-                    .zip(right_context.columns.iter())
-                    .map(|(left, right)| (left.index, right.index))
-                    .collect::<Vec<_>>();
-                (left_context, pairs, left_expr, right_expr)
-            };
+        let pairs = output_context
+            .columns
+            .iter()
+            .zip(right_columns.iter())
+            .map(|(left, right)| (left.index, right.index))
+            .collect::<Vec<_>>();
         let union_plan = UnionAll { pairs };
         let mut new_expr = SExpr::create_binary(union_plan.into(), left_expr, right_expr);
         if distinct {
             new_expr = self.bind_distinct(
-                &new_bind_context,
-                new_bind_context.all_column_bindings(),
+                &output_context,
+                output_context.all_column_bindings(),
                 &mut HashMap::new(),
                 new_expr,
             )?;
         }
-        Ok((new_expr, new_bind_context))
+        Ok((new_expr, output_context))
     }
 
     fn bind_intersect(
         &mut self,
-        left_context: BindContext,
-        right_context: BindContext,
+        output_context: BindContext,
+        right_columns: Vec<ColumnBinding>,
         left_expr: SExpr,
         right_expr: SExpr,
     ) -> Result<(SExpr, BindContext)> {
         self.bind_intersect_or_except(
-            left_context,
-            right_context,
+            output_context,
+            right_columns,
             left_expr,
             right_expr,
             JoinType::LeftSemi,
@@ -394,14 +444,14 @@ impl<'a> Binder {
 
     fn bind_except(
         &mut self,
-        left_context: BindContext,
-        right_context: BindContext,
+        output_context: BindContext,
+        right_columns: Vec<ColumnBinding>,
         left_expr: SExpr,
         right_expr: SExpr,
     ) -> Result<(SExpr, BindContext)> {
         self.bind_intersect_or_except(
-            left_context,
-            right_context,
+            output_context,
+            right_columns,
             left_expr,
             right_expr,
             JoinType::LeftAnti,
@@ -410,26 +460,21 @@ impl<'a> Binder {
 
     fn bind_intersect_or_except(
         &mut self,
-        left_context: BindContext,
-        right_context: BindContext,
+        output_context: BindContext,
+        right_columns: Vec<ColumnBinding>,
         left_expr: SExpr,
         right_expr: SExpr,
         join_type: JoinType,
     ) -> Result<(SExpr, BindContext)> {
         let left_expr = self.bind_distinct(
-            &left_context,
-            left_context.all_column_bindings(),
+            &output_context,
+            output_context.all_column_bindings(),
             &mut HashMap::new(),
             left_expr,
         )?;
-        let mut left_conditions = Vec::with_capacity(left_context.columns.len());
-        let mut right_conditions = Vec::with_capacity(right_context.columns.len());
-        assert_eq!(left_context.columns.len(), right_context.columns.len());
-        for (left_column, right_column) in left_context
-            .columns
-            .iter()
-            .zip(right_context.columns.iter())
-        {
+        let mut left_conditions = Vec::with_capacity(output_context.columns.len());
+        let mut right_conditions = Vec::with_capacity(right_columns.len());
+        for (left_column, right_column) in output_context.columns.iter().zip(right_columns) {
             left_conditions.push(
                 BoundColumnRef {
                     column: left_column.clone(),
@@ -438,7 +483,7 @@ impl<'a> Binder {
             );
             right_conditions.push(
                 BoundColumnRef {
-                    column: right_column.clone(),
+                    column: right_column,
                 }
                 .into(),
             );
@@ -451,107 +496,122 @@ impl<'a> Binder {
             left_expr,
             right_expr,
         )?;
-        Ok((s_expr, left_context))
+        Ok((s_expr, output_context))
     }
 
+    /// Reconcile the output columns of both sides of a set operation, the columns at the
+    /// same position are cast to their common super type.
+    ///
+    /// The output columns of left side are always projected to columns with fresh indexes,
+    /// which are the output columns of the set operation. The right side is only projected
+    /// if cast is needed.
     #[allow(clippy::type_complexity)]
-    fn coercion_union_type(
+    pub(super) fn reconcile_set_operation(
         &self,
-        left_bind_context: BindContext,
-        right_bind_context: BindContext,
-        mut left_expr: SExpr,
-        mut right_expr: SExpr,
-        coercion_type: DataTypeImpl,
-    ) -> Result<(BindContext, Vec<(IndexType, IndexType)>, SExpr, SExpr)> {
-        let mut left_scalar_items = Vec::with_capacity(left_bind_context.columns.len());
-        let mut right_scalar_items = Vec::with_capacity(right_bind_context.columns.len());
-        let mut new_bind_context = BindContext::new();
-        let mut pairs = Vec::with_capacity(left_bind_context.columns.len());
-        for (left_col, right_col) in left_bind_context
-            .columns
-            .iter()
-            .zip(right_bind_context.columns.iter())
-        {
-            let left_index = if left_col.data_type != coercion_type {
-                let new_column_index = self.metadata.write().add_column(
-                    left_col.column_name.clone(),
-                    coercion_type.clone(),
-                    None,
-                    None,
-                );
-                let column_binding = ColumnBinding {
-                    database_name: None,
-                    table_name: None,
-                    column_name: left_col.column_name.clone(),
-                    index: new_column_index,
-                    data_type: Box::new(coercion_type.clone()),
-                    visibility: Visibility::Visible,
-                };
-                let left_coercion_expr = CastExpr {
-                    argument: Box::new(
-                        BoundColumnRef {
-                            column: left_col.clone(),
-                        }
-                        .into(),
-                    ),
-                    from_type: Box::new(*left_col.data_type.clone()),
-                    target_type: Box::new(coercion_type.clone()),
-                };
-                left_scalar_items.push(ScalarItem {
-                    scalar: left_coercion_expr.into(),
-                    index: new_column_index,
-                });
-                new_bind_context.add_column_binding(column_binding);
-                new_column_index
-            } else {
-                new_bind_context.add_column_binding(left_col.clone());
-                left_col.index
-            };
-            let right_index = if right_col.data_type != coercion_type {
-                let new_column_index = self.metadata.write().add_column(
+        op: &SetOperator,
+        left_bind_context: &BindContext,
+        right_bind_context: &BindContext,
+        left_expr: SExpr,
+        right_expr: SExpr,
+    ) -> Result<(Vec<ColumnBinding>, Vec<ColumnBinding>, SExpr, SExpr)> {
+        let op_name = match op {
+            SetOperator::Union => "UNION",
+            SetOperator::Except => "EXCEPT",
+            SetOperator::Intersect => "INTERSECT",
+        };
+        let left_columns = left_bind_context.result_column_bindings();
+        let right_columns = right_bind_context.result_column_bindings();
+        if left_columns.len() != right_columns.len() {
+            return Err(ErrorCode::SemanticError(format!(
+                "each {} query must have the same number of columns, but the left side has {} columns and the right side has {} columns",
+                op_name,
+                left_columns.len(),
+                right_columns.len()
+            )));
+        }
+
+        let mut left_scalar_items = Vec::with_capacity(left_columns.len());
+        let mut right_scalar_items = vec![];
+        let mut left_output_columns = Vec::with_capacity(left_columns.len());
+        let mut right_output_columns = Vec::with_capacity(right_columns.len());
+        for (left_col, right_col) in left_columns.into_iter().zip(right_columns) {
+            // Find the common super type, e.g. `Timestamp` of `Date` and `Timestamp`.
+            let data_type = merge_types(&left_col.data_type, &right_col.data_type)
+                .or_else(|_| compare_coercion(&left_col.data_type, &right_col.data_type))
+                .map_err(|_| {
+                    ErrorCode::SemanticError(format!(
+                        "{} types {} and {} of column \"{}\" cannot be matched",
+                        op_name,
+                        left_col.data_type.name(),
+                        right_col.data_type.name(),
+                        left_col.column_name
+                    ))
+                })?;
+
+            let index = self.metadata.write().add_column(
+                left_col.column_name.clone(),
+                data_type.clone(),
+                None,
+                None,
+            );
+            left_output_columns.push(ColumnBinding {
+                database_name: None,
+                table_name: None,
+                column_name: left_col.column_name.clone(),
+                index,
+                data_type: Box::new(data_type.clone()),
+                visibility: Visibility::Visible,
+                lineage: left_col.lineage.clone(),
+            });
+            left_scalar_items.push(ScalarItem {
+                scalar: wrap_cast_if_needed(BoundColumnRef { column: left_col }.into(), &data_type),
+                index,
+            });
+
+            if *right_col.data_type != data_type {
+                let index = self.metadata.write().add_column(
                     right_col.column_name.clone(),
-                    coercion_type.clone(),
+                    data_type.clone(),
                     None,
                     None,
                 );
-                let right_coercion_expr = CastExpr {
-                    argument: Box::new(
-                        BoundColumnRef {
-                            column: right_col.clone(),
-                        }
-                        .into(),
-                    ),
-                    from_type: Box::new(*right_col.data_type.clone()),
-                    target_type: Box::new(coercion_type.clone()),
-                };
+                right_output_columns.push(ColumnBinding {
+                    index,
+                    data_type: Box::new(data_type.clone()),
+                    ..right_col.clone()
+                });
                 right_scalar_items.push(ScalarItem {
-                    scalar: right_coercion_expr.into(),
-                    index: new_column_index,
+                    scalar: wrap_cast(BoundColumnRef { column: right_col }.into(), &data_type),
+                    index,
                 });
-                new_column_index
             } else {
-                right_col.index
-            };
-            pairs.push((left_index, right_index));
-        }
-        if !left_scalar_items.is_empty() {
-            left_expr = SExpr::create_unary(
-                EvalScalar {
-                    items: left_scalar_items,
-                }
-                .into(),
-                left_expr,
-            );
+                right_output_columns.push(right_col);
+            }
         }
-        if !right_scalar_items.is_empty() {
-            right_expr = SExpr::create_unary(
+
+        let left_expr = SExpr::create_unary(
+            EvalScalar {
+                items: left_scalar_items,
+            }
+            .into(),
+            left_expr,
+        );
+        let right_expr = if right_scalar_items.is_empty() {
+            right_expr
+        } else {
+            SExpr::create_unary(
                 EvalScalar {
                     items: right_scalar_items,
                 }
                 .into(),
                 right_expr,
-            );
-        }
-        Ok((new_bind_context, pairs, left_expr, right_expr))
+            )
+        };
+        Ok((
+            left_output_columns,
+            right_output_columns,
+            left_expr,
+            right_expr,
+        ))
     }
 }
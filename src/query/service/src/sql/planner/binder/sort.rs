@@ -130,10 +130,37 @@ impl<'a> Binder {
                     })?;
                     match result {
                         NameResolutionResult::Column(column) => {
+                            // In an aggregated query, a column which is not in select list
+                            // must be a group item, e.g. `SELECT count(*) FROM t GROUP BY a ORDER BY a`.
+                            let agg_info = &from_context.aggregate_info;
+                            let index = if !agg_info.group_items.is_empty()
+                                || !agg_info.aggregate_functions.is_empty()
+                            {
+                                let mut group_checker = GroupingChecker::new(from_context);
+                                let scalar = group_checker.resolve(
+                                    &BoundColumnRef {
+                                        column: column.clone(),
+                                    }
+                                    .into(),
+                                    Some(std::slice::from_ref(&ident.span)),
+                                )?;
+                                match scalar {
+                                    Scalar::BoundColumnRef(BoundColumnRef { column }) => {
+                                        column.index
+                                    }
+                                    _ => {
+                                        return Err(ErrorCode::LogicalError(
+                                            "Invalid grouping check result",
+                                        ));
+                                    }
+                                }
+                            } else {
+                                column.index
+                            };
                             order_items.push(OrderItem {
                                 expr: order.clone(),
                                 name: column.column_name.clone(),
-                                index: column.index,
+                                index,
                                 need_eval_scalar: false,
                             });
                         }
@@ -146,12 +173,13 @@ impl<'a> Binder {
                     lit: Literal::Integer(index),
                     ..
                 } => {
-                    let index = *index as usize - 1;
-                    if index >= projections.len() {
+                    if *index == 0 || *index as usize > projections.len() {
                         return Err(ErrorCode::SemanticError(order.expr.span().display_error(
-                            format!("ORDER BY position {} is not in select list", index + 1),
+                            format!("ORDER BY position {} is not in select list", index),
                         )));
                     }
+                    // Convert to zero-based index
+                    let index = *index as usize - 1;
                     order_items.push(OrderItem {
                         expr: order.clone(),
                         name: projections[index].column_name.clone(),
@@ -160,9 +188,18 @@ impl<'a> Binder {
                     });
                 }
                 _ => {
+                    // Unlike a bare identifier, the columns referenced inside an expression
+                    // are resolved with `FROM` clause first, so an alias in select list with
+                    // the same name as an input column is shadowed by the column,
+                    // e.g. `SELECT -a AS a FROM t ORDER BY a + 1`.
                     let mut bind_context = from_context.clone();
                     for column_binding in projections.iter() {
-                        if bind_context.columns.contains(column_binding) {
+                        if bind_context.columns.contains(column_binding)
+                            || from_context
+                                .columns
+                                .iter()
+                                .any(|column| column.column_name == column_binding.column_name)
+                        {
                             continue;
                         }
                         bind_context.columns.push(column_binding.clone());
@@ -185,6 +222,24 @@ impl<'a> Binder {
                             Ok(None)
                         })
                         .map_err(|e| ErrorCode::SemanticError(e.message()))?;
+                    if distinct {
+                        // The expression must be computed from the select list, or be one of the
+                        // select list expressions, e.g. `SELECT DISTINCT a + 1 ... ORDER BY a + 1`.
+                        let from_select_list = bound_expr
+                            .used_columns()
+                            .iter()
+                            .all(|index| projections.iter().any(|column| column.index == *index));
+                        if !from_select_list
+                            && !scalar_items
+                                .values()
+                                .any(|item| item.scalar == rewrite_scalar)
+                        {
+                            return Err(ErrorCode::SemanticError(order.expr.span().display_error(
+                                "for SELECT DISTINCT, ORDER BY expressions must appear in select list"
+                                    .to_string(),
+                            )));
+                        }
+                    }
                     let column_binding = self.create_column_binding(
                         None,
                         None,
@@ -14,6 +14,7 @@
 
 use std::sync::Arc;
 
+use common_ast::ast::Expr;
 use common_ast::ast::Indirection;
 use common_ast::ast::SelectStmt;
 use common_ast::ast::SelectTarget;
@@ -22,11 +23,13 @@ use common_ast::ast::TableAlias;
 use common_ast::ast::TableReference;
 use common_ast::ast::TimeTravelPoint;
 use common_ast::parser::parse_sql;
+use common_ast::parser::token::Token;
 use common_ast::parser::tokenize_sql;
 use common_ast::Backtrace;
 use common_ast::Dialect;
 use common_ast::DisplayError;
 use common_catalog::catalog::CATALOG_DEFAULT;
+use common_datavalues::type_coercion::merge_types;
 use common_datavalues::prelude::*;
 use common_exception::ErrorCode;
 use common_exception::Result;
@@ -36,15 +39,18 @@ use common_planner::IndexType;
 use crate::sql::binder::scalar::ScalarBinder;
 use crate::sql::binder::Binder;
 use crate::sql::binder::ColumnBinding;
+use crate::sql::binder::ColumnLineage;
 use crate::sql::binder::CteInfo;
 use crate::sql::binder::Visibility;
 use crate::sql::optimizer::SExpr;
 use crate::sql::planner::semantic::normalize_identifier;
 use crate::sql::planner::semantic::TypeChecker;
 use crate::sql::plans::ConstantExpr;
+use crate::sql::plans::ConstantTableScan;
 use crate::sql::plans::LogicalGet;
 use crate::sql::plans::Scalar;
 use crate::sql::BindContext;
+use crate::storages::view::view_table::COLUMN_NAMES;
 use crate::storages::view::view_table::QUERY;
 use crate::storages::NavigationPoint;
 use crate::storages::Table;
@@ -57,7 +63,10 @@ impl<'a> Binder {
         stmt: &SelectStmt<'a>,
     ) -> Result<(SExpr, BindContext)> {
         for select_target in &stmt.select_list {
-            if let SelectTarget::QualifiedName(names) = select_target {
+            if let SelectTarget::QualifiedName {
+                qualified: names, ..
+            } = select_target
+            {
                 for indirect in names {
                     if indirect == &Indirection::Star {
                         return Err(ErrorCode::SemanticError(stmt.span.display_error(
@@ -108,10 +117,12 @@ impl<'a> Binder {
                     .map(|ident| normalize_identifier(ident, &self.name_resolution_ctx).name)
                     .unwrap_or_else(|| self.ctx.get_current_catalog());
 
-                // Get database name
+                // Get database name, the unqualified table is resolved in the default database
+                // of current scope, e.g. the database of the view being expanded.
                 let database = database
                     .as_ref()
                     .map(|ident| normalize_identifier(ident, &self.name_resolution_ctx).name)
+                    .or_else(|| bind_context.default_database.clone())
                     .unwrap_or_else(|| self.ctx.get_current_database());
 
                 let tenant = self.ctx.get_tenant();
@@ -133,21 +144,8 @@ impl<'a> Binder {
                     .await?;
                 match table_meta.engine() {
                     "VIEW" => {
-                        let query = table_meta
-                            .options()
-                            .get(QUERY)
-                            .ok_or_else(|| ErrorCode::LogicalError("Invalid VIEW object"))?;
-                        let tokens = tokenize_sql(query.as_str())?;
-                        let backtrace = Backtrace::new();
-                        let (stmt, _) = parse_sql(&tokens, Dialect::PostgreSQL, &backtrace)?;
-                        if let Statement::Query(query) = &stmt {
-                            self.bind_query(bind_context, query).await
-                        } else {
-                            Err(ErrorCode::LogicalError(format!(
-                                "Invalid VIEW object: {}",
-                                table_meta.name()
-                            )))
-                        }
+                        self.bind_view(bind_context, &database, table_meta, alias)
+                            .await
                     }
                     _ => {
                         let table_index =
@@ -171,6 +169,7 @@ impl<'a> Binder {
                 params,
                 alias,
             } => {
+                let func_name = normalize_identifier(name, &self.name_resolution_ctx).name;
                 let mut scalar_binder = ScalarBinder::new(
                     bind_context,
                     self.ctx.clone(),
@@ -178,27 +177,26 @@ impl<'a> Binder {
                     self.metadata.clone(),
                     &[],
                 );
-                let mut args = Vec::with_capacity(params.len());
+                let mut expressions = Vec::with_capacity(params.len());
                 for arg in params.iter() {
-                    args.push(scalar_binder.bind(arg).await?);
-                }
-
-                let expressions = args
-                    .into_iter()
-                    .map(|(scalar, _)| match scalar {
-                        Scalar::ConstantExpr(ConstantExpr { value, data_type }) => {
-                            Ok(LegacyExpression::Literal {
+                    let (scalar, _) = scalar_binder.bind(arg).await?;
+                    match scalar {
+                        Scalar::ConstantExpr(ConstantExpr { value, data_type }) => expressions
+                            .push(LegacyExpression::Literal {
                                 value,
                                 column_name: None,
                                 data_type: *data_type,
-                            })
+                            }),
+                        _ => {
+                            return Err(ErrorCode::SemanticError(arg.span().display_error(
+                                format!(
+                                    "arguments of table function {} must be constant expressions",
+                                    func_name
+                                ),
+                            )));
                         }
-                        _ => Err(ErrorCode::UnImplement(format!(
-                            "Unsupported table argument type: {:?}",
-                            scalar
-                        ))),
-                    })
-                    .collect::<Result<Vec<LegacyExpression>>>()?;
+                    }
+                }
 
                 let table_args = Some(expressions);
 
@@ -206,10 +204,7 @@ impl<'a> Binder {
                 let table_meta: Arc<dyn TableFunction> = self
                     .catalogs
                     .get_catalog(CATALOG_DEFAULT)?
-                    .get_table_function(
-                        &normalize_identifier(name, &self.name_resolution_ctx).name,
-                        table_args,
-                    )?;
+                    .get_table_function(&func_name, table_args)?;
                 let table = table_meta.as_table();
 
                 let table_index = self.metadata.write().add_table(
@@ -238,7 +233,107 @@ impl<'a> Binder {
                 }
                 Ok((s_expr, bind_context))
             }
+            TableReference::Values {
+                span,
+                values,
+                alias,
+            } => {
+                let (s_expr, mut bind_context) =
+                    self.bind_values(bind_context, span, values).await?;
+                if let Some(alias) = alias {
+                    bind_context.apply_table_alias(alias, &self.name_resolution_ctx)?;
+                }
+                Ok((s_expr, bind_context))
+            }
+        }
+    }
+
+    /// Bind a `VALUES (...), (...)` table constructor. The columns are named `col0`, `col1`, ...
+    /// and each column takes the common super type of the values in all rows.
+    async fn bind_values(
+        &mut self,
+        bind_context: &BindContext,
+        span: &'a [Token<'a>],
+        values: &[Vec<Expr<'a>>],
+    ) -> Result<(SExpr, BindContext)> {
+        let num_columns = values[0].len();
+        let mut rows = Vec::with_capacity(values.len());
+        let mut column_types: Vec<DataTypeImpl> = Vec::with_capacity(num_columns);
+        for (row_index, row) in values.iter().enumerate() {
+            if row.len() != num_columns {
+                return Err(ErrorCode::SemanticError(span.display_error(format!(
+                    "VALUES row {} has {} values, but row 1 has {} values",
+                    row_index + 1,
+                    row.len(),
+                    num_columns
+                ))));
+            }
+
+            // Values can't reference any columns, so they are bound in an empty context.
+            let values_context = BindContext::new();
+            let mut scalar_binder = ScalarBinder::new(
+                &values_context,
+                self.ctx.clone(),
+                &self.name_resolution_ctx,
+                self.metadata.clone(),
+                &[],
+            );
+            let mut row_values = Vec::with_capacity(num_columns);
+            for (column_index, expr) in row.iter().enumerate() {
+                let (scalar, data_type) = scalar_binder.bind(expr).await?;
+                let value = match scalar {
+                    Scalar::ConstantExpr(ConstantExpr { value, .. }) => value,
+                    _ => {
+                        return Err(ErrorCode::SemanticError(expr.span().display_error(
+                            "VALUES only supports constant expressions".to_string(),
+                        )));
+                    }
+                };
+                if row_index == 0 {
+                    column_types.push(data_type);
+                } else {
+                    let column_type = &column_types[column_index];
+                    let merged_type = merge_types(column_type, &data_type).map_err(|_| {
+                        ErrorCode::SemanticError(span.display_error(format!(
+                            "VALUES types {} and {} of column \"col{}\" cannot be matched",
+                            column_type.name(),
+                            data_type.name(),
+                            column_index
+                        )))
+                    })?;
+                    column_types[column_index] = merged_type;
+                }
+                row_values.push(value);
+            }
+            rows.push(row_values);
+        }
+
+        let mut bind_context = BindContext::with_parent(Box::new(bind_context.clone()));
+        let mut columns = Vec::with_capacity(num_columns);
+        for (column_index, data_type) in column_types.into_iter().enumerate() {
+            let column_name = format!("col{}", column_index);
+            let index = self.metadata.write().add_column(
+                column_name.clone(),
+                data_type.clone(),
+                None,
+                None,
+            );
+            bind_context.add_column_binding(ColumnBinding {
+                database_name: None,
+                table_name: None,
+                column_name,
+                index,
+                data_type: Box::new(data_type),
+                visibility: Visibility::Visible,
+                lineage: None,
+            });
+            columns.push(index);
         }
+
+        Ok((
+            SExpr::create_leaf(ConstantTableScan { columns, rows }.into()),
+            bind_context,
+        ))
     }
 
     fn bind_cte(
@@ -248,8 +343,10 @@ impl<'a> Binder {
         alias: &Option<TableAlias>,
         cte_info: &CteInfo,
     ) -> Result<(SExpr, BindContext)> {
+        let (s_expr, cte_output_context) = self.instantiate_cte(cte_info);
+
         let mut new_bind_context = bind_context.clone();
-        new_bind_context.columns = cte_info.bind_context.columns.clone();
+        new_bind_context.columns = cte_output_context.columns;
         let mut cols_alias = cte_info.columns_alias.clone();
         if let Some(alias) = alias {
             for (idx, col_alias) in alias.columns.iter().enumerate() {
@@ -264,22 +361,72 @@ impl<'a> Binder {
             .as_ref()
             .map(|alias| normalize_identifier(&alias.name, &self.name_resolution_ctx).name)
             .unwrap_or_else(|| table_name.to_string());
-        for column in new_bind_context.columns.iter_mut() {
-            column.database_name = None;
-            column.table_name = Some(alias_table_name.clone());
-        }
+        new_bind_context.rename_columns(alias_table_name, &cols_alias)?;
+        Ok((s_expr, new_bind_context))
+    }
 
-        if cols_alias.len() > new_bind_context.columns.len() {
+    /// Bind a view as a derived table. The stored query of view is bound in the database
+    /// where the view is defined, and its columns are qualified with the name of view and
+    /// renamed with the column names declared by the view.
+    async fn bind_view(
+        &mut self,
+        bind_context: &BindContext,
+        database: &str,
+        table_meta: Arc<dyn Table>,
+        alias: &Option<TableAlias<'a>>,
+    ) -> Result<(SExpr, BindContext)> {
+        let view_name = table_meta.name().to_string();
+        let qualified_view_name = format!("{database}.{view_name}");
+        if let Some(position) = bind_context
+            .view_path
+            .iter()
+            .position(|view| view == &qualified_view_name)
+        {
+            let mut cycle = bind_context.view_path[position..].to_vec();
+            cycle.push(qualified_view_name);
             return Err(ErrorCode::SemanticError(format!(
-                "table has {} columns available but {} columns specified",
-                new_bind_context.columns.len(),
-                cols_alias.len()
+                "recursive view is detected: {}",
+                cycle.join(" -> ")
             )));
         }
-        for (index, column_name) in cols_alias.iter().enumerate() {
-            new_bind_context.columns[index].column_name = column_name.clone();
+
+        let options = table_meta.options();
+        let query = options
+            .get(QUERY)
+            .ok_or_else(|| ErrorCode::LogicalError("Invalid VIEW object"))?;
+        let column_names: Vec<String> = match options.get(COLUMN_NAMES) {
+            Some(column_names) => serde_json::from_str(column_names)?,
+            None => vec![],
+        };
+        let tokens = tokenize_sql(query.as_str())?;
+        let backtrace = Backtrace::new();
+        let (stmt, _) = parse_sql(&tokens, Dialect::PostgreSQL, &backtrace)?;
+        let query = match &stmt {
+            Statement::Query(query) => query,
+            _ => {
+                return Err(ErrorCode::LogicalError(format!(
+                    "Invalid VIEW object: {view_name}"
+                )));
+            }
+        };
+
+        // Tables referenced by the view are resolved in the database of the view,
+        // rather than the database of current query.
+        let mut view_context = bind_context.clone();
+        view_context.default_database = Some(database.to_string());
+        view_context.view_path.push(qualified_view_name);
+        let (s_expr, mut view_output_context) = self.bind_query(&view_context, query).await?;
+        view_output_context.default_database = bind_context.default_database.clone();
+        view_output_context.view_path = bind_context.view_path.clone();
+
+        view_output_context.rename_columns(view_name, &column_names)?;
+        for column in view_output_context.columns.iter_mut() {
+            column.database_name = Some(database.to_string());
+        }
+        if let Some(alias) = alias {
+            view_output_context.apply_table_alias(alias, &self.name_resolution_ctx)?;
         }
-        Ok((cte_info.s_expr.clone(), new_bind_context))
+        Ok((s_expr, view_output_context))
     }
 
     async fn bind_base_table(
@@ -303,6 +450,11 @@ impl<'a> Binder {
                 } else {
                     Visibility::Visible
                 },
+                lineage: Some(ColumnLineage::Table {
+                    database: database_name.to_string(),
+                    table: table.name().to_string(),
+                    column: column.name().to_string(),
+                }),
             };
             bind_context.add_column_binding(column_binding);
         }
@@ -0,0 +1,475 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use common_ast::ast::Expr;
+use common_ast::ast::Identifier;
+use common_ast::ast::Literal;
+use common_ast::ast::OrderByExpr;
+use common_ast::ast::Query;
+use common_ast::ast::SelectStmt;
+use common_ast::ast::SelectTarget;
+use common_ast::ast::SubqueryModifier;
+use common_ast::ast::Window;
+use common_ast::ast::WindowSpec;
+use common_ast::parser::token::Token;
+use common_ast::walk_expr;
+use common_ast::walk_window;
+use common_ast::DisplayError;
+use common_ast::Visitor;
+use common_datavalues::DataField;
+use common_datavalues::DataValue;
+use common_datavalues::Float64Type;
+use common_datavalues::UInt64Type;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_functions::aggregates::AggregateFunctionFactory;
+
+use crate::sql::binder::aggregate::AggregateRewriter;
+use crate::sql::binder::scalar::ScalarBinder;
+use crate::sql::binder::Binder;
+use crate::sql::normalize_identifier;
+use crate::sql::optimizer::SExpr;
+use crate::sql::planner::semantic::GroupingChecker;
+use crate::sql::planner::semantic::TypeChecker;
+use crate::sql::plans::Scalar;
+use crate::sql::plans::ScalarExpr;
+use crate::sql::plans::Window as WindowPlan;
+use crate::sql::plans::WindowFunction;
+use crate::sql::plans::WindowOrderBy;
+use crate::sql::BindContext;
+
+#[derive(Default, Clone, PartialEq, Eq, Debug)]
+pub struct WindowInfo {
+    /// Window functions, they will be evaluated after aggregation
+    pub window_scalar_exprs: Vec<WindowFunction>,
+
+    /// Mapping: (window function display name) -> (index of window function in `window_scalar_exprs`)
+    /// This is used to find a window function in current context. A window function
+    /// referencing a named window, e.g. `sum(a) OVER w`, is registered with both its
+    /// own display name and the one with the window specification expanded, so the
+    /// window functions sharing the same window definition are evaluated only once.
+    pub window_functions_map: HashMap<String, usize>,
+}
+
+impl WindowInfo {
+    /// Returns true if `scalar` references the output of any window function.
+    pub fn contains_window_function(&self, scalar: &Scalar) -> bool {
+        let used_columns = scalar.used_columns();
+        self.window_scalar_exprs
+            .iter()
+            .any(|window_function| used_columns.contains(&window_function.index))
+    }
+}
+
+/// Ranking window functions, which don't take any argument.
+const RANKING_FUNCTIONS: [&str; 5] = [
+    "row_number",
+    "rank",
+    "dense_rank",
+    "percent_rank",
+    "cume_dist",
+];
+
+impl<'a> Binder {
+    /// Analyze window functions in select clause and `ORDER BY` clause, each of them
+    /// will be registered in `WindowInfo` with a new column.
+    /// The window functions are referenced as columns when binding the select list.
+    pub(super) async fn analyze_window_select(
+        &mut self,
+        bind_context: &mut BindContext,
+        stmt: &SelectStmt<'a>,
+        order_by: &[OrderByExpr<'a>],
+    ) -> Result<()> {
+        let mut window_definitions: HashMap<String, &WindowSpec<'a>> = HashMap::new();
+        for definition in stmt.window_list.iter() {
+            let name = normalize_identifier(&definition.name, &self.name_resolution_ctx).name;
+            if window_definitions
+                .insert(name.clone(), &definition.spec)
+                .is_some()
+            {
+                return Err(ErrorCode::SemanticError(
+                    definition
+                        .name
+                        .span
+                        .display_error(format!("window \"{}\" is already defined", name)),
+                ));
+            }
+        }
+
+        let mut finder = WindowFunctionFinder::default();
+        for target in stmt.select_list.iter() {
+            if let SelectTarget::AliasedExpr { expr, .. } = target {
+                walk_expr(&mut finder, expr);
+            }
+        }
+        for order in order_by.iter() {
+            walk_expr(&mut finder, &order.expr);
+        }
+        if let Some(span) = finder.nested {
+            return Err(ErrorCode::SemanticError(span.display_error(
+                "window function calls cannot be nested".to_string(),
+            )));
+        }
+
+        for expr in finder.window_functions.iter() {
+            let display_name = format!("{:#}", expr);
+            if bind_context
+                .window_info
+                .window_functions_map
+                .contains_key(&display_name)
+            {
+                continue;
+            }
+
+            let (span, distinct, name, args, params, window) = match expr {
+                Expr::FunctionCall {
+                    span,
+                    distinct,
+                    name,
+                    args,
+                    params,
+                    window: Some(window),
+                } => (*span, *distinct, name, args, params, window),
+                _ => unreachable!(),
+            };
+
+            // Expand the named window, so that the window functions sharing
+            // the same window definition can be identified.
+            let spec = match window {
+                Window::WindowSpec(spec) => spec,
+                Window::WindowReference(ident) => {
+                    let name = normalize_identifier(ident, &self.name_resolution_ctx).name;
+                    window_definitions.get(&name).copied().ok_or_else(|| {
+                        ErrorCode::SemanticError(
+                            ident
+                                .span
+                                .display_error(format!("window \"{}\" does not exist", name)),
+                        )
+                    })?
+                }
+            };
+            let expanded_name = format!("{:#}", Expr::FunctionCall {
+                span,
+                distinct,
+                name: name.clone(),
+                args: args.clone(),
+                params: params.clone(),
+                window: Some(Window::WindowSpec(spec.clone())),
+            });
+            if let Some(index) = bind_context
+                .window_info
+                .window_functions_map
+                .get(&expanded_name)
+                .cloned()
+            {
+                bind_context
+                    .window_info
+                    .window_functions_map
+                    .insert(display_name, index);
+                continue;
+            }
+
+            let window_function = self
+                .resolve_window_function(
+                    bind_context,
+                    span,
+                    distinct,
+                    name,
+                    args,
+                    params,
+                    spec,
+                    expanded_name.clone(),
+                )
+                .await?;
+
+            let window_info = &mut bind_context.window_info;
+            window_info.window_scalar_exprs.push(window_function);
+            let index = window_info.window_scalar_exprs.len() - 1;
+            window_info.window_functions_map.insert(display_name, index);
+            window_info
+                .window_functions_map
+                .insert(expanded_name, index);
+        }
+
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn resolve_window_function(
+        &mut self,
+        bind_context: &mut BindContext,
+        span: &[Token<'_>],
+        distinct: bool,
+        name: &Identifier<'_>,
+        args: &[Expr<'_>],
+        params: &[Literal],
+        spec: &WindowSpec<'_>,
+        display_name: String,
+    ) -> Result<WindowFunction> {
+        let func_name = name.name.to_lowercase();
+        if distinct {
+            return Err(ErrorCode::SemanticError(span.display_error(
+                "DISTINCT is not supported for window functions".to_string(),
+            )));
+        }
+
+        // Arguments and window specification are bound with the input of `SELECT`
+        let mut scalar_binder = ScalarBinder::new(
+            bind_context,
+            self.ctx.clone(),
+            &self.name_resolution_ctx,
+            self.metadata.clone(),
+            &[],
+        );
+        let mut arguments = Vec::with_capacity(args.len());
+        for arg in args.iter() {
+            arguments.push(scalar_binder.bind(arg).await?);
+        }
+        let mut partition_by = Vec::with_capacity(spec.partition_by.len());
+        for expr in spec.partition_by.iter() {
+            partition_by.push(scalar_binder.bind(expr).await?.0);
+        }
+        let mut order_by = Vec::with_capacity(spec.order_by.len());
+        for order in spec.order_by.iter() {
+            order_by.push(WindowOrderBy {
+                expr: scalar_binder.bind(&order.expr).await?.0,
+                asc: order.asc.unwrap_or(true),
+                nulls_first: order.nulls_first.unwrap_or(false),
+            });
+        }
+
+        let (params, return_type) = if RANKING_FUNCTIONS.contains(&func_name.as_str()) {
+            if !args.is_empty() || !params.is_empty() {
+                return Err(ErrorCode::SemanticError(span.display_error(format!(
+                    "window function {} does not take arguments",
+                    func_name
+                ))));
+            }
+            let return_type = match func_name.as_str() {
+                "percent_rank" | "cume_dist" => Float64Type::new_impl(),
+                _ => UInt64Type::new_impl(),
+            };
+            (vec![], return_type)
+        } else if AggregateFunctionFactory::instance().check(&func_name) {
+            let type_checker = TypeChecker::new(
+                bind_context,
+                self.ctx.clone(),
+                &self.name_resolution_ctx,
+                self.metadata.clone(),
+                &[],
+            );
+            let params = params
+                .iter()
+                .map(|literal| {
+                    type_checker
+                        .resolve_literal(literal, None)
+                        .map(|box (value, _)| value)
+                })
+                .collect::<Result<Vec<DataValue>>>()?;
+            let data_fields = arguments
+                .iter()
+                .map(|(_, data_type)| DataField::new("", data_type.clone()))
+                .collect();
+            let agg_func = AggregateFunctionFactory::instance()
+                .get(&func_name, params.clone(), data_fields)
+                .map_err(|e| ErrorCode::SemanticError(span.display_error(e.message())))?;
+            (params, agg_func.return_type()?)
+        } else {
+            return Err(ErrorCode::SemanticError(span.display_error(format!(
+                "OVER specified, but {} is not a window function nor an aggregate function",
+                func_name
+            ))));
+        };
+
+        // Aggregate functions in the arguments and window specification are
+        // computed by aggregation, e.g. `rank() OVER (ORDER BY sum(a))`.
+        let mut rewriter = AggregateRewriter::new(bind_context, self.metadata.clone());
+        let args = arguments
+            .iter()
+            .map(|(arg, _)| rewriter.visit(arg))
+            .collect::<Result<Vec<_>>>()?;
+        let partition_by = partition_by
+            .iter()
+            .map(|scalar| rewriter.visit(scalar))
+            .collect::<Result<Vec<_>>>()?;
+        let order_by = order_by
+            .into_iter()
+            .map(|order| {
+                Ok(WindowOrderBy {
+                    expr: rewriter.visit(&order.expr)?,
+                    ..order
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let index =
+            self.metadata
+                .write()
+                .add_column(display_name.clone(), return_type.clone(), None, None);
+
+        Ok(WindowFunction {
+            index,
+            display_name,
+            func_name,
+            params,
+            args,
+            partition_by,
+            order_by,
+            return_type: Box::new(return_type),
+        })
+    }
+
+    /// Build a `Window` plan evaluating the window functions registered in `bind_context`.
+    pub(super) fn bind_window(
+        &mut self,
+        bind_context: &BindContext,
+        child: SExpr,
+    ) -> Result<SExpr> {
+        let window_functions = bind_context
+            .window_info
+            .window_scalar_exprs
+            .iter()
+            .map(|window_function| {
+                if !bind_context.in_grouping {
+                    return Ok(window_function.clone());
+                }
+                // Window functions are evaluated after aggregation, so they can
+                // only reference group items and aggregate functions.
+                let mut grouping_checker = GroupingChecker::new(bind_context);
+                Ok(WindowFunction {
+                    args: window_function
+                        .args
+                        .iter()
+                        .map(|arg| grouping_checker.resolve(arg, None))
+                        .collect::<Result<Vec<_>>>()?,
+                    partition_by: window_function
+                        .partition_by
+                        .iter()
+                        .map(|scalar| grouping_checker.resolve(scalar, None))
+                        .collect::<Result<Vec<_>>>()?,
+                    order_by: window_function
+                        .order_by
+                        .iter()
+                        .map(|order| {
+                            Ok(WindowOrderBy {
+                                expr: grouping_checker.resolve(&order.expr, None)?,
+                                asc: order.asc,
+                                nulls_first: order.nulls_first,
+                            })
+                        })
+                        .collect::<Result<Vec<_>>>()?,
+                    ..window_function.clone()
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(SExpr::create_unary(
+            WindowPlan { window_functions }.into(),
+            child,
+        ))
+    }
+}
+
+/// Check there is no window function in `expr`, which is a part of `clause`.
+pub(super) fn check_no_window_function(expr: &Expr, clause: &str) -> Result<()> {
+    let mut finder = WindowFunctionFinder::default();
+    walk_expr(&mut finder, expr);
+    match finder.window_functions.first() {
+        Some(window_function) => Err(ErrorCode::SemanticError(
+            window_function
+                .span()
+                .display_error(format!("window functions are not allowed in {}", clause)),
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Find window function calls in an expression.
+#[derive(Default)]
+struct WindowFunctionFinder<'ast> {
+    window_functions: Vec<Expr<'ast>>,
+    // Span of the first window function call inside another one.
+    nested: Option<&'ast [Token<'ast>]>,
+    in_window_function: bool,
+}
+
+impl<'ast> Visitor<'ast> for WindowFunctionFinder<'ast> {
+    fn visit_function_call(
+        &mut self,
+        span: &'ast [Token<'ast>],
+        distinct: bool,
+        name: &'ast Identifier<'ast>,
+        args: &'ast [Expr<'ast>],
+        params: &'ast [Literal],
+        window: &'ast Option<Window<'ast>>,
+    ) {
+        if window.is_none() {
+            for arg in args {
+                walk_expr(self, arg);
+            }
+            return;
+        }
+
+        if self.in_window_function && self.nested.is_none() {
+            self.nested = Some(span);
+        }
+        self.window_functions.push(Expr::FunctionCall {
+            span,
+            distinct,
+            name: name.clone(),
+            args: args.to_vec(),
+            params: params.to_vec(),
+            window: window.clone(),
+        });
+
+        let in_window_function = self.in_window_function;
+        self.in_window_function = true;
+        for arg in args {
+            walk_expr(self, arg);
+        }
+        if let Some(window) = window {
+            walk_window(self, window);
+        }
+        self.in_window_function = in_window_function;
+    }
+
+    // Window functions in a subquery are computed by the subquery itself.
+    fn visit_subquery(
+        &mut self,
+        _span: &'ast [Token<'ast>],
+        _modifier: &'ast Option<SubqueryModifier>,
+        _subquery: &'ast Query<'ast>,
+    ) {
+    }
+
+    fn visit_exists(
+        &mut self,
+        _span: &'ast [Token<'ast>],
+        _not: bool,
+        _subquery: &'ast Query<'ast>,
+    ) {
+    }
+
+    fn visit_in_subquery(
+        &mut self,
+        _span: &'ast [Token<'ast>],
+        expr: &'ast Expr<'ast>,
+        _subquery: &'ast Query<'ast>,
+        _not: bool,
+    ) {
+        walk_expr(self, expr);
+    }
+}
@@ -38,6 +38,8 @@ use crate::sql::plans::PhysicalScan;
 use crate::sql::plans::RelOperator;
 use crate::sql::plans::Scalar;
 use crate::sql::plans::Sort;
+use crate::sql::plans::Window;
+use crate::sql::plans::WindowFunction;
 use crate::sql::ScalarExpr;
 
 #[derive(Clone)]
@@ -81,6 +83,9 @@ impl Display for FormatContext {
                 RelOperator::UnionAll(_) => write!(f, "Union"),
                 RelOperator::Pattern(_) => write!(f, "Pattern"),
                 RelOperator::DummyTableScan(_) => write!(f, "DummyTableScan"),
+                RelOperator::ConstantTableScan(_) => write!(f, "ConstantTableScan"),
+                RelOperator::Window(_) => write!(f, "Window"),
+                RelOperator::RecursiveCte(_) => write!(f, "RecursiveCte"),
             },
             Self::Text(text) => write!(f, "{}", text),
         }
@@ -217,6 +222,7 @@ fn to_format_tree(
         RelOperator::Sort(op) => sort_to_format_tree(op, metadata, children),
         RelOperator::Limit(op) => limit_to_format_tree(op, metadata, children),
         RelOperator::Exchange(op) => exchange_to_format_tree(op, metadata, children),
+        RelOperator::Window(op) => window_to_format_tree(op, metadata, children),
 
         _ => FormatTreeNode::with_children(
             FormatContext::RelOp {
@@ -602,6 +608,13 @@ fn limit_to_format_tree(
                 FormatTreeNode::new(FormatContext::Text(format!("limit: [{}]", limit))),
                 FormatTreeNode::new(FormatContext::Text(format!("offset: [{}]", op.offset))),
             ],
+            if !op.with_ties.is_empty() {
+                vec![FormatTreeNode::new(FormatContext::Text(
+                    "with ties: [true]".to_string(),
+                ))]
+            } else {
+                vec![]
+            },
             children,
         ]
         .concat(),
@@ -640,3 +653,73 @@ fn exchange_to_format_tree(
         ),
     }
 }
+
+fn format_window_function(metadata: &MetadataRef, window_function: &WindowFunction) -> String {
+    let args = window_function
+        .args
+        .iter()
+        .map(|arg| format_scalar(metadata, arg))
+        .collect::<Vec<String>>()
+        .join(", ");
+    let mut window = vec![];
+    if !window_function.partition_by.is_empty() {
+        window.push(format!(
+            "PARTITION BY {}",
+            window_function
+                .partition_by
+                .iter()
+                .map(|scalar| format_scalar(metadata, scalar))
+                .collect::<Vec<String>>()
+                .join(", ")
+        ));
+    }
+    if !window_function.order_by.is_empty() {
+        window.push(format!(
+            "ORDER BY {}",
+            window_function
+                .order_by
+                .iter()
+                .map(|order_by| format!(
+                    "{} {}",
+                    format_scalar(metadata, &order_by.expr),
+                    if order_by.asc { "ASC" } else { "DESC" }
+                ))
+                .collect::<Vec<String>>()
+                .join(", ")
+        ));
+    }
+    format!(
+        "{}({}) OVER ({}) (#{})",
+        window_function.func_name,
+        args,
+        window.join(" "),
+        window_function.index
+    )
+}
+
+fn window_to_format_tree(
+    op: &Window,
+    metadata: MetadataRef,
+    children: Vec<FormatTreeNode<FormatContext>>,
+) -> FormatTreeNode<FormatContext> {
+    let window_functions = op
+        .window_functions
+        .iter()
+        .map(|window_function| format_window_function(&metadata, window_function))
+        .collect::<Vec<String>>()
+        .join(", ");
+    FormatTreeNode::with_children(
+        FormatContext::RelOp {
+            metadata,
+            rel_operator: Box::new(op.clone().into()),
+        },
+        vec![
+            vec![FormatTreeNode::new(FormatContext::Text(format!(
+                "window functions: [{}]",
+                window_functions
+            )))],
+            children,
+        ]
+        .concat(),
+    )
+}
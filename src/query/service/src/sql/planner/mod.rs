@@ -16,6 +16,7 @@ mod format;
 mod metadata;
 #[allow(clippy::module_inception)]
 mod planner;
+mod prepared_statement;
 mod semantic;
 
 pub(crate) mod binder;
@@ -25,9 +26,12 @@ pub mod plans;
 pub use binder::BindContext;
 pub use binder::Binder;
 pub use binder::ColumnBinding;
+pub use binder::ColumnLineage;
+pub use binder::NameResolutionResult;
 pub use binder::Visibility;
 pub use planner::Planner;
 pub use plans::ScalarExpr;
+pub use prepared_statement::PreparedStatement;
 pub use semantic::normalize_identifier;
 pub use semantic::IdentifierNormalizer;
 pub use semantic::NameResolutionContext;
@@ -38,15 +38,18 @@ impl CostModel for DefaultCostModel {
 fn compute_cost_impl(memo: &Memo, m_expr: &MExpr) -> Result<Cost> {
     match &m_expr.plan {
         RelOperator::PhysicalScan(plan) => compute_cost_physical_scan(memo, m_expr, plan),
-        RelOperator::DummyTableScan(_) => Ok(Cost(0.0)),
+        RelOperator::DummyTableScan(_) | RelOperator::ConstantTableScan(_) => Ok(Cost(0.0)),
         RelOperator::PhysicalHashJoin(plan) => compute_cost_hash_join(memo, m_expr, plan),
-        RelOperator::UnionAll(_) => compute_cost_union_all(memo, m_expr),
+        RelOperator::UnionAll(_) | RelOperator::RecursiveCte(_) => {
+            compute_cost_union_all(memo, m_expr)
+        }
 
         RelOperator::EvalScalar(_)
         | RelOperator::Filter(_)
         | RelOperator::Aggregate(_)
         | RelOperator::Sort(_)
-        | RelOperator::Limit(_) => compute_cost_unary_common_operator(memo, m_expr),
+        | RelOperator::Limit(_)
+        | RelOperator::Window(_) => compute_cost_unary_common_operator(memo, m_expr),
 
         _ => Err(ErrorCode::LogicalError(
             "Cannot compute cost from logical plan",
@@ -317,6 +317,7 @@ impl SubqueryRewriter {
                         index,
                         data_type: subquery.data_type.clone(),
                         visibility: Visibility::Visible,
+                        lineage: None,
                     },
                 });
                 let child_expr = *subquery.child_expr.as_ref().unwrap().clone();
@@ -439,6 +440,7 @@ impl SubqueryRewriter {
                         index: *derived_column,
                         data_type: Box::from(column_entry.data_type().clone()),
                         visibility: Visibility::Visible,
+                        lineage: None,
                     };
                     items.push(ScalarItem {
                         scalar: Scalar::BoundColumnRef(BoundColumnRef {
@@ -508,6 +510,7 @@ impl SubqueryRewriter {
                             index: *derived_column,
                             data_type: Box::from(column_entry.data_type().clone()),
                             visibility: Visibility::Visible,
+                            lineage: None,
                         }
                     };
                     group_items.push(ScalarItem {
@@ -550,13 +553,17 @@ impl SubqueryRewriter {
                 Ok(SExpr::create_unary(plan.plan().clone(), flatten_plan))
             }
 
-            RelOperator::UnionAll(op) => {
+            RelOperator::Window(_) => Err(ErrorCode::UnImplement(
+                "Window function in correlated subquery is not supported yet",
+            )),
+
+            RelOperator::UnionAll(_) | RelOperator::RecursiveCte(_) => {
                 let left_flatten_plan =
                     self.flatten(plan.child(0)?, correlated_columns, flatten_info)?;
                 let right_flatten_plan =
                     self.flatten(plan.child(1)?, correlated_columns, flatten_info)?;
                 Ok(SExpr::create_binary(
-                    op.clone().into(),
+                    plan.plan().clone(),
                     left_flatten_plan,
                     right_flatten_plan,
                 ))
@@ -567,6 +574,7 @@ impl SubqueryRewriter {
             | RelOperator::LogicalGet(_)
             | RelOperator::PhysicalScan(_)
             | RelOperator::DummyTableScan(_)
+            | RelOperator::ConstantTableScan(_)
             | RelOperator::PhysicalHashJoin(_) => Err(ErrorCode::LogicalError(
                 "Invalid plan type for flattening subquery",
             )),
@@ -591,6 +599,7 @@ impl SubqueryRewriter {
                             index: *index,
                             data_type: column_binding.data_type.clone(),
                             visibility: column_binding.visibility,
+                            lineage: column_binding.lineage.clone(),
                         },
                     }));
                 }
@@ -685,6 +694,7 @@ impl SubqueryRewriter {
                     index: *correlated_column,
                     data_type: Box::from(data_type.clone()),
                     visibility: Visibility::Visible,
+                    lineage: None,
                 },
             });
             let derive_column = self.derived_columns.get(correlated_column).unwrap();
@@ -696,6 +706,7 @@ impl SubqueryRewriter {
                     index: *derive_column,
                     data_type: Box::from(data_type),
                     visibility: Visibility::Visible,
+                    lineage: None,
                 },
             });
             left_conditions.push(left_column);
@@ -24,6 +24,7 @@ use crate::sql::plans::Aggregate;
 use crate::sql::plans::EvalScalar;
 use crate::sql::plans::LogicalGet;
 use crate::sql::plans::RelOperator;
+use crate::sql::plans::Window;
 use crate::sql::ScalarExpr;
 
 pub struct UnusedColumnPruner {
@@ -209,12 +210,44 @@ impl UnusedColumnPruner {
                     self.keep_required_columns(expr.child(0)?, required)?,
                 ))
             }
-            RelOperator::Limit(p) => Ok(SExpr::create_unary(
-                RelOperator::Limit(p.clone()),
-                self.keep_required_columns(expr.child(0)?, required)?,
-            )),
+            RelOperator::Limit(p) => {
+                // Keep the sort keys to find the ties of the last row.
+                p.with_ties.iter().for_each(|s| {
+                    required.insert(s.index);
+                });
+                Ok(SExpr::create_unary(
+                    RelOperator::Limit(p.clone()),
+                    self.keep_required_columns(expr.child(0)?, required)?,
+                ))
+            }
+
+            RelOperator::Window(p) => {
+                let mut used = vec![];
+                // Only keep window functions needed by parent plan.
+                for window_function in p.window_functions.iter() {
+                    if !required.contains(&window_function.index) {
+                        continue;
+                    }
+                    used.push(window_function.clone());
+                    required.extend(window_function.used_columns());
+                }
+                if used.is_empty() {
+                    // Eliminate unnecessary `Window`
+                    self.keep_required_columns(expr.child(0)?, required)
+                } else {
+                    Ok(SExpr::create_unary(
+                        RelOperator::Window(Window {
+                            window_functions: used,
+                        }),
+                        self.keep_required_columns(expr.child(0)?, required)?,
+                    ))
+                }
+            }
 
-            RelOperator::DummyTableScan(_) | RelOperator::UnionAll(_) => Ok(expr.clone()),
+            RelOperator::DummyTableScan(_)
+            | RelOperator::ConstantTableScan(_)
+            | RelOperator::UnionAll(_)
+            | RelOperator::RecursiveCte(_) => Ok(expr.clone()),
 
             _ => Err(ErrorCode::LogicalError(
                 "Attempting to prune columns of a physical plan is not allowed",
@@ -119,20 +119,46 @@ impl SubqueryRewriter {
                 Ok(SExpr::create_unary(plan.into(), input))
             }
 
-            RelOperator::LogicalInnerJoin(_) | RelOperator::UnionAll(_) => {
-                Ok(SExpr::create_binary(
-                    s_expr.plan().clone(),
-                    self.rewrite(s_expr.child(0)?)?,
-                    self.rewrite(s_expr.child(1)?)?,
-                ))
+            RelOperator::Window(mut plan) => {
+                let mut input = self.rewrite(s_expr.child(0)?)?;
+
+                for window_function in plan.window_functions.iter_mut() {
+                    for scalar in window_function
+                        .args
+                        .iter_mut()
+                        .chain(window_function.partition_by.iter_mut())
+                        .chain(
+                            window_function
+                                .order_by
+                                .iter_mut()
+                                .map(|order_by| &mut order_by.expr),
+                        )
+                    {
+                        let res = self.try_rewrite_subquery(scalar, &input, false)?;
+                        input = res.1;
+                        *scalar = res.0;
+                    }
+                }
+
+                Ok(SExpr::create_unary(plan.into(), input))
             }
 
+            RelOperator::LogicalInnerJoin(_)
+            | RelOperator::UnionAll(_)
+            | RelOperator::RecursiveCte(_) => Ok(SExpr::create_binary(
+                s_expr.plan().clone(),
+                self.rewrite(s_expr.child(0)?)?,
+                self.rewrite(s_expr.child(1)?)?,
+            )),
+
             RelOperator::Limit(_) | RelOperator::Sort(_) => Ok(SExpr::create_unary(
                 s_expr.plan().clone(),
                 self.rewrite(s_expr.child(0)?)?,
             )),
 
-            RelOperator::DummyTableScan(_) | RelOperator::LogicalGet(_) => Ok(s_expr.clone()),
+            RelOperator::DummyTableScan(_)
+            | RelOperator::ConstantTableScan(_)
+            | RelOperator::LogicalGet(_) => Ok(s_expr.clone()),
 
             RelOperator::PhysicalHashJoin(_)
             | RelOperator::Pattern(_)
@@ -306,6 +332,7 @@ impl SubqueryRewriter {
                         index,
                         data_type,
                         visibility: Visibility::Visible,
+                        lineage: None,
                     },
                 });
 
@@ -377,6 +404,7 @@ impl SubqueryRewriter {
                 let limit = Limit {
                     limit: Some(1),
                     offset: 0,
+                    with_ties: vec![],
                 };
                 subquery_expr = SExpr::create_unary(limit.into(), subquery_expr.clone());
 
@@ -424,6 +452,7 @@ impl SubqueryRewriter {
                                 index: agg_func_index,
                                 data_type: Box::new(agg_func.return_type()?),
                                 visibility: Visibility::Visible,
+                                lineage: None,
                             },
                         }
                         .into(),
@@ -473,6 +502,7 @@ impl SubqueryRewriter {
                         index,
                         data_type: subquery.data_type.clone(),
                         visibility: Visibility::Visible,
+                        lineage: None,
                     },
                 });
                 let child_expr = *subquery.child_expr.as_ref().unwrap().clone();
@@ -71,9 +71,10 @@ pub fn optimize(
             rewrite_kind,
         }),
         Plan::Explain { kind, plan } => match kind {
-            ExplainKind::Raw | ExplainKind::Ast(_) | ExplainKind::Syntax(_) => {
-                Ok(Plan::Explain { kind, plan })
-            }
+            ExplainKind::Raw
+            | ExplainKind::Lineage
+            | ExplainKind::Ast(_)
+            | ExplainKind::Syntax(_) => Ok(Plan::Explain { kind, plan }),
             _ => Ok(Plan::Explain {
                 kind,
                 plan: Box::new(optimize(ctx, opt_ctx, *plan)?),
@@ -67,6 +67,10 @@ impl Rule for RulePushDownLimitSort {
 
     fn apply(&self, s_expr: &SExpr, state: &mut TransformState) -> common_exception::Result<()> {
         let limit: Limit = s_expr.plan().clone().try_into()?;
+        if !limit.with_ties.is_empty() {
+            // The rows tying with the last one would be discarded by the sort.
+            return Ok(());
+        }
         if let Some(mut count) = limit.limit {
             count += limit.offset;
             let sort = s_expr.child(0)?;
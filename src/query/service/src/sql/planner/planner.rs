@@ -14,12 +14,14 @@
 
 use std::sync::Arc;
 
+use common_ast::ast::Statement;
 use common_ast::parser::parse_sql;
 use common_ast::parser::token::Token;
 use common_ast::parser::token::TokenKind;
 use common_ast::parser::token::Tokenizer;
 use common_ast::Backtrace;
 use common_catalog::catalog::CatalogManager;
+use common_datavalues::DataValue;
 use common_exception::Result;
 use common_planner::Metadata;
 use common_planner::MetadataRef;
@@ -33,6 +35,7 @@ use crate::sql::optimizer::OptimizerContext;
 use crate::sql::plans::Plan;
 use crate::sql::Binder;
 use crate::sql::NameResolutionContext;
+use crate::sql::PreparedStatement;
 
 const PROBE_INSERT_INITIAL_TOKENS: usize = 128;
 const PROBE_INSERT_MAX_TOKENS: usize = 128 * 8;
@@ -47,6 +50,26 @@ impl Planner {
     }
 
     pub async fn plan_sql(&mut self, sql: &str) -> Result<(Plan, MetadataRef, Option<String>)> {
+        let prepared = self.prepare_sql(sql).await?;
+        prepared.check_parameter_count(0)?;
+        let optimized_plan = self.optimize(prepared.plan)?;
+        Ok((optimized_plan, prepared.metadata, prepared.format))
+    }
+
+    /// Plan a prepared statement with the values of its placeholders.
+    pub fn plan_prepared(
+        &mut self,
+        prepared: &PreparedStatement,
+        values: &[DataValue],
+    ) -> Result<(Plan, MetadataRef, Option<String>)> {
+        let (plan, metadata) = prepared.bind_parameters(values)?;
+        let optimized_plan = self.optimize(plan)?;
+        Ok((optimized_plan, metadata, prepared.format.clone()))
+    }
+
+    /// Parse and bind the SQL without optimizing it, the placeholders in it are left to be
+    /// substituted on each execution by [`Planner::plan_prepared`].
+    pub async fn prepare_sql(&mut self, sql: &str) -> Result<PreparedStatement> {
         let settings = self.ctx.get_settings();
         let sql_dialect = settings.get_sql_dialect()?;
 
@@ -78,6 +101,10 @@ impl Planner {
                 // Step 2: Parse the SQL.
                 let backtrace = Backtrace::new();
                 let (stmt, format) = parse_sql(&tokens, sql_dialect, &backtrace)?;
+                if !matches!(stmt, Statement::ShowWarnings) {
+                    // `SHOW WARNINGS` reports the warnings of the previous statement.
+                    self.ctx.clear_warnings();
+                }
 
                 // Step 3: Bind AST with catalog, and generate a pure logical SExpr
                 let metadata = Arc::new(RwLock::new(Metadata::default()));
@@ -90,13 +117,7 @@ impl Planner {
                 );
                 let plan = binder.bind(&stmt).await?;
 
-                // Step 4: Optimize the SExpr with optimizers, and generate optimized physical SExpr
-                let opt_ctx = Arc::new(OptimizerContext::new(OptimizerConfig {
-                    enable_distributed_optimization: !self.ctx.get_cluster().is_empty(),
-                }));
-                let optimized_plan = optimize(self.ctx.clone(), opt_ctx, plan)?;
-
-                Ok((optimized_plan, metadata.clone(), format))
+                Ok(PreparedStatement::new(plan, metadata.clone(), format))
             }
             .await;
 
@@ -126,4 +147,12 @@ impl Planner {
             }
         }
     }
+
+    // Optimize the SExpr with optimizers, and generate optimized physical SExpr
+    fn optimize(&self, plan: Plan) -> Result<Plan> {
+        let opt_ctx = Arc::new(OptimizerContext::new(OptimizerConfig {
+            enable_distributed_optimization: !self.ctx.get_cluster().is_empty(),
+        }));
+        optimize(self.ctx.clone(), opt_ctx, plan)
+    }
 }
@@ -23,11 +23,15 @@ use crate::sql::plans::LogicalOperator;
 use crate::sql::plans::Operator;
 use crate::sql::plans::PhysicalOperator;
 use crate::sql::plans::RelOp;
+use crate::sql::plans::SortItem;
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Limit {
     pub limit: Option<usize>,
     pub offset: usize,
+    // Sort keys of `FETCH FIRST n ROWS WITH TIES` to find the ties of the last row, taken from
+    // the `Sort` of the child when binding, empty if not `WITH TIES`
+    pub with_ties: Vec<SortItem>,
 }
 
 impl Operator for Limit {
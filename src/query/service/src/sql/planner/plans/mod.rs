@@ -13,6 +13,7 @@
 // limitations under the License.
 
 mod aggregate;
+mod constant_table_scan;
 mod copy_v2;
 pub mod create_table_v2;
 mod dummy_table_scan;
@@ -29,10 +30,12 @@ mod pattern;
 mod physical_scan;
 mod presign;
 mod recluster_table;
+mod recursive_cte;
 mod scalar;
 pub mod share;
 mod sort;
 mod union_all;
+mod window;
 
 use std::fmt::Display;
 use std::sync::Arc;
@@ -85,6 +88,7 @@ use common_planner::plans::UndropDatabasePlan;
 use common_planner::plans::UndropTablePlan;
 use common_planner::plans::UseDatabasePlan;
 use common_planner::MetadataRef;
+pub use constant_table_scan::ConstantTableScan;
 pub use copy_v2::CopyPlanV2;
 pub use copy_v2::ValidationMode;
 pub use create_table_v2::CreateTablePlanV2;
@@ -108,11 +112,15 @@ pub use physical_scan::PhysicalScan;
 pub use presign::PresignAction;
 pub use presign::PresignPlan;
 pub use recluster_table::ReclusterTablePlan;
+pub use recursive_cte::RecursiveCte;
 pub use scalar::*;
 pub use share::*;
 pub use sort::Sort;
 pub use sort::SortItem;
 pub use union_all::UnionAll;
+pub use window::Window;
+pub use window::WindowFunction;
+pub use window::WindowOrderBy;
 
 use super::BindContext;
 use crate::sql::optimizer::SExpr;
@@ -225,6 +233,7 @@ pub enum RewriteKind {
     ShowMetrics,
     ShowProcessList,
     ShowEngines,
+    ShowWarnings,
 
     ShowDatabases,
     ShowTables,
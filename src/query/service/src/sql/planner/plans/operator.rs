@@ -16,6 +16,7 @@ use common_exception::ErrorCode;
 use common_exception::Result;
 
 use super::aggregate::Aggregate;
+use super::constant_table_scan::ConstantTableScan;
 use super::dummy_table_scan::DummyTableScan;
 use super::eval_scalar::EvalScalar;
 use super::filter::Filter;
@@ -25,8 +26,10 @@ use super::logical_get::LogicalGet;
 use super::logical_join::LogicalInnerJoin;
 use super::pattern::PatternPlan;
 use super::physical_scan::PhysicalScan;
+use super::recursive_cte::RecursiveCte;
 use super::sort::Sort;
 use super::union_all::UnionAll;
+use super::window::Window;
 use crate::sql::optimizer::PhysicalProperty;
 use crate::sql::optimizer::RelExpr;
 use crate::sql::optimizer::RelationalProperty;
@@ -84,6 +87,9 @@ pub enum RelOp {
     Exchange,
     UnionAll,
     DummyTableScan,
+    ConstantTableScan,
+    Window,
+    RecursiveCte,
 
     // Pattern
     Pattern,
@@ -106,6 +112,9 @@ pub enum RelOperator {
     Exchange(Exchange),
     UnionAll(UnionAll),
     DummyTableScan(DummyTableScan),
+    ConstantTableScan(ConstantTableScan),
+    Window(Window),
+    RecursiveCte(RecursiveCte),
 
     Pattern(PatternPlan),
 }
@@ -126,6 +135,9 @@ impl Operator for RelOperator {
             RelOperator::Exchange(rel_op) => rel_op.rel_op(),
             RelOperator::UnionAll(rel_op) => rel_op.rel_op(),
             RelOperator::DummyTableScan(rel_op) => rel_op.rel_op(),
+            RelOperator::ConstantTableScan(rel_op) => rel_op.rel_op(),
+            RelOperator::Window(rel_op) => rel_op.rel_op(),
+            RelOperator::RecursiveCte(rel_op) => rel_op.rel_op(),
         }
     }
 
@@ -144,6 +156,9 @@ impl Operator for RelOperator {
             RelOperator::Exchange(rel_op) => rel_op.is_physical(),
             RelOperator::UnionAll(rel_op) => rel_op.is_physical(),
             RelOperator::DummyTableScan(rel_op) => rel_op.is_physical(),
+            RelOperator::ConstantTableScan(rel_op) => rel_op.is_physical(),
+            RelOperator::Window(rel_op) => rel_op.is_physical(),
+            RelOperator::RecursiveCte(rel_op) => rel_op.is_physical(),
         }
     }
 
@@ -162,6 +177,9 @@ impl Operator for RelOperator {
             RelOperator::Exchange(rel_op) => rel_op.is_logical(),
             RelOperator::UnionAll(rel_op) => rel_op.is_logical(),
             RelOperator::DummyTableScan(rel_op) => rel_op.is_logical(),
+            RelOperator::ConstantTableScan(rel_op) => rel_op.is_logical(),
+            RelOperator::Window(rel_op) => rel_op.is_logical(),
+            RelOperator::RecursiveCte(rel_op) => rel_op.is_logical(),
         }
     }
 
@@ -180,6 +198,9 @@ impl Operator for RelOperator {
             RelOperator::Exchange(rel_op) => rel_op.as_logical(),
             RelOperator::UnionAll(rel_op) => rel_op.as_logical(),
             RelOperator::DummyTableScan(rel_op) => rel_op.as_logical(),
+            RelOperator::ConstantTableScan(rel_op) => rel_op.as_logical(),
+            RelOperator::Window(rel_op) => rel_op.as_logical(),
+            RelOperator::RecursiveCte(rel_op) => rel_op.as_logical(),
         }
     }
 
@@ -198,6 +219,9 @@ impl Operator for RelOperator {
             RelOperator::Exchange(rel_op) => rel_op.as_physical(),
             RelOperator::UnionAll(rel_op) => rel_op.as_physical(),
             RelOperator::DummyTableScan(rel_op) => rel_op.as_physical(),
+            RelOperator::ConstantTableScan(rel_op) => rel_op.as_physical(),
+            RelOperator::Window(rel_op) => rel_op.as_physical(),
+            RelOperator::RecursiveCte(rel_op) => rel_op.as_physical(),
         }
     }
 }
@@ -449,3 +473,60 @@ impl TryFrom<RelOperator> for DummyTableScan {
         }
     }
 }
+
+impl From<ConstantTableScan> for RelOperator {
+    fn from(v: ConstantTableScan) -> Self {
+        Self::ConstantTableScan(v)
+    }
+}
+
+impl TryFrom<RelOperator> for ConstantTableScan {
+    type Error = ErrorCode;
+    fn try_from(value: RelOperator) -> Result<Self> {
+        if let RelOperator::ConstantTableScan(value) = value {
+            Ok(value)
+        } else {
+            Err(ErrorCode::LogicalError(
+                "Cannot downcast RelOperator to ConstantTableScan",
+            ))
+        }
+    }
+}
+
+impl From<Window> for RelOperator {
+    fn from(v: Window) -> Self {
+        Self::Window(v)
+    }
+}
+
+impl TryFrom<RelOperator> for Window {
+    type Error = ErrorCode;
+    fn try_from(value: RelOperator) -> Result<Self> {
+        if let RelOperator::Window(value) = value {
+            Ok(value)
+        } else {
+            Err(ErrorCode::LogicalError(
+                "Cannot downcast RelOperator to Window",
+            ))
+        }
+    }
+}
+
+impl From<RecursiveCte> for RelOperator {
+    fn from(v: RecursiveCte) -> Self {
+        Self::RecursiveCte(v)
+    }
+}
+
+impl TryFrom<RelOperator> for RecursiveCte {
+    type Error = ErrorCode;
+    fn try_from(value: RelOperator) -> Result<Self> {
+        if let RelOperator::RecursiveCte(value) = value {
+            Ok(value)
+        } else {
+            Err(ErrorCode::LogicalError(
+                "Cannot downcast RelOperator to RecursiveCte",
+            ))
+        }
+    }
+}
@@ -0,0 +1,118 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::Result;
+use common_planner::IndexType;
+
+use crate::sql::optimizer::Distribution;
+use crate::sql::optimizer::PhysicalProperty;
+use crate::sql::optimizer::RelExpr;
+use crate::sql::optimizer::RelationalProperty;
+use crate::sql::optimizer::RequiredProperty;
+use crate::sql::plans::LogicalOperator;
+use crate::sql::plans::Operator;
+use crate::sql::plans::PhysicalOperator;
+use crate::sql::plans::RelOp;
+
+/// A recursive common table expression, the first child is the non-recursive term and
+/// the second one is the recursive term.
+///
+/// The rows of the non-recursive term are the first working table, and the recursive term
+/// is evaluated on the working table of the previous iteration until it produces no rows.
+/// The reference to the CTE in the recursive term is bound with the columns of the
+/// non-recursive term, which stand for the working table.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct RecursiveCte {
+    pub table_name: String,
+    /// Output columns of the CTE, produced by the non-recursive term.
+    pub columns: Vec<IndexType>,
+    /// Columns of the recursive term, in the same order as `columns`.
+    pub recursive_columns: Vec<IndexType>,
+    /// Fail the query if the recursive term still produces rows after so many iterations,
+    /// see `max_recursive_cte_iterations`.
+    pub max_iterations: u64,
+}
+
+impl Operator for RecursiveCte {
+    fn rel_op(&self) -> RelOp {
+        RelOp::RecursiveCte
+    }
+
+    fn is_physical(&self) -> bool {
+        true
+    }
+
+    fn is_logical(&self) -> bool {
+        true
+    }
+
+    fn as_physical(&self) -> Option<&dyn PhysicalOperator> {
+        Some(self)
+    }
+
+    fn as_logical(&self) -> Option<&dyn LogicalOperator> {
+        Some(self)
+    }
+}
+
+impl LogicalOperator for RecursiveCte {
+    fn derive_relational_prop<'a>(&self, rel_expr: &RelExpr<'a>) -> Result<RelationalProperty> {
+        let anchor_prop = rel_expr.derive_relational_prop_child(0)?;
+        let recursive_prop = rel_expr.derive_relational_prop_child(1)?;
+
+        // Derive output columns
+        let output_columns = anchor_prop
+            .output_columns
+            .union(&recursive_prop.output_columns)
+            .cloned()
+            .collect();
+
+        // Derive outer columns
+        let outer_columns = anchor_prop
+            .outer_columns
+            .union(&recursive_prop.outer_columns)
+            .cloned()
+            .collect();
+
+        // The number of iterations is unknown, take a single one as the estimation.
+        let cardinality = anchor_prop.cardinality + recursive_prop.cardinality;
+
+        Ok(RelationalProperty {
+            output_columns,
+            outer_columns,
+            cardinality,
+            precise_cardinality: None,
+        })
+    }
+}
+
+impl PhysicalOperator for RecursiveCte {
+    fn derive_physical_prop<'a>(&self, _rel_expr: &RelExpr<'a>) -> Result<PhysicalProperty> {
+        Ok(PhysicalProperty {
+            distribution: Distribution::Serial,
+        })
+    }
+
+    fn compute_required_prop_child<'a>(
+        &self,
+        _rel_expr: &RelExpr<'a>,
+        _child_index: usize,
+        required: &RequiredProperty,
+    ) -> Result<RequiredProperty> {
+        // Each iteration reads the whole working table of the previous one.
+        let mut required = required.clone();
+        required.distribution = Distribution::Serial;
+        Ok(required)
+    }
+}
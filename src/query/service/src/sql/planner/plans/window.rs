@@ -0,0 +1,147 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.#[derive(Clone, Debug)]
+
+use common_datavalues::DataTypeImpl;
+use common_datavalues::DataValue;
+use common_exception::Result;
+use common_planner::IndexType;
+
+use crate::sql::optimizer::ColumnSet;
+use crate::sql::optimizer::Distribution;
+use crate::sql::optimizer::PhysicalProperty;
+use crate::sql::optimizer::RelExpr;
+use crate::sql::optimizer::RelationalProperty;
+use crate::sql::optimizer::RequiredProperty;
+use crate::sql::plans::LogicalOperator;
+use crate::sql::plans::Operator;
+use crate::sql::plans::PhysicalOperator;
+use crate::sql::plans::RelOp;
+use crate::sql::plans::Scalar;
+use crate::sql::plans::ScalarExpr;
+
+/// Evaluate window functions, each window function produces a new column
+/// for every input row.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Window {
+    pub window_functions: Vec<WindowFunction>,
+}
+
+/// A window function call with its window specification,
+/// e.g. `rank() OVER (PARTITION BY a ORDER BY b DESC)`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct WindowFunction {
+    /// Index of the output column
+    pub index: IndexType,
+    pub display_name: String,
+
+    pub func_name: String,
+    pub params: Vec<DataValue>,
+    pub args: Vec<Scalar>,
+    pub partition_by: Vec<Scalar>,
+    pub order_by: Vec<WindowOrderBy>,
+    pub return_type: Box<DataTypeImpl>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct WindowOrderBy {
+    pub expr: Scalar,
+    pub asc: bool,
+    pub nulls_first: bool,
+}
+
+impl WindowFunction {
+    pub fn used_columns(&self) -> ColumnSet {
+        let mut used_columns = ColumnSet::new();
+        for scalar in self
+            .args
+            .iter()
+            .chain(self.partition_by.iter())
+            .chain(self.order_by.iter().map(|order_by| &order_by.expr))
+        {
+            used_columns.extend(scalar.used_columns());
+        }
+        used_columns
+    }
+}
+
+impl Operator for Window {
+    fn rel_op(&self) -> RelOp {
+        RelOp::Window
+    }
+
+    fn is_physical(&self) -> bool {
+        true
+    }
+
+    fn is_logical(&self) -> bool {
+        true
+    }
+
+    fn as_physical(&self) -> Option<&dyn PhysicalOperator> {
+        Some(self)
+    }
+
+    fn as_logical(&self) -> Option<&dyn LogicalOperator> {
+        Some(self)
+    }
+}
+
+impl PhysicalOperator for Window {
+    fn derive_physical_prop<'a>(&self, rel_expr: &RelExpr<'a>) -> Result<PhysicalProperty> {
+        rel_expr.derive_physical_prop_child(0)
+    }
+
+    fn compute_required_prop_child<'a>(
+        &self,
+        _rel_expr: &RelExpr<'a>,
+        _child_index: usize,
+        required: &RequiredProperty,
+    ) -> Result<RequiredProperty> {
+        // TODO: distribute the input by the partition keys.
+        let mut required = required.clone();
+        required.distribution = Distribution::Serial;
+        Ok(required)
+    }
+}
+
+impl LogicalOperator for Window {
+    fn derive_relational_prop<'a>(&self, rel_expr: &RelExpr<'a>) -> Result<RelationalProperty> {
+        let input_prop = rel_expr.derive_relational_prop_child(0)?;
+
+        // Derive output columns
+        let mut output_columns = input_prop.output_columns;
+        for window_function in self.window_functions.iter() {
+            output_columns.insert(window_function.index);
+        }
+
+        // Derive outer columns
+        let mut outer_columns = input_prop.outer_columns;
+        for window_function in self.window_functions.iter() {
+            let outer = window_function
+                .used_columns()
+                .difference(&output_columns)
+                .cloned()
+                .collect::<ColumnSet>();
+            outer_columns = outer_columns.union(&outer).cloned().collect();
+        }
+        outer_columns = outer_columns.difference(&output_columns).cloned().collect();
+
+        Ok(RelationalProperty {
+            output_columns,
+            outer_columns,
+            cardinality: input_prop.cardinality,
+            precise_cardinality: input_prop.precise_cardinality,
+        })
+    }
+}
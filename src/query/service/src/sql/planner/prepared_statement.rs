@@ -0,0 +1,248 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use common_datavalues::DataType;
+use common_datavalues::DataTypeImpl;
+use common_datavalues::DataValue;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planner::IndexType;
+use common_planner::MetadataRef;
+use parking_lot::RwLock;
+
+use crate::sql::binder::wrap_cast_if_needed;
+use crate::sql::optimizer::SExpr;
+use crate::sql::plans::AggregateFunction;
+use crate::sql::plans::AndExpr;
+use crate::sql::plans::BoundColumnRef;
+use crate::sql::plans::CastExpr;
+use crate::sql::plans::ComparisonExpr;
+use crate::sql::plans::ConstantExpr;
+use crate::sql::plans::FunctionCall;
+use crate::sql::plans::OrExpr;
+use crate::sql::plans::Plan;
+use crate::sql::plans::RelOperator;
+use crate::sql::plans::Scalar;
+use crate::sql::plans::SubqueryExpr;
+
+/// A statement bound with placeholders such as `?` or `$1`, the values of the placeholders
+/// are supplied at execute time with [`crate::sql::Planner::plan_prepared`].
+///
+/// The placeholders are bound to columns with the inferred types, so that the statement
+/// needn't to be bound again for each execution, they are simply substituted with the values.
+pub struct PreparedStatement {
+    pub(crate) plan: Plan,
+    pub(crate) metadata: MetadataRef,
+    pub(crate) format: Option<String>,
+    /// Index of the column bound to each placeholder, ordered by the placeholder index.
+    parameters: Vec<(IndexType, DataTypeImpl)>,
+}
+
+impl PreparedStatement {
+    pub(crate) fn new(plan: Plan, metadata: MetadataRef, format: Option<String>) -> Self {
+        let parameters = {
+            let metadata = metadata.read();
+            metadata
+                .placeholders()
+                .values()
+                .flatten()
+                .map(|index| (*index, metadata.column(*index).data_type().clone()))
+                .collect()
+        };
+        PreparedStatement {
+            plan,
+            metadata,
+            format,
+            parameters,
+        }
+    }
+
+    /// Inferred types of the parameters, the n-th type is for placeholder `$n` or the n-th `?`.
+    pub fn parameter_types(&self) -> Vec<DataTypeImpl> {
+        self.parameters
+            .iter()
+            .map(|(_, data_type)| data_type.clone())
+            .collect()
+    }
+
+    pub(crate) fn check_parameter_count(&self, count: usize) -> Result<()> {
+        if count == self.parameters.len() {
+            return Ok(());
+        }
+        let expected = match self.parameters.len() {
+            0 => "no parameters".to_string(),
+            1 => format!("1 parameter ($1 {})", self.parameters[0].1.name()),
+            n => {
+                let types = self
+                    .parameters
+                    .iter()
+                    .enumerate()
+                    .map(|(i, (_, data_type))| format!("${} {}", i + 1, data_type.name()))
+                    .collect::<Vec<_>>();
+                format!("{} parameters ({})", n, types.join(", "))
+            }
+        };
+        Err(ErrorCode::BadArguments(format!(
+            "Statement expects {}, but {} supplied",
+            expected, count
+        )))
+    }
+
+    /// Substitute the placeholders with `values`, each value is cast to the inferred type of
+    /// its placeholder. Returns the plan with its own copy of metadata.
+    pub(crate) fn bind_parameters(&self, values: &[DataValue]) -> Result<(Plan, MetadataRef)> {
+        self.check_parameter_count(values.len())?;
+
+        let replacements = self
+            .parameters
+            .iter()
+            .zip(values)
+            .map(|((index, data_type), value)| {
+                let constant = ConstantExpr {
+                    value: value.clone(),
+                    data_type: Box::new(value.data_type()),
+                };
+                (*index, wrap_cast_if_needed(constant.into(), data_type))
+            })
+            .collect::<HashMap<_, _>>();
+        let metadata = Arc::new(RwLock::new(self.metadata.read().clone()));
+        let plan = replace_placeholders_in_plan(&self.plan, &metadata, &replacements);
+        Ok((plan, metadata))
+    }
+}
+
+fn replace_placeholders_in_plan(
+    plan: &Plan,
+    metadata: &MetadataRef,
+    replacements: &HashMap<IndexType, Scalar>,
+) -> Plan {
+    match plan {
+        Plan::Query {
+            s_expr,
+            bind_context,
+            rewrite_kind,
+            ..
+        } => {
+            let mut s_expr = s_expr.clone();
+            replace_placeholders_in_s_expr(&mut s_expr, replacements);
+            Plan::Query {
+                s_expr,
+                metadata: metadata.clone(),
+                bind_context: bind_context.clone(),
+                rewrite_kind: rewrite_kind.clone(),
+            }
+        }
+        Plan::Explain { kind, plan } => Plan::Explain {
+            kind: kind.clone(),
+            plan: Box::new(replace_placeholders_in_plan(plan, metadata, replacements)),
+        },
+        plan => plan.clone(),
+    }
+}
+
+fn replace_placeholders_in_s_expr(s_expr: &mut SExpr, replacements: &HashMap<IndexType, Scalar>) {
+    let scalars: Vec<&mut Scalar> = match &mut s_expr.plan {
+        RelOperator::LogicalGet(get) => get
+            .push_down_predicates
+            .iter_mut()
+            .flatten()
+            .chain(
+                get.prewhere
+                    .iter_mut()
+                    .flat_map(|prewhere| prewhere.predicates.iter_mut()),
+            )
+            .collect(),
+        RelOperator::LogicalInnerJoin(join) => join
+            .left_conditions
+            .iter_mut()
+            .chain(join.right_conditions.iter_mut())
+            .chain(join.other_conditions.iter_mut())
+            .collect(),
+        RelOperator::EvalScalar(eval_scalar) => eval_scalar
+            .items
+            .iter_mut()
+            .map(|item| &mut item.scalar)
+            .collect(),
+        RelOperator::Filter(filter) => filter.predicates.iter_mut().collect(),
+        RelOperator::Aggregate(aggregate) => aggregate
+            .group_items
+            .iter_mut()
+            .chain(aggregate.aggregate_functions.iter_mut())
+            .map(|item| &mut item.scalar)
+            .collect(),
+        RelOperator::Window(window) => window
+            .window_functions
+            .iter_mut()
+            .flat_map(|func| {
+                func.args
+                    .iter_mut()
+                    .chain(func.partition_by.iter_mut())
+                    .chain(func.order_by.iter_mut().map(|order_by| &mut order_by.expr))
+            })
+            .collect(),
+        _ => vec![],
+    };
+    for scalar in scalars {
+        replace_placeholders_in_scalar(scalar, replacements);
+    }
+
+    for child in s_expr.children.iter_mut() {
+        replace_placeholders_in_s_expr(child, replacements);
+    }
+}
+
+fn replace_placeholders_in_scalar(scalar: &mut Scalar, replacements: &HashMap<IndexType, Scalar>) {
+    match scalar {
+        Scalar::BoundColumnRef(BoundColumnRef { column }) => {
+            if let Some(replacement) = replacements.get(&column.index) {
+                *scalar = replacement.clone();
+            }
+        }
+        Scalar::ConstantExpr(_) => {}
+        Scalar::AndExpr(AndExpr { left, right, .. })
+        | Scalar::OrExpr(OrExpr { left, right, .. })
+        | Scalar::ComparisonExpr(ComparisonExpr { left, right, .. }) => {
+            replace_placeholders_in_scalar(left, replacements);
+            replace_placeholders_in_scalar(right, replacements);
+        }
+        Scalar::AggregateFunction(AggregateFunction { args, .. })
+        | Scalar::FunctionCall(FunctionCall {
+            arguments: args, ..
+        }) => {
+            for arg in args.iter_mut() {
+                replace_placeholders_in_scalar(arg, replacements);
+            }
+        }
+        Scalar::CastExpr(CastExpr { argument, .. }) => {
+            replace_placeholders_in_scalar(argument, replacements);
+        }
+        Scalar::SubqueryExpr(SubqueryExpr {
+            subquery,
+            child_expr,
+            outer_columns,
+            ..
+        }) => {
+            // The placeholders are no longer outer columns of the subquery once substituted,
+            // otherwise it is taken as a correlated subquery by the decorrelation.
+            outer_columns.retain(|index| !replacements.contains_key(index));
+            replace_placeholders_in_s_expr(subquery, replacements);
+            if let Some(child_expr) = child_expr {
+                replace_placeholders_in_scalar(child_expr, replacements);
+            }
+        }
+    }
+}
@@ -17,8 +17,6 @@ use common_ast::DisplayError;
 use common_exception::ErrorCode;
 use common_exception::Result;
 
-use crate::sql::binder::ColumnBinding;
-use crate::sql::binder::Visibility;
 use crate::sql::plans::AndExpr;
 use crate::sql::plans::BoundColumnRef;
 use crate::sql::plans::CastExpr;
@@ -26,7 +24,6 @@ use crate::sql::plans::ComparisonExpr;
 use crate::sql::plans::FunctionCall;
 use crate::sql::plans::OrExpr;
 use crate::sql::plans::Scalar;
-use crate::sql::plans::ScalarExpr;
 use crate::sql::BindContext;
 
 /// Check validity of scalar expression in a grouping context.
@@ -42,21 +39,7 @@ impl<'a> GroupingChecker<'a> {
     }
 
     pub fn resolve(&mut self, scalar: &Scalar, span: Option<&[Token<'_>]>) -> Result<Scalar> {
-        if let Some(index) = self
-            .bind_context
-            .aggregate_info
-            .group_items_map
-            .get(&format!("{:?}", scalar))
-        {
-            let column = &self.bind_context.aggregate_info.group_items[*index];
-            let column_binding = ColumnBinding {
-                database_name: None,
-                table_name: None,
-                column_name: "group_item".to_string(),
-                index: column.index,
-                data_type: Box::new(column.scalar.data_type()),
-                visibility: Visibility::Visible,
-            };
+        if let Some(column_binding) = self.bind_context.find_by_scalar(scalar) {
             return Ok(BoundColumnRef {
                 column: column_binding,
             }
@@ -64,12 +47,24 @@ impl<'a> GroupingChecker<'a> {
         }
 
         match scalar {
+            // Window functions are evaluated after aggregation
+            Scalar::BoundColumnRef(_)
+                if self
+                    .bind_context
+                    .window_info
+                    .contains_window_function(scalar) =>
+            {
+                Ok(scalar.clone())
+            }
             Scalar::BoundColumnRef(column) => {
                 // If this is a group item, then it should have been replaced with `group_items_map`
                 let mut err_msg = format!(
                     "column \"{}\" must appear in the GROUP BY clause or be used in an aggregate function",
                     &column.column.column_name
                 );
+                if let Some(hint) = column.column.lineage_hint() {
+                    err_msg = format!("{err_msg}, {hint}");
+                }
                 err_msg = span.map_or(err_msg.clone(), |span| span.display_error(err_msg.clone()));
                 Err(ErrorCode::SemanticError(err_msg))
             }
@@ -118,27 +113,8 @@ impl<'a> GroupingChecker<'a> {
                 Ok(scalar.clone())
             }
 
-            Scalar::AggregateFunction(agg) => {
-                if let Some(column) = self
-                    .bind_context
-                    .aggregate_info
-                    .aggregate_functions_map
-                    .get(&agg.display_name)
-                {
-                    let agg_func = &self.bind_context.aggregate_info.aggregate_functions[*column];
-                    let column_binding = ColumnBinding {
-                        database_name: None,
-                        table_name: None,
-                        column_name: agg.display_name.clone(),
-                        index: agg_func.index,
-                        data_type: Box::new(agg_func.scalar.data_type()),
-                        visibility: Visibility::Visible,
-                    };
-                    return Ok(BoundColumnRef {
-                        column: column_binding,
-                    }
-                    .into());
-                }
+            // Aggregate functions have been resolved by `find_by_scalar` above
+            Scalar::AggregateFunction(_) => {
                 Err(ErrorCode::LogicalError("Invalid aggregate function"))
             }
         }
@@ -31,6 +31,7 @@ use common_ast::parser::tokenize_sql;
 use common_ast::Backtrace;
 use common_ast::DisplayError;
 use common_catalog::catalog::CatalogManager;
+use common_datavalues::type_coercion::compare_coercion;
 use common_datavalues::type_coercion::merge_types;
 use common_datavalues::ArrayType;
 use common_datavalues::DataField;
@@ -61,10 +62,14 @@ use super::normalize_identifier;
 use crate::catalogs::CatalogManagerHelper;
 use crate::evaluator::Evaluator;
 use crate::sessions::TableContext;
+use crate::sql::binder::contain_aggregate;
+use crate::sql::binder::wrap_cast;
 use crate::sql::binder::wrap_cast_if_needed;
 use crate::sql::binder::Binder;
+use crate::sql::binder::ExprContext;
 use crate::sql::binder::NameResolutionResult;
 use crate::sql::optimizer::RelExpr;
+use crate::sql::optimizer::SExpr;
 use crate::sql::planner::metadata::optimize_remove_count_args;
 use crate::sql::plans::AggregateFunction;
 use crate::sql::plans::AndExpr;
@@ -73,13 +78,18 @@ use crate::sql::plans::CastExpr;
 use crate::sql::plans::ComparisonExpr;
 use crate::sql::plans::ComparisonOp;
 use crate::sql::plans::ConstantExpr;
+use crate::sql::plans::EvalScalar;
 use crate::sql::plans::FunctionCall;
 use crate::sql::plans::OrExpr;
 use crate::sql::plans::Scalar;
+use crate::sql::plans::ScalarItem;
 use crate::sql::plans::SubqueryExpr;
 use crate::sql::plans::SubqueryType;
 use crate::sql::BindContext;
+use crate::sql::ColumnBinding;
+use crate::sql::ColumnLineage;
 use crate::sql::ScalarExpr;
+use crate::sql::Visibility;
 
 /// A helper for type checking.
 ///
@@ -98,9 +108,9 @@ pub struct TypeChecker<'a> {
 
     aliases: &'a [(String, Scalar)],
 
-    // true if current expr is inside an aggregate function.
+    // Name of the aggregate function if current expr is inside an aggregate function.
     // This is used to check if there is nested aggregate function.
-    in_aggregate_function: bool,
+    in_aggregate_function: Option<String>,
 }
 
 impl<'a> TypeChecker<'a> {
@@ -117,7 +127,7 @@ impl<'a> TypeChecker<'a> {
             name_resolution_ctx,
             metadata,
             aliases,
-            in_aggregate_function: false,
+            in_aggregate_function: None,
         }
     }
 
@@ -160,35 +170,9 @@ impl<'a> TypeChecker<'a> {
             Expr::ColumnRef {
                 database,
                 table,
-                column: ident,
+                column,
                 ..
-            } => {
-                let database = database
-                    .as_ref()
-                    .map(|ident| normalize_identifier(ident, self.name_resolution_ctx).name);
-                let table = table
-                    .as_ref()
-                    .map(|ident| normalize_identifier(ident, self.name_resolution_ctx).name);
-                let column = normalize_identifier(ident, self.name_resolution_ctx).name;
-                let result = self.bind_context.resolve_name(
-                    database.as_deref(),
-                    table.as_deref(),
-                    column.as_str(),
-                    &ident.span,
-                    self.aliases,
-                )?;
-                let (scalar, data_type) = match result {
-                    NameResolutionResult::Column(column) => {
-                        let data_type = *column.data_type.clone();
-                        (BoundColumnRef { column }.into(), data_type)
-                    }
-                    NameResolutionResult::Alias { scalar, .. } => {
-                        (scalar.clone(), scalar.data_type())
-                    }
-                };
-
-                Box::new((scalar, data_type))
-            }
+            } => self.resolve_column_ref(database, table, column)?,
 
             Expr::IsNull {
                 span, expr, not, ..
@@ -211,6 +195,7 @@ impl<'a> TypeChecker<'a> {
                             },
                             args: vec![(args[0]).clone()],
                             params: vec![],
+                            window: None,
                         }],
                         None,
                     )
@@ -291,71 +276,111 @@ impl<'a> TypeChecker<'a> {
                 not,
                 ..
             } => {
+                // Resolve the left operand and each item only once, the type of a placeholder
+                // is inferred from the other side of comparison like `=`.
+                let mut items = Vec::with_capacity(list.len());
+                let (left, left_type) = if matches!(expr.as_ref(), Expr::Placeholder { .. }) {
+                    for item in list.iter() {
+                        items.push(self.resolve(item, None).await?);
+                    }
+                    let item_type = items.first().map(|item| item.1.clone());
+                    *self.resolve(expr, item_type).await?
+                } else {
+                    let box (left, left_type) = self.resolve(expr, None).await?;
+                    for item in list.iter() {
+                        items.push(self.resolve(item, Some(left_type.clone())).await?);
+                    }
+                    (left, left_type)
+                };
+
+                // Check each item can be compared with the left operand, the same as `IN (subquery)`.
+                for (item, resolved) in list.iter().zip(items.iter()) {
+                    Self::resolve_in_compare_type(item.span(), &left_type, &resolved.1)?;
+                }
+
                 if list.len() > 3
                     && list
                         .iter()
                         .all(|e| matches!(e, Expr::Literal { lit, .. } if lit != &Literal::Null))
                 {
-                    let tuple_expr = Expr::Tuple {
-                        span,
-                        exprs: list.clone(),
-                    };
-                    let args = vec![expr.as_ref(), &tuple_expr];
+                    let mut left_type = left_type;
+                    if let Scalar::SubqueryExpr(subquery) = &left {
+                        if subquery.typ == SubqueryType::Scalar && !left_type.is_nullable() {
+                            left_type = NullableType::new_impl(left_type);
+                        }
+                    }
+                    let (args, arg_types): (Vec<_>, Vec<_>) =
+                        items.into_iter().map(|item| *item).unzip();
+                    let box (tuple, tuple_type) = Self::resolve_tuple_call(span, args, arg_types)?;
+                    let box (in_func, in_type) = self
+                        .resolve_scalar_function_call(
+                            span,
+                            "in",
+                            vec![left, tuple],
+                            vec![left_type, tuple_type],
+                            required_type,
+                        )
+                        .await?;
                     if *not {
-                        self.resolve_function(
+                        self.resolve_scalar_function_call(
                             span,
                             "not",
-                            &[&Expr::FunctionCall {
-                                span,
-                                distinct: false,
-                                name: Identifier {
-                                    name: "in".to_string(),
-                                    quote: None,
-                                    span: span[0].clone(),
-                                },
-                                args: args.iter().copied().cloned().collect(),
-                                params: vec![],
-                            }],
+                            vec![in_func],
+                            vec![in_type],
                             None,
                         )
                         .await?
                     } else {
-                        self.resolve_function(span, "in", &args, required_type)
-                            .await?
+                        Box::new((in_func, in_type))
                     }
                 } else {
-                    let mut result = list
-                        .iter()
-                        .map(|e| Expr::BinaryOp {
-                            span,
-                            op: BinaryOperator::Eq,
-                            left: expr.clone(),
-                            right: Box::new(e.clone()),
-                        })
-                        .fold(None, |mut acc, e| {
-                            match acc.as_mut() {
-                                None => acc = Some(e),
-                                Some(acc) => {
-                                    *acc = Expr::BinaryOp {
-                                        span,
-                                        op: BinaryOperator::Or,
-                                        left: Box::new(acc.clone()),
-                                        right: Box::new(e.clone()),
-                                    }
+                    // Rewrite `expr IN (a, b, c)` into `expr = a OR expr = b OR expr = c`.
+                    let mut result: Option<Scalar> = None;
+                    for box (item, _) in items.into_iter() {
+                        let func = FunctionFactory::instance().get(
+                            ComparisonOp::Equal.to_func_name(),
+                            &[&left.data_type(), &item.data_type()],
+                        )?;
+                        let eq: Scalar = ComparisonExpr {
+                            op: ComparisonOp::Equal,
+                            left: Box::new(left.clone()),
+                            right: Box::new(item),
+                            return_type: Box::new(func.return_type()),
+                        }
+                        .into();
+                        result = Some(match result {
+                            None => eq,
+                            Some(acc) => {
+                                let func = FunctionFactory::instance()
+                                    .get("or", &[&acc.data_type(), &eq.data_type()])?;
+                                OrExpr {
+                                    left: Box::new(acc),
+                                    right: Box::new(eq),
+                                    return_type: Box::new(func.return_type()),
                                 }
+                                .into()
                             }
-                            acc
-                        })
-                        .unwrap();
+                        });
+                    }
 
+                    let result = result.ok_or_else(|| {
+                        ErrorCode::SemanticError(
+                            span.display_error("IN list cannot be empty".to_string()),
+                        )
+                    })?;
+                    let result_type = result.data_type();
                     if *not {
-                        result = Expr::UnaryOp {
+                        self.resolve_scalar_function_call(
                             span,
-                            op: UnaryOperator::Not,
-                            expr: Box::new(result),
-                        };
+                            "not",
+                            vec![result],
+                            vec![result_type],
+                            required_type,
+                        )
+                        .await?
+                    } else {
+                        Box::new((result, result_type))
                     }
-                    self.resolve(&result, required_type).await?
                 }
             }
 
@@ -515,6 +540,7 @@ impl<'a> TypeChecker<'a> {
                                 },
                                 args: vec![*operand.clone(), c.clone()],
                                 params: vec![],
+                                window: None,
                             };
                             arguments.push(equal_expr)
                         }
@@ -564,6 +590,10 @@ impl<'a> TypeChecker<'a> {
                 ))
             }
 
+            Expr::FunctionCall {
+                window: Some(_), ..
+            } => self.resolve_window_function(expr)?,
+
             Expr::FunctionCall {
                 span,
                 distinct,
@@ -582,13 +612,7 @@ impl<'a> TypeChecker<'a> {
                 let args: Vec<&Expr> = args.iter().collect();
 
                 if AggregateFunctionFactory::instance().check(func_name) {
-                    if self.in_aggregate_function {
-                        // Reset the state
-                        self.in_aggregate_function = false;
-                        return Err(ErrorCode::SemanticError(expr.span().display_error(
-                            "aggregate function calls cannot be nested".to_string(),
-                        )));
-                    }
+                    self.check_aggregate_function_allowed(expr, func_name)?;
 
                     // Check aggregate function
                     let params = params
@@ -599,12 +623,12 @@ impl<'a> TypeChecker<'a> {
                         })
                         .collect::<Result<Vec<DataValue>>>()?;
 
-                    self.in_aggregate_function = true;
+                    self.in_aggregate_function = Some(func_name.to_string());
                     let mut arguments = vec![];
                     for arg in args.iter() {
                         arguments.push(self.resolve(arg, None).await?);
                     }
-                    self.in_aggregate_function = false;
+                    self.in_aggregate_function = None;
 
                     let data_fields = arguments
                         .iter()
@@ -656,6 +680,8 @@ impl<'a> TypeChecker<'a> {
             }
 
             Expr::CountAll { .. } => {
+                // `COUNT(*)` counts rows, so it's bound without resolving any column.
+                self.check_aggregate_function_allowed(expr, "count")?;
                 let agg_func = AggregateFunctionFactory::instance().get("count", vec![], vec![])?;
 
                 Box::new((
@@ -743,26 +769,30 @@ impl<'a> TypeChecker<'a> {
                             exprs.push(expr.clone());
                             accessores.push(accessor.clone());
                         }
-                        Expr::ColumnRef {
-                            ref database,
-                            ref table,
-                            ref column,
-                            ..
-                        } => {
-                            let box (_, data_type) = self.resolve(&expr, None).await?;
-                            if data_type.data_type_id() != TypeID::Struct {
-                                break;
-                            }
+                        Expr::ColumnRef { .. } => {
+                            let box (scalar, data_type) = self.resolve(&expr, None).await?;
+                            let column = match scalar {
+                                Scalar::BoundColumnRef(BoundColumnRef { column })
+                                    if data_type.data_type_id() == TypeID::Struct =>
+                                {
+                                    column
+                                }
+                                _ => break,
+                            };
                             // if the column is StructColumn, pushdown map access to storage
-                            return self
-                                .resolve_map_access_pushdown(
-                                    data_type,
-                                    accessores,
-                                    database.clone(),
-                                    table.clone(),
-                                    column.clone(),
-                                )
-                                .await;
+                            let path = accessores
+                                .iter()
+                                .rev()
+                                .map(|accessor| match accessor {
+                                    MapAccessor::Bracket { key } => key.clone(),
+                                    MapAccessor::Period { key } | MapAccessor::Colon { key } => {
+                                        Literal::String(key.name.clone())
+                                    }
+                                })
+                                .collect::<Vec<_>>();
+                            let column = self.resolve_struct_field(&column, &path, &span[0])?;
+                            let data_type = *column.data_type.clone();
+                            return Ok(Box::new((BoundColumnRef { column }.into(), data_type)));
                         }
                         _ => break,
                     }
@@ -881,6 +911,8 @@ impl<'a> TypeChecker<'a> {
             }
 
             Expr::Tuple { span, exprs, .. } => self.resolve_tuple(span, exprs).await?,
+
+            Expr::Placeholder { index, .. } => self.resolve_placeholder(*index, required_type)?,
         };
 
         Ok(Box::new(self.post_resolve(&scalar, &data_type)?))
@@ -1010,8 +1042,19 @@ impl<'a> TypeChecker<'a> {
             | BinaryOperator::Eq
             | BinaryOperator::NotEq => {
                 let op = ComparisonOp::try_from(op)?;
-                let box (left, _) = self.resolve(left, None).await?;
-                let box (right, _) = self.resolve(right, None).await?;
+                // The type of a placeholder is inferred from the other side of comparison.
+                let (left, right) = match (left, right) {
+                    (Expr::Placeholder { .. }, _) => {
+                        let box (right, right_type) = self.resolve(right, None).await?;
+                        let box (left, _) = self.resolve(left, Some(right_type)).await?;
+                        (left, right)
+                    }
+                    _ => {
+                        let box (left, left_type) = self.resolve(left, None).await?;
+                        let box (right, _) = self.resolve(right, Some(left_type)).await?;
+                        (left, right)
+                    }
+                };
                 let func = FunctionFactory::instance()
                     .get(op.to_func_name(), &[&left.data_type(), &right.data_type()])?;
                 Ok(Box::new((
@@ -1374,7 +1417,7 @@ impl<'a> TypeChecker<'a> {
 
         // Create new `BindContext` with current `bind_context` as its parent, so we can resolve outer columns.
         let bind_context = BindContext::with_parent(Box::new(self.bind_context.clone()));
-        let (s_expr, output_context) = binder.bind_query(&bind_context, subquery).await?;
+        let (mut s_expr, output_context) = binder.bind_query(&bind_context, subquery).await?;
 
         if (typ == SubqueryType::Scalar || typ == SubqueryType::Any)
             && output_context.columns.len() > 1
@@ -1386,28 +1429,55 @@ impl<'a> TypeChecker<'a> {
         }
 
         let mut data_type = output_context.columns[0].data_type.clone();
-
-        let rel_expr = RelExpr::with_s_expr(&s_expr);
-        let rel_prop = rel_expr.derive_relational_prop()?;
+        let mut output_column = output_context.columns[0].index;
 
         let mut child_scalar = None;
         if let Some(expr) = child_expr {
             assert_eq!(output_context.columns.len(), 1);
-            let box (mut scalar, scalar_data_type) = self.resolve(&expr, None).await?;
-            if scalar_data_type != *data_type {
-                // Make comparison scalar type keep consistent
-                let coercion_type = merge_types(&scalar_data_type, &data_type)?;
-                scalar = wrap_cast_if_needed(scalar, &coercion_type);
-                data_type = Box::new(coercion_type);
-            }
-            child_scalar = Some(Box::new(scalar));
+            let box (scalar, scalar_data_type) = self.resolve(&expr, None).await?;
+            let compare_type =
+                Self::resolve_in_compare_type(expr.span(), &scalar_data_type, &data_type)?;
+            if compare_type != *data_type {
+                // Cast the output column of subquery to the compare type, so both sides
+                // of the comparison have the same type.
+                let output_binding = &output_context.columns[0];
+                let index = self.metadata.write().add_column(
+                    output_binding.column_name.clone(),
+                    compare_type.clone(),
+                    None,
+                    None,
+                );
+                let cast = wrap_cast(
+                    BoundColumnRef {
+                        column: output_binding.clone(),
+                    }
+                    .into(),
+                    &compare_type,
+                );
+                s_expr = SExpr::create_unary(
+                    EvalScalar {
+                        items: vec![ScalarItem {
+                            scalar: cast,
+                            index,
+                        }],
+                    }
+                    .into(),
+                    s_expr,
+                );
+                output_column = index;
+            }
+            child_scalar = Some(Box::new(wrap_cast_if_needed(scalar, &compare_type)));
+            data_type = Box::new(compare_type);
         }
 
+        let rel_expr = RelExpr::with_s_expr(&s_expr);
+        let rel_prop = rel_expr.derive_relational_prop()?;
+
         let subquery_expr = SubqueryExpr {
             subquery: Box::new(s_expr),
             child_expr: child_scalar,
             compare_op,
-            output_column: output_context.columns[0].index,
+            output_column,
             projection_index: None,
             data_type: data_type.clone(),
             allow_multi_rows,
@@ -1418,6 +1488,23 @@ impl<'a> TypeChecker<'a> {
         Ok(Box::new((subquery_expr.into(), *data_type)))
     }
 
+    /// Resolve the common type to compare the left operand of `IN` with the right side.
+    /// This is shared by `expr IN (subquery)` and `expr IN (expr, ...)`, so both forms
+    /// accept the same types.
+    fn resolve_in_compare_type(
+        span: &[Token<'_>],
+        left_type: &DataTypeImpl,
+        right_type: &DataTypeImpl,
+    ) -> Result<DataTypeImpl> {
+        compare_coercion(left_type, right_type).map_err(|_| {
+            ErrorCode::SemanticError(span.display_error(format!(
+                "types {} and {} cannot be matched",
+                left_type.name(),
+                right_type.name()
+            )))
+        })
+    }
+
     fn is_rewritable_scalar_function(func_name: &str) -> bool {
         matches!(
             func_name.to_lowercase().as_str(),
@@ -1575,6 +1662,7 @@ impl<'a> TypeChecker<'a> {
                         },
                         args: vec![(*arg).clone()],
                         params: vec![],
+                        window: None,
                     };
 
                     new_args.push(is_not_null_expr);
@@ -1638,6 +1726,121 @@ impl<'a> TypeChecker<'a> {
         )))
     }
 
+    /// Resolve placeholder of prepared statement, such as `$1`.
+    ///
+    /// A placeholder is bound to a column once its type can be inferred from `required_type`,
+    /// otherwise it's resolved as `NULL` and will be reported by `Binder` if it's never typed.
+    fn resolve_placeholder(
+        &self,
+        index: usize,
+        required_type: Option<DataTypeImpl>,
+    ) -> Result<Box<(Scalar, DataTypeImpl)>> {
+        if index == 0 {
+            return Err(ErrorCode::SemanticError(
+                "Placeholder index must start from $1".to_string(),
+            ));
+        }
+        // A placeholder compared with another untyped placeholder is still untyped.
+        let required_type = required_type.filter(|ty| ty.data_type_id() != TypeID::Null);
+        let column_index = self.metadata.write().add_placeholder(index, required_type);
+        match column_index {
+            Some(column_index) => {
+                let data_type = self
+                    .metadata
+                    .read()
+                    .column(column_index)
+                    .data_type()
+                    .clone();
+                let column = ColumnBinding {
+                    database_name: None,
+                    table_name: None,
+                    column_name: format!("${index}"),
+                    index: column_index,
+                    data_type: Box::new(data_type.clone()),
+                    visibility: Visibility::Visible,
+                    lineage: None,
+                };
+                Ok(Box::new((BoundColumnRef { column }.into(), data_type)))
+            }
+            None => {
+                let data_type = NullType::new_impl();
+                Ok(Box::new((
+                    ConstantExpr {
+                        value: DataValue::Null,
+                        data_type: Box::new(data_type.clone()),
+                    }
+                    .into(),
+                    data_type,
+                )))
+            }
+        }
+    }
+
+    /// Check that an aggregate function call is allowed in the current context, i.e. it's
+    /// neither in WHERE or GROUP BY nor nested inside another aggregate function call.
+    fn check_aggregate_function_allowed(&mut self, expr: &Expr<'_>, func_name: &str) -> Result<()> {
+        match self.bind_context.expr_context {
+            ExprContext::WhereClause => {
+                return Err(ErrorCode::SemanticError(expr.span().display_error(
+                    format!("aggregate function {func_name} is not allowed in WHERE, use HAVING to filter on aggregated values"),
+                )));
+            }
+            ExprContext::GroupClause => {
+                return Err(ErrorCode::SemanticError(expr.span().display_error(
+                    format!("aggregate function {func_name} is not allowed in GROUP BY"),
+                )));
+            }
+            _ => {}
+        }
+
+        if let Some(outer_func_name) = self.in_aggregate_function.take() {
+            return Err(ErrorCode::SemanticError(expr.span().display_error(
+                format!("aggregate function calls cannot be nested, {func_name} is called inside {outer_func_name}"),
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Resolve a window function call to the output column of it. The window functions
+    /// have been registered in `WindowInfo` before binding the select list.
+    fn resolve_window_function(&mut self, expr: &Expr<'_>) -> Result<Box<(Scalar, DataTypeImpl)>> {
+        if self.in_aggregate_function.is_some() {
+            // Reset the state
+            self.in_aggregate_function = None;
+            return Err(ErrorCode::SemanticError(expr.span().display_error(
+                "aggregate function calls cannot contain window function calls".to_string(),
+            )));
+        }
+
+        let display_name = format!("{:#}", expr);
+        let window_info = &self.bind_context.window_info;
+        let window_function = window_info
+            .window_functions_map
+            .get(&display_name)
+            .map(|index| &window_info.window_scalar_exprs[*index])
+            .ok_or_else(|| {
+                ErrorCode::SemanticError(
+                    expr.span()
+                        .display_error("window functions are not allowed here".to_string()),
+                )
+            })?;
+
+        let column = ColumnBinding {
+            database_name: None,
+            table_name: None,
+            column_name: display_name.clone(),
+            index: window_function.index,
+            data_type: window_function.return_type.clone(),
+            visibility: Visibility::Visible,
+            lineage: Some(ColumnLineage::Expression(display_name)),
+        };
+        Ok(Box::new((
+            BoundColumnRef { column }.into(),
+            *window_function.return_type.clone(),
+        )))
+    }
+
     /// Resolve literal values.
     pub fn resolve_literal(
         &self,
@@ -1714,6 +1917,15 @@ impl<'a> TypeChecker<'a> {
             args.push(arg);
             arg_types.push(data_type);
         }
+        Self::resolve_tuple_call(span, args, arg_types)
+    }
+
+    /// Build the `tuple` function call of resolved arguments.
+    fn resolve_tuple_call(
+        span: &[Token<'_>],
+        args: Vec<Scalar>,
+        arg_types: Vec<DataTypeImpl>,
+    ) -> Result<Box<(Scalar, DataTypeImpl)>> {
         let arg_types_ref: Vec<&DataTypeImpl> = arg_types.iter().collect();
         let tuple_func = TupleFunction::try_create_func("", &arg_types_ref)
             .map_err(|e| ErrorCode::SemanticError(span.display_error(e.message())))?;
@@ -1777,23 +1989,152 @@ impl<'a> TypeChecker<'a> {
         }
     }
 
-    #[async_recursion::async_recursion]
-    async fn resolve_map_access_pushdown(
-        &mut self,
-        data_type: DataTypeImpl,
-        mut accessores: Vec<MapAccessor<'async_recursion>>,
-        database: Option<Identifier<'async_recursion>>,
-        table: Option<Identifier<'async_recursion>>,
-        column: Identifier<'async_recursion>,
+    /// Resolve a column reference, which could be qualified by table name and database name,
+    /// or followed by a field path of a struct column. For example, `a.b.c` could refer to:
+    ///
+    ///   - field `c` of column `b` in table `a`
+    ///   - field `b.c` of column `a`
+    ///   - column `c` in table `b` of database `a`
+    ///
+    /// All the interpretations are tried in the order above, and an error will be raised if
+    /// more than one of them can be resolved.
+    fn resolve_column_ref(
+        &self,
+        database: &Option<Identifier<'_>>,
+        table: &Option<Identifier<'_>>,
+        column: &Identifier<'_>,
     ) -> Result<Box<(Scalar, DataTypeImpl)>> {
-        let mut names = Vec::new();
-        let column_name = normalize_identifier(&column, self.name_resolution_ctx).name;
-        names.push(column_name);
-        let mut data_types = Vec::new();
-        data_types.push(data_type.clone());
-
-        while !accessores.is_empty() {
-            let data_type = data_types.pop().unwrap();
+        let idents = database
+            .iter()
+            .chain(table.iter())
+            .chain(std::iter::once(column))
+            .collect::<Vec<_>>();
+        let names = idents
+            .iter()
+            .map(|ident| normalize_identifier(ident, self.name_resolution_ctx).name)
+            .collect::<Vec<_>>();
+
+        // (database, table, column, field path)
+        let interpretations = match names.as_slice() {
+            [column] => vec![(None, None, column, &names[1..])],
+            [table, column] => vec![
+                (None, Some(table), column, &names[2..]),
+                (None, None, table, &names[1..]),
+            ],
+            [database, table, column] => vec![
+                (None, Some(database), table, &names[2..]),
+                (None, None, database, &names[1..]),
+                (Some(database), Some(table), column, &names[3..]),
+            ],
+            _ => unreachable!(),
+        };
+
+        let span = &column.span;
+        let mut resolved = vec![];
+        // The error of accessing a field of an existing column is more relevant than
+        // the error of resolving the name as a column.
+        let mut field_error = None;
+        let mut column_error = None;
+        for (database, table, column, path) in interpretations {
+            let result = self.bind_context.resolve_name(
+                database.map(|name| name.as_str()),
+                table.map(|name| name.as_str()),
+                column,
+                span,
+                if path.is_empty() { self.aliases } else { &[] },
+            );
+            let result = match result {
+                Ok(NameResolutionResult::Column(binding)) if !path.is_empty() => {
+                    let path = path
+                        .iter()
+                        .map(|name| Literal::String(name.clone()))
+                        .collect::<Vec<_>>();
+                    match self.resolve_struct_field(&binding, &path, &idents[0].span) {
+                        Ok(binding) => Ok(NameResolutionResult::Column(binding)),
+                        Err(e) => {
+                            field_error.get_or_insert(e);
+                            continue;
+                        }
+                    }
+                }
+                result => result,
+            };
+            match result {
+                Ok(result) => {
+                    let qualified_column = database
+                        .into_iter()
+                        .chain(table)
+                        .chain(std::iter::once(column))
+                        .map(|name| name.as_str())
+                        .collect::<Vec<_>>()
+                        .join(".");
+                    let interpretation = if path.is_empty() {
+                        format!("column \"{qualified_column}\"")
+                    } else {
+                        format!(
+                            "field \"{}\" of column \"{qualified_column}\"",
+                            path.join(".")
+                        )
+                    };
+                    resolved.push((interpretation, result));
+                }
+                Err(e) if path.is_empty() => column_error = Some(e),
+                Err(_) => {}
+            }
+        }
+
+        if resolved.len() > 1 {
+            let interpretations = resolved
+                .iter()
+                .map(|(interpretation, _)| interpretation.as_str())
+                .collect::<Vec<_>>()
+                .join(", ");
+            return Err(ErrorCode::SemanticError(span.display_error(format!(
+                "column reference \"{}\" is ambiguous, it could refer to {}",
+                names.join("."),
+                interpretations
+            ))));
+        }
+
+        match resolved.pop() {
+            Some((_, NameResolutionResult::Column(column))) => {
+                let data_type = *column.data_type.clone();
+                Ok(Box::new((BoundColumnRef { column }.into(), data_type)))
+            }
+            Some((_, NameResolutionResult::Alias { alias, scalar })) => {
+                if let Some(func_name) = &self.in_aggregate_function {
+                    if contain_aggregate(&scalar)? {
+                        return Err(ErrorCode::SemanticError(span.display_error(format!(
+                            "aggregate function {func_name} cannot reference \"{alias}\", which is an alias of an aggregate function"
+                        ))));
+                    }
+                }
+                let data_type = scalar.data_type();
+                Ok(Box::new((scalar, data_type)))
+            }
+            None => Err(field_error
+                .or(column_error)
+                .unwrap_or_else(|| ErrorCode::LogicalError("Invalid column reference"))),
+        }
+    }
+
+    /// Resolve a field of struct column to the inner column, which is read from storage
+    /// directly. For example, field `b.c` of column `a` is resolved to inner column `a:b:c`.
+    fn resolve_struct_field(
+        &self,
+        column: &ColumnBinding,
+        path: &[Literal],
+        span: &Token<'_>,
+    ) -> Result<ColumnBinding> {
+        let mut names = vec![column.column_name.clone()];
+        let mut data_type = *column.data_type.clone();
+        for key in path.iter() {
+            if data_type.data_type_id() != TypeID::Struct {
+                return Err(ErrorCode::SemanticError(span.display_error(format!(
+                    "column \"{}\" is not a struct",
+                    names.join(".")
+                ))));
+            }
             let struct_type: StructType = data_type.try_into()?;
             let inner_types = struct_type.types();
             let inner_names = match struct_type.names() {
@@ -1803,35 +2144,19 @@ impl<'a> TypeChecker<'a> {
                     .collect::<Vec<_>>(),
             };
 
-            let accessor = accessores.pop().unwrap();
-            let accessor_lit = match accessor {
-                MapAccessor::Bracket { key } => key.clone(),
-                MapAccessor::Period { key } | MapAccessor::Colon { key } => {
-                    Literal::String(key.name.clone())
-                }
-            };
-
-            match accessor_lit {
+            let idx = match key {
                 Literal::Integer(idx) => {
-                    if idx as usize >= inner_types.len() {
+                    if *idx as usize >= inner_types.len() {
                         return Err(ErrorCode::SemanticError(format!(
                             "tuple index {} is out of bounds for length {}",
                             idx,
                             inner_types.len()
                         )));
                     }
-                    let inner_name = inner_names.get(idx as usize).unwrap();
-                    let inner_type = inner_types.get(idx as usize).unwrap();
-                    names.push(inner_name.clone());
-                    data_types.push(inner_type.clone());
+                    *idx as usize
                 }
-                Literal::String(name) => match inner_names.iter().position(|k| k == &name) {
-                    Some(idx) => {
-                        let inner_name = inner_names.get(idx).unwrap();
-                        let inner_type = inner_types.get(idx).unwrap();
-                        names.push(inner_name.clone());
-                        data_types.push(inner_type.clone());
-                    }
+                Literal::String(name) => match inner_names.iter().position(|k| k == name) {
+                    Some(idx) => idx,
                     None => {
                         return Err(ErrorCode::SemanticError(format!(
                             "tuple name `{}` is not exist",
@@ -1839,33 +2164,29 @@ impl<'a> TypeChecker<'a> {
                         )));
                     }
                 },
-                _ => unreachable!(),
-            }
+                _ => {
+                    return Err(ErrorCode::SemanticError(
+                        span.display_error(format!("invalid field {} of struct", key)),
+                    ));
+                }
+            };
+            names.push(inner_names[idx].clone());
+            data_type = inner_types[idx].clone();
         }
 
-        let database = database
-            .as_ref()
-            .map(|ident| normalize_identifier(ident, self.name_resolution_ctx).name);
-        let table = table
-            .as_ref()
-            .map(|ident| normalize_identifier(ident, self.name_resolution_ctx).name);
-        let inner_column_name = names.join(":");
-
-        let result = self.bind_context.resolve_name(
-            database.as_deref(),
-            table.as_deref(),
-            inner_column_name.as_str(),
-            &column.span,
-            self.aliases,
-        )?;
-        let (scalar, data_type) = match result {
-            NameResolutionResult::Column(column) => {
-                let data_type = *column.data_type.clone();
-                (BoundColumnRef { column }.into(), data_type)
-            }
-            NameResolutionResult::Alias { scalar, .. } => (scalar.clone(), scalar.data_type()),
-        };
-        Ok(Box::new((scalar, data_type)))
+        // Inner columns are qualified by the same names as the struct column
+        match self.bind_context.resolve_name(
+            column.database_name.as_deref(),
+            column.table_name.as_deref(),
+            &names.join(":"),
+            span,
+            &[],
+        )? {
+            NameResolutionResult::Column(column) => Ok(column),
+            NameResolutionResult::Alias { .. } => Err(ErrorCode::LogicalError(
+                "Inner column of struct can't be an alias",
+            )),
+        }
     }
 
     #[allow(clippy::only_used_in_recursion)]
@@ -2045,6 +2366,7 @@ impl<'a> TypeChecker<'a> {
                     name,
                     args,
                     params,
+                    window,
                 } => Ok(Expr::FunctionCall {
                     span,
                     distinct: *distinct,
@@ -2054,6 +2376,7 @@ impl<'a> TypeChecker<'a> {
                         .map(|arg| self.clone_expr_with_replacement(arg, replacement_fn))
                         .collect::<Result<Vec<Expr>>>()?,
                     params: params.clone(),
+                    window: window.clone(),
                 }),
                 Expr::Case {
                     span,
@@ -66,6 +66,7 @@ table_cache_block_meta_count = 102400
 table_memory_cache_mb_size = 256
 table_disk_cache_root = "_cache"
 table_disk_cache_mb_size = 1024
+spill_dir = "./.databend/spill"
 management_mode = false
 jwt_key_file = ""
 async_insert_max_data_size = 10000
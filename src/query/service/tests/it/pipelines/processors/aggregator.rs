@@ -0,0 +1,552 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_base::base::tokio;
+use common_datablocks::assert_blocks_sorted_eq;
+use common_datablocks::pretty_format_blocks;
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_functions::aggregates::AggregateFunctionFactory;
+use databend_query::pipelines::processors::AggregatorParams;
+use databend_query::pipelines::processors::AggregatorSpiller;
+use databend_query::pipelines::processors::AggregatorTransformParams;
+use databend_query::pipelines::processors::TransformAggregator;
+use databend_query::sessions::QueryContext;
+use databend_query::sessions::TableContext;
+use futures::TryStreamExt;
+
+use crate::pipelines::processors::aggregator_state_version::run_transform;
+use crate::storages::fuse::table_test_fixture::*;
+
+#[tokio::test]
+async fn test_partial_final_aggregator_with_group_keys() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let db = fixture.default_db_name();
+    let ctx = fixture.ctx();
+
+    let qry = format!(
+        "create table {}.agg_mem (a tinyint unsigned, s varchar) engine = Memory",
+        db
+    );
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    let qry = format!(
+        "insert into {}.agg_mem values (1, 'x'), (1, 'y'), (2, 'x'), (1, 'x'), (2, 'x'), (3, 'z')",
+        db
+    );
+    execute_command(ctx.clone(), qry.as_str()).await?;
+
+    // Keys fit in a u8.
+    {
+        let expected = vec![
+            "+---+---+----+",
+            "| a | c | sa |",
+            "+---+---+----+",
+            "| 1 | 3 | 3  |",
+            "| 2 | 2 | 4  |",
+            "| 3 | 1 | 3  |",
+            "+---+---+----+",
+        ];
+        let qry = format!(
+            "select a, count(*) as c, sum(a) as sa from {}.agg_mem group by a",
+            db
+        );
+        expects_ok(
+            "group_by_u8",
+            execute_query(ctx.clone(), &qry).await,
+            expected,
+        )
+        .await?;
+    }
+
+    // Keys are serialized.
+    {
+        let expected = vec![
+            "+---+---+----+",
+            "| s | c | sa |",
+            "+---+---+----+",
+            "| x | 4 | 6  |",
+            "| y | 1 | 1  |",
+            "| z | 1 | 3  |",
+            "+---+---+----+",
+        ];
+        let qry = format!(
+            "select s, count(*) as c, sum(a) as sa from {}.agg_mem group by s",
+            db
+        );
+        expects_ok(
+            "group_by_string",
+            execute_query(ctx.clone(), &qry).await,
+            expected,
+        )
+        .await?;
+    }
+
+    {
+        let expected = vec![
+            "+---+---+---+----+",
+            "| a | s | c | sa |",
+            "+---+---+---+----+",
+            "| 1 | x | 2 | 2  |",
+            "| 1 | y | 1 | 1  |",
+            "| 2 | x | 2 | 4  |",
+            "| 3 | z | 1 | 3  |",
+            "+---+---+---+----+",
+        ];
+        let qry = format!(
+            "select a, s, count(*) as c, sum(a) as sa from {}.agg_mem group by a, s",
+            db
+        );
+        expects_ok(
+            "group_by_u8_and_string",
+            execute_query(ctx.clone(), &qry).await,
+            expected,
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_partial_aggregator_generates_bounded_blocks() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let ctx = fixture.ctx();
+
+    // Each partial aggregator emits its groups in blocks of at most 2 rows.
+    execute_command(ctx.clone(), "set max_block_size = 2").await?;
+
+    {
+        let expected = vec![
+            "+---+---+----+",
+            "| k | c | s  |",
+            "+---+---+----+",
+            "| 0 | 4 | 30 |",
+            "| 1 | 4 | 34 |",
+            "| 2 | 4 | 38 |",
+            "| 3 | 4 | 42 |",
+            "| 4 | 4 | 46 |",
+            "+---+---+----+",
+        ];
+        let qry =
+            "select number % 5 as k, count(*) as c, sum(number) as s from numbers(20) group by k";
+        expects_ok(
+            "with_aggregates",
+            execute_query(ctx.clone(), qry).await,
+            expected,
+        )
+        .await?;
+    }
+
+    {
+        let expected = vec![
+            "+---+", "| k |", "+---+", "| 0 |", "| 1 |", "| 2 |", "| 3 |", "| 4 |", "+---+",
+        ];
+        let qry = "select number % 5 as k from numbers(20) group by k";
+        expects_ok(
+            "without_aggregates",
+            execute_query(ctx.clone(), qry).await,
+            expected,
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_partial_aggregator_block_size() -> Result<()> {
+    let (_guard, ctx) = crate::tests::create_query_context().await?;
+    ctx.get_settings()
+        .set_settings("max_block_size".to_string(), "2".to_string(), false)?;
+
+    // select k, count() from t group by k
+    let input_schema = DataSchemaRefExt::create(vec![DataField::new("k", u8::to_data_type())]);
+    let partial_schema = DataSchemaRefExt::create(vec![
+        DataField::new("count()", Vu8::to_data_type()),
+        DataField::new("_group_by_key", u8::to_data_type()),
+        DataField::new("_group_by_bucket", i64::to_data_type()),
+    ]);
+    let keys_schema = DataSchemaRefExt::create(vec![
+        DataField::new("_group_by_key", u8::to_data_type()),
+        DataField::new("_group_by_bucket", i64::to_data_type()),
+    ]);
+    let count = AggregateFunctionFactory::instance().get("count", vec![], vec![])?;
+
+    for (case_name, params) in [
+        (
+            "with_aggregates",
+            AggregatorParams::try_create(
+                partial_schema,
+                input_schema.clone(),
+                &[0],
+                &[count],
+                &["count()".to_string()],
+                &[vec![]],
+                "auto",
+                0,
+            )?,
+        ),
+        (
+            "without_aggregates",
+            AggregatorParams::try_create(
+                keys_schema,
+                input_schema.clone(),
+                &[0],
+                &[],
+                &[],
+                &[],
+                "auto",
+                0,
+            )?,
+        ),
+    ] {
+        // 5 groups are generated by bucket in blocks of at most 2 rows, 0, 52, 117 and 248
+        // go to the bucket 0 of the two-level hash table, 3 goes to the bucket 11.
+        let block = DataBlock::create(input_schema.clone(), vec![Series::from_data(vec![
+            0u8, 52, 3, 0, 117, 248, 52,
+        ])]);
+        let blocks = run_partial_aggregator(&ctx, &params, vec![block])?;
+        let rows = blocks
+            .iter()
+            .map(|block| block.num_rows())
+            .collect::<Vec<_>>();
+        assert_eq!(rows, vec![2, 2, 1], "case name {}", case_name);
+        let buckets = blocks
+            .iter()
+            .map(|block| block.column(block.num_columns() - 1).get_i64(0))
+            .collect::<Result<Vec<_>>>()?;
+        assert_eq!(buckets, vec![0, 0, 11], "case name {}", case_name);
+
+        // An empty hash table generates no block.
+        let block = DataBlock::empty_with_schema(input_schema.clone());
+        let blocks = run_partial_aggregator(&ctx, &params, vec![block])?;
+        assert!(blocks.is_empty(), "case name {}", case_name);
+    }
+
+    Ok(())
+}
+
+fn run_partial_aggregator(
+    ctx: &Arc<QueryContext>,
+    params: &Arc<AggregatorParams>,
+    blocks: Vec<DataBlock>,
+) -> Result<Vec<DataBlock>> {
+    run_transform(blocks, |input, output| {
+        TransformAggregator::try_create_partial(
+            input.clone(),
+            output.clone(),
+            AggregatorTransformParams::try_create(input, output, params)?,
+            ctx.clone(),
+        )
+    })
+}
+
+#[tokio::test]
+async fn test_partial_aggregator_spills_states() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let ctx = fixture.ctx();
+
+    // Spill the hash table after each consumed block, the partial aggregator merges the
+    // same keys from the spilled blocks.
+    execute_command(ctx.clone(), "set max_block_size = 3").await?;
+    execute_command(ctx.clone(), "set group_by_spill_threshold_bytes = 1").await?;
+
+    {
+        let expected = vec![
+            "+---+---+----+",
+            "| k | c | s  |",
+            "+---+---+----+",
+            "| 0 | 4 | 30 |",
+            "| 1 | 4 | 34 |",
+            "| 2 | 4 | 38 |",
+            "| 3 | 4 | 42 |",
+            "| 4 | 4 | 46 |",
+            "+---+---+----+",
+        ];
+        let qry =
+            "select number % 5 as k, count(*) as c, sum(number) as s from numbers(20) group by k";
+        expects_ok(
+            "with_aggregates",
+            execute_query(ctx.clone(), qry).await,
+            expected,
+        )
+        .await?;
+    }
+
+    {
+        let expected = vec![
+            "+---+---+",
+            "| k | c |",
+            "+---+---+",
+            "| 0 | 4 |",
+            "| 1 | 4 |",
+            "| 2 | 4 |",
+            "| 3 | 4 |",
+            "| 4 | 4 |",
+            "+---+---+",
+        ];
+        let qry = "select to_varchar(number % 5) as k, count(*) as c from numbers(20) group by k";
+        expects_ok(
+            "with_serialized_keys",
+            execute_query(ctx.clone(), qry).await,
+            expected,
+        )
+        .await?;
+    }
+
+    {
+        let expected = vec![
+            "+---+", "| k |", "+---+", "| 0 |", "| 1 |", "| 2 |", "| 3 |", "| 4 |", "+---+",
+        ];
+        let qry = "select number % 5 as k from numbers(20) group by k";
+        expects_ok(
+            "without_aggregates",
+            execute_query(ctx.clone(), qry).await,
+            expected,
+        )
+        .await?;
+    }
+
+    // The spill files are removed once the queries finish.
+    let spill_dir = ctx.get_config().query.spill_dir;
+    assert_eq!(std::fs::read_dir(spill_dir)?.count(), 0);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_partial_aggregator_merges_spilled_buckets() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let ctx = fixture.ctx();
+    // Both sides cross-check their output against the raw input and panic on mismatch.
+    ctx.get_settings().set_enable_aggregator_self_check(true)?;
+
+    // select k, count() from t group by k
+    let input_schema = DataSchemaRefExt::create(vec![DataField::new("k", u8::to_data_type())]);
+    let partial_schema = DataSchemaRefExt::create(vec![
+        DataField::new("count()", Vu8::to_data_type()),
+        DataField::new("_group_by_key", u8::to_data_type()),
+        DataField::new("_group_by_bucket", i64::to_data_type()),
+    ]);
+    let final_schema = DataSchemaRefExt::create(vec![
+        DataField::new("count()", u64::to_data_type()),
+        DataField::new("k", u8::to_data_type()),
+    ]);
+    let aggregator_params = |output_schema: DataSchemaRef, spill_threshold_bytes: usize| {
+        let count = AggregateFunctionFactory::instance().get("count", vec![], vec![])?;
+        AggregatorParams::try_create(
+            output_schema,
+            input_schema.clone(),
+            &[0],
+            &[count],
+            &["count()".to_string()],
+            &[vec![]],
+            "auto",
+            spill_threshold_bytes,
+        )
+    };
+
+    // The hash table is spilled after each consumed block.
+    let partial_params = aggregator_params(partial_schema, 1)?;
+    let blocks = [vec![1u8, 2, 3], vec![2, 3, 4], vec![3, 4, 5]]
+        .into_iter()
+        .map(|keys| DataBlock::create(input_schema.clone(), vec![Series::from_data(keys)]))
+        .collect();
+    let partial_blocks = run_partial_aggregator(&ctx, &partial_params, blocks)?;
+
+    // Each group is generated once, merged from all the spills.
+    let mut keys = vec![];
+    for block in &partial_blocks {
+        for row in 0..block.num_rows() {
+            keys.push(block.column(1).get_u64(row)?);
+        }
+    }
+    keys.sort_unstable();
+    assert_eq!(keys, vec![1, 2, 3, 4, 5]);
+
+    let final_params = aggregator_params(final_schema, 0)?;
+    let final_blocks = run_transform(partial_blocks, |input, output| {
+        TransformAggregator::try_create_final(
+            input.clone(),
+            output.clone(),
+            AggregatorTransformParams::try_create(input, output, &final_params)?,
+            ctx.clone(),
+        )
+    })?;
+    let expected = vec![
+        "+---------+---+",
+        "| count() | k |",
+        "+---------+---+",
+        "| 1       | 1 |",
+        "| 1       | 5 |",
+        "| 2       | 2 |",
+        "| 2       | 4 |",
+        "| 3       | 3 |",
+        "+---------+---+",
+    ];
+    assert_blocks_sorted_eq(expected, &final_blocks);
+
+    Ok(())
+}
+
+#[test]
+fn test_aggregator_spiller() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let schema = DataSchemaRefExt::create(vec![DataField::new("k", u8::to_data_type())]);
+    let block = |keys: Vec<u8>| DataBlock::create(schema.clone(), vec![Series::from_data(keys)]);
+
+    // Two spills of the hash table, each in the order of buckets.
+    let mut spiller = AggregatorSpiller::create(schema.clone(), dir.path());
+    spiller.spill(1, block(vec![1, 2]))?;
+    spiller.spill(3, block(vec![3]))?;
+    spiller.finish_file()?;
+    spiller.spill(0, block(vec![4]))?;
+    spiller.spill(3, block(vec![5]))?;
+    spiller.spill(3, block(vec![6]))?;
+    assert_eq!(spiller.spilled_blocks(), 5);
+    assert_eq!(spiller.spill_files().len(), 2);
+    assert!(spiller.spill_files().iter().all(|path| path.exists()));
+
+    // The blocks of a bucket are read back from all the files.
+    assert_eq!(
+        spiller.spilled_buckets().into_iter().collect::<Vec<_>>(),
+        vec![0, 1, 3]
+    );
+    assert!(spiller.is_spilled(3));
+    assert!(!spiller.is_spilled(2));
+
+    let expected: Vec<(usize, Vec<Vec<u64>>)> = vec![
+        (0, vec![vec![4]]),
+        (1, vec![vec![1, 2]]),
+        (3, vec![vec![3], vec![5], vec![6]]),
+    ];
+    let mut restored = vec![];
+    for bucket in spiller.spilled_buckets() {
+        let blocks = spiller
+            .restore_bucket(bucket)?
+            .iter()
+            .map(|block| {
+                (0..block.num_rows())
+                    .map(|row| block.column(0).get_u64(row))
+                    .collect::<Result<Vec<_>>>()
+            })
+            .collect::<Result<Vec<_>>>()?;
+        restored.push((bucket, blocks));
+    }
+    assert_eq!(restored, expected);
+
+    let spill_files = spiller
+        .spill_files()
+        .iter()
+        .map(|path| path.to_path_buf())
+        .collect::<Vec<_>>();
+    drop(spiller);
+    assert!(spill_files.iter().all(|path| !path.exists()));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_final_aggregator_generates_bounded_blocks() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let ctx = fixture.ctx();
+
+    // 7 groups are generated by the final aggregator in blocks of at most 3 rows.
+    execute_command(ctx.clone(), "set max_block_size = 3").await?;
+
+    for (case_name, qry) in [
+        (
+            "with_aggregates",
+            "select number % 7 as k, count(*) as c from numbers(70) group by k",
+        ),
+        (
+            "without_aggregates",
+            "select number % 7 as k from numbers(70) group by k",
+        ),
+    ] {
+        let blocks: Vec<DataBlock> = execute_query(ctx.clone(), qry).await?.try_collect().await?;
+
+        let rows = blocks.iter().map(|block| block.num_rows()).sum::<usize>();
+        assert_eq!(rows, 7, "case name {}", case_name);
+        assert!(blocks.len() >= 3, "case name {}", case_name);
+        assert!(
+            blocks.iter().all(|block| block.num_rows() <= 3),
+            "case name {}",
+            case_name
+        );
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_two_level_aggregator_with_hash_methods() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let ctx = fixture.ctx();
+
+    // The buckets of the partial aggregators are merged by several final aggregators.
+    execute_command(ctx.clone(), "set max_threads = 4").await?;
+
+    // The keys fit in a u8, so that every hash method can represent them.
+    for (case_name, qry) in [
+        (
+            "with_aggregates",
+            "select to_uint8(number % 200) as k, count(*) as c, sum(number) as s from numbers(1000) group by k order by k",
+        ),
+        (
+            "without_aggregates",
+            "select to_uint8(number % 200) as k from numbers(1000) group by k order by k",
+        ),
+    ] {
+        // The result of the first hash method with one-level hash tables.
+        let mut expected: Option<String> = None;
+        for method in [
+            "serializer",
+            "keys_u8",
+            "keys_u16",
+            "keys_u32",
+            "keys_u64",
+            "keys_u128",
+            "keys_u256",
+            "keys_u512",
+        ] {
+            let set_method = format!("set group_by_hash_method = '{}'", method);
+            execute_command(ctx.clone(), &set_method).await?;
+
+            // One-level hash tables, then two-level ones from the first key on.
+            for threshold in [10000, 0] {
+                let set_threshold = format!("set group_by_two_level_threshold = {}", threshold);
+                execute_command(ctx.clone(), &set_threshold).await?;
+
+                let blocks: Vec<DataBlock> =
+                    execute_query(ctx.clone(), qry).await?.try_collect().await?;
+                let rows = blocks.iter().map(|block| block.num_rows()).sum::<usize>();
+                assert_eq!(rows, 200, "case name {}, hash method {}", case_name, method);
+
+                let actual = pretty_format_blocks(&blocks)?;
+                let expected = expected.get_or_insert_with(|| actual.clone());
+                assert_eq!(
+                    &actual, expected,
+                    "case name {}, hash method {}, two level threshold {}",
+                    case_name, method, threshold
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
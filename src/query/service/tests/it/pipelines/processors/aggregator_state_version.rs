@@ -0,0 +1,206 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use bytes::BufMut;
+use bytes::BytesMut;
+use common_base::base::tokio;
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_functions::aggregates::AggregateFunctionFactory;
+use databend_query::pipelines::processors::connect;
+use databend_query::pipelines::processors::deserialize_state_version;
+use databend_query::pipelines::processors::port::InputPort;
+use databend_query::pipelines::processors::port::OutputPort;
+use databend_query::pipelines::processors::processor::Event;
+use databend_query::pipelines::processors::processor::ProcessorPtr;
+use databend_query::pipelines::processors::serialize_state_version;
+use databend_query::pipelines::processors::AggregatorParams;
+use databend_query::pipelines::processors::AggregatorTransformParams;
+use databend_query::pipelines::processors::TransformAggregator;
+use databend_query::pipelines::processors::AGGREGATE_STATE_VERSION;
+
+#[test]
+fn test_aggregate_state_version() -> Result<()> {
+    let mut bytes = BytesMut::new();
+    serialize_state_version(&mut bytes);
+    bytes.put_slice(b"state");
+
+    let mut reader = &bytes[..];
+    deserialize_state_version(&mut reader)?;
+    assert_eq!(reader, b"state");
+
+    Ok(())
+}
+
+#[test]
+fn test_aggregate_state_version_mismatch() -> Result<()> {
+    let bytes = [AGGREGATE_STATE_VERSION + 1, 1, 2, 3];
+    let mut reader = &bytes[..];
+    let err = deserialize_state_version(&mut reader).unwrap_err();
+    assert_eq!(err.code(), ErrorCode::bad_bytes_code());
+    assert_eq!(
+        err.message(),
+        format!(
+            "Incompatible aggregate state version {}, expected {}",
+            AGGREGATE_STATE_VERSION + 1,
+            AGGREGATE_STATE_VERSION
+        )
+    );
+
+    let mut reader: &[u8] = &[];
+    let err = deserialize_state_version(&mut reader).unwrap_err();
+    assert_eq!(err.message(), "Missing aggregate state version");
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_final_aggregator_rejects_state_version_mismatch() -> Result<()> {
+    let (_guard, ctx) = crate::tests::create_query_context().await?;
+
+    // select k, sum(v) from t group by k
+    let input_schema = DataSchemaRefExt::create(vec![
+        DataField::new("k", u8::to_data_type()),
+        DataField::new("v", u64::to_data_type()),
+    ]);
+    let partial_schema = DataSchemaRefExt::create(vec![
+        DataField::new("sum(v)", Vu8::to_data_type()),
+        DataField::new("_group_by_key", u8::to_data_type()),
+        DataField::new("_group_by_bucket", i64::to_data_type()),
+    ]);
+    let final_schema = DataSchemaRefExt::create(vec![
+        DataField::new("sum(v)", u64::to_data_type()),
+        DataField::new("k", u8::to_data_type()),
+    ]);
+    let aggregator_params = |output_schema: DataSchemaRef| {
+        let sum = AggregateFunctionFactory::instance()
+            .get("sum", vec![], vec![input_schema.field(1).clone()])?;
+        AggregatorParams::try_create(
+            output_schema,
+            input_schema.clone(),
+            &[0],
+            &[sum],
+            &["sum(v)".to_string()],
+            &[vec![1]],
+            "auto",
+            0,
+        )
+    };
+    let partial_params = aggregator_params(partial_schema)?;
+    let final_params = aggregator_params(final_schema)?;
+
+    let block = DataBlock::create(input_schema.clone(), vec![
+        Series::from_data(vec![1u8, 2, 1]),
+        Series::from_data(vec![10u64, 20, 30]),
+    ]);
+    let partial_blocks = run_transform(vec![block], |input, output| {
+        TransformAggregator::try_create_partial(
+            input.clone(),
+            output.clone(),
+            AggregatorTransformParams::try_create(input, output, &partial_params)?,
+            ctx.clone(),
+        )
+    })?;
+    assert!(!partial_blocks.is_empty());
+
+    // Rewrite the version header of the states, as if they came from a node of another version.
+    let mut mismatched = Vec::with_capacity(partial_blocks.len());
+    for partial in &partial_blocks {
+        let states = (0..partial.num_rows())
+            .map(|row| match partial.column(0).get(row) {
+                DataValue::String(mut bytes) => {
+                    assert_eq!(bytes[0], AGGREGATE_STATE_VERSION);
+                    bytes[0] = AGGREGATE_STATE_VERSION + 1;
+                    Ok(bytes)
+                }
+                other => Err(ErrorCode::LogicalError(format!(
+                    "Expect serialized states, but got {:?}",
+                    other
+                ))),
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let states: Vec<&[u8]> = states.iter().map(|x| x.as_slice()).collect();
+        mismatched.push(DataBlock::create(partial.schema().clone(), vec![
+            Series::from_data(states),
+            partial.column(1).clone(),
+            partial.column(2).clone(),
+        ]));
+    }
+
+    let err = run_transform(mismatched, |input, output| {
+        TransformAggregator::try_create_final(
+            input.clone(),
+            output.clone(),
+            AggregatorTransformParams::try_create(input, output, &final_params)?,
+            ctx.clone(),
+        )
+    })
+    .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::bad_bytes_code());
+    assert_eq!(
+        err.message(),
+        format!(
+            "Incompatible aggregate state version {}, expected {}",
+            AGGREGATE_STATE_VERSION + 1,
+            AGGREGATE_STATE_VERSION
+        )
+    );
+
+    Ok(())
+}
+
+/// Drive a single transform to the end with the given input blocks, and collect its output.
+pub fn run_transform<F>(blocks: Vec<DataBlock>, create: F) -> Result<Vec<DataBlock>>
+where F: FnOnce(Arc<InputPort>, Arc<OutputPort>) -> Result<ProcessorPtr> {
+    let input = InputPort::create();
+    let output = OutputPort::create();
+    let transform = create(input.clone(), output.clone())?;
+
+    let upstream = OutputPort::create();
+    let downstream = InputPort::create();
+    let mut blocks = blocks.into_iter();
+    let mut outputs = vec![];
+    unsafe {
+        connect(&input, &upstream);
+        connect(&downstream, &output);
+
+        downstream.set_need_data();
+        loop {
+            match transform.event()? {
+                Event::NeedData => match blocks.next() {
+                    Some(block) => upstream.push_data(Ok(block)),
+                    None => upstream.finish(),
+                },
+                Event::Sync => transform.process()?,
+                Event::NeedConsume => {
+                    if let Some(block) = downstream.pull_data() {
+                        outputs.push(block?);
+                    }
+                    downstream.set_need_data();
+                }
+                Event::Finished => return Ok(outputs),
+                event => {
+                    return Err(ErrorCode::LogicalError(format!(
+                        "Unexpected event {:?}",
+                        event
+                    )));
+                }
+            }
+        }
+    }
+}
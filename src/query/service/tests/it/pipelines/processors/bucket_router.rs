@@ -0,0 +1,72 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_base::base::tokio;
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use databend_query::pipelines::processors::processor::Event;
+use databend_query::pipelines::processors::Processor;
+use databend_query::pipelines::processors::TransformBucketRouter;
+
+use crate::pipelines::processors::resize::connect_inputs;
+use crate::pipelines::processors::resize::connect_outputs;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_bucket_router_routes_by_bucket() -> Result<()> {
+    let mut router = TransformBucketRouter::create(2, 3);
+    let router_inputs = connect_inputs(router.get_inputs());
+    let router_outputs = connect_outputs(router.get_outputs());
+
+    for output in &router_outputs {
+        output.set_need_data();
+    }
+    assert!(matches!(router.event()?, Event::NeedData));
+
+    // The same bucket always goes to the same output.
+    for (input, bucket) in [(0, 4), (1, 5), (0, 0), (1, 3), (0, 4)] {
+        router_inputs[input].push_data(Ok(bucket_block(bucket)));
+        assert!(matches!(router.event()?, Event::NeedConsume));
+
+        let output = &router_outputs[bucket as usize % 3];
+        let block = output.pull_data().unwrap()?;
+        assert_eq!(block.column(1).get_i64(0)?, bucket);
+        output.set_need_data();
+    }
+
+    // Blocks mixing buckets cannot be routed.
+    router_inputs[1].push_data(Ok(bucket_block(-1)));
+    assert!(router.event().is_err());
+
+    for input in &router_inputs {
+        input.finish();
+    }
+    assert!(matches!(router.event()?, Event::Finished));
+    for output in &router_outputs {
+        assert!(output.is_finished());
+    }
+
+    Ok(())
+}
+
+fn bucket_block(bucket: i64) -> DataBlock {
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("_group_by_key", u8::to_data_type()),
+        DataField::new("_group_by_bucket", i64::to_data_type()),
+    ]);
+    DataBlock::create(schema, vec![
+        Series::from_data(vec![1u8, 2]),
+        Series::from_data(vec![bucket, bucket]),
+    ])
+}
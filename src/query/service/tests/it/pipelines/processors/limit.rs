@@ -0,0 +1,64 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_base::base::tokio;
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use databend_query::pipelines::processors::connect;
+use databend_query::pipelines::processors::port::InputPort;
+use databend_query::pipelines::processors::port::OutputPort;
+use databend_query::pipelines::processors::processor::Event;
+use databend_query::pipelines::processors::TransformLimit;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_limit_finishes_input_once_reached() -> Result<()> {
+    let input = InputPort::create();
+    let output = OutputPort::create();
+    let limit = TransformLimit::try_create(Some(3), 1, input.clone(), output.clone())?;
+
+    let upstream = OutputPort::create();
+    let downstream = InputPort::create();
+    unsafe {
+        connect(&input, &upstream);
+        connect(&downstream, &output);
+    }
+
+    let schema = DataSchemaRefExt::create(vec![DataField::new("a", i32::to_data_type())]);
+    let block = DataBlock::create(schema, vec![Series::from_data(vec![1i32, 2, 3, 4, 5])]);
+
+    unsafe {
+        downstream.set_need_data();
+        assert!(matches!(limit.event()?, Event::NeedData));
+
+        upstream.push_data(Ok(block));
+        assert!(matches!(limit.event()?, Event::Sync));
+        limit.process()?;
+        assert!(matches!(limit.event()?, Event::NeedConsume));
+
+        // The offset is skipped and the block is cut at the limit.
+        let taken = downstream.pull_data().unwrap()?;
+        assert_eq!(taken.num_rows(), 3);
+        assert_eq!(taken.column(0).get(0), DataValue::Int64(2));
+
+        // Nothing more is needed, the upstream is finished without being drained.
+        downstream.set_need_data();
+        assert!(matches!(limit.event()?, Event::Finished));
+    }
+
+    assert!(upstream.is_finished());
+    assert!(downstream.is_finished());
+
+    Ok(())
+}
@@ -12,4 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod aggregator;
+mod aggregator_state_version;
+mod bucket_router;
+mod limit;
 mod resize;
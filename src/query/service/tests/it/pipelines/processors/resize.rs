@@ -42,7 +42,7 @@ async fn test_resize_output_not_need() -> Result<()> {
     Ok(())
 }
 
-fn connect_inputs(inputs: &[Arc<InputPort>]) -> Vec<Arc<OutputPort>> {
+pub fn connect_inputs(inputs: &[Arc<InputPort>]) -> Vec<Arc<OutputPort>> {
     let mut outputs = Vec::with_capacity(inputs.len());
 
     unsafe {
@@ -56,7 +56,7 @@ fn connect_inputs(inputs: &[Arc<InputPort>]) -> Vec<Arc<OutputPort>> {
     outputs
 }
 
-fn connect_outputs(outputs: &[Arc<OutputPort>]) -> Vec<Arc<InputPort>> {
+pub fn connect_outputs(outputs: &[Arc<OutputPort>]) -> Vec<Arc<InputPort>> {
     let mut inputs = Vec::with_capacity(outputs.len());
 
     unsafe {
@@ -12,6 +12,16 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use common_base::base::tokio;
+use common_exception::Result;
+use databend_query::sql::executor::PhysicalPlan;
+use databend_query::sql::executor::PhysicalPlanBuilder;
+use databend_query::sql::plans::Plan;
+use databend_query::sql::Planner;
+
+use crate::tests::create_query_context_with_cluster;
+use crate::tests::ClusterDescriptor;
+
 #[test]
 pub fn test_format_field_name() {
     use databend_query::sql::executor::decode_field_name;
@@ -22,3 +32,47 @@ pub fn test_format_field_name() {
     let (decoded_name, decoded_index) = decode_field_name(field_name.as_str()).unwrap();
     assert!(decoded_name == display_name && decoded_index == index);
 }
+
+#[tokio::test]
+async fn test_limit_with_ties_in_cluster() -> Result<()> {
+    let (_guard, ctx) = create_query_context_with_cluster(
+        ClusterDescriptor::new()
+            .with_node("node1", "127.0.0.1:9091")
+            .with_node("node2", "127.0.0.1:9092")
+            .with_local_id("node1"),
+    )
+    .await?;
+
+    // The join is distributed, so there are exchanges between the limit and the tables.
+    let sql = "SELECT t.number % 3 AS k FROM numbers(10) t, numbers(10) t1 \
+               WHERE t.number = t1.number ORDER BY k FETCH FIRST 1 ROWS WITH TIES";
+    let mut planner = Planner::new(ctx.clone());
+    let (plan, _, _) = planner.plan_sql(sql).await?;
+    let physical_plan = match plan {
+        Plan::Query {
+            s_expr, metadata, ..
+        } => {
+            PhysicalPlanBuilder::new(metadata, ctx.clone())
+                .build(&s_expr)
+                .await?
+        }
+        _ => unreachable!("expect a query plan, but got {}", plan),
+    };
+    assert!(physical_plan.is_distributed_plan());
+
+    fn find_limit(plan: &PhysicalPlan) -> Option<&PhysicalPlan> {
+        match plan {
+            PhysicalPlan::Limit(_) => Some(plan),
+            _ => plan.children().find_map(find_limit),
+        }
+    }
+    match find_limit(&physical_plan) {
+        Some(PhysicalPlan::Limit(limit)) => {
+            assert_eq!(limit.with_ties.len(), 1);
+            assert!(limit.with_ties[0].asc);
+        }
+        _ => unreachable!("expect a limit in {:?}", physical_plan),
+    }
+
+    Ok(())
+}
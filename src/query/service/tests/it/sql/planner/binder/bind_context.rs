@@ -0,0 +1,209 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_ast::parser::token::Token;
+use common_ast::parser::token::TokenKind;
+use common_datavalues::DataTypeImpl;
+use common_datavalues::Int32Type;
+use common_datavalues::StructType;
+use common_exception::Result;
+use databend_query::sql::BindContext;
+use databend_query::sql::ColumnBinding;
+use databend_query::sql::ColumnLineage;
+use databend_query::sql::NameResolutionResult;
+use databend_query::sql::Visibility;
+
+fn column_binding(
+    table: &str,
+    column: &str,
+    index: usize,
+    data_type: DataTypeImpl,
+    visibility: Visibility,
+) -> ColumnBinding {
+    ColumnBinding {
+        database_name: Some("db".to_string()),
+        table_name: Some(table.to_string()),
+        column_name: column.to_string(),
+        index,
+        data_type: Box::new(data_type),
+        visibility,
+        lineage: Some(ColumnLineage::Table {
+            database: "db".to_string(),
+            table: table.to_string(),
+            column: column.to_string(),
+        }),
+    }
+}
+
+// Context of `t(a INT, s TUPLE(x INT)) JOIN t1(a INT) USING(a)`, which contains
+// the hidden inner column `s:x` and the column `t1.a` hidden from unqualified wildcard.
+fn bind_context() -> BindContext {
+    let mut bind_context = BindContext::new();
+    let struct_type =
+        StructType::new_impl(Some(vec!["x".to_string()]), vec![Int32Type::new_impl()]);
+    for column in [
+        column_binding("t", "a", 0, Int32Type::new_impl(), Visibility::Visible),
+        column_binding("t", "s", 1, struct_type, Visibility::Visible),
+        column_binding("t", "s:x", 2, Int32Type::new_impl(), Visibility::InVisible),
+        column_binding(
+            "t1",
+            "a",
+            3,
+            Int32Type::new_impl(),
+            Visibility::UnqualifiedWildcardInVisible,
+        ),
+    ] {
+        bind_context.add_column_binding(column);
+    }
+    bind_context
+}
+
+fn column_names<'a>(columns: impl Iterator<Item = &'a ColumnBinding>) -> Vec<String> {
+    columns
+        .map(|column| match &column.table_name {
+            Some(table_name) => format!("{}.{}", table_name, column.column_name),
+            None => column.column_name.clone(),
+        })
+        .collect()
+}
+
+#[test]
+fn test_hidden_columns_in_wildcard() -> Result<()> {
+    let bind_context = bind_context();
+
+    assert_eq!(
+        column_names(bind_context.wildcard_column_bindings(None, None)),
+        vec!["t.a", "t.s"]
+    );
+    assert_eq!(
+        column_names(bind_context.wildcard_column_bindings(None, Some("t"))),
+        vec!["t.a", "t.s"]
+    );
+    assert_eq!(
+        column_names(bind_context.wildcard_column_bindings(Some("db"), Some("t1"))),
+        vec!["t1.a"]
+    );
+    assert_eq!(
+        column_names(bind_context.wildcard_column_bindings(Some("db1"), Some("t1"))),
+        Vec::<String>::new()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_hidden_columns_in_result_set() -> Result<()> {
+    let bind_context = bind_context();
+
+    assert_eq!(
+        column_names(bind_context.result_column_bindings().iter()),
+        vec!["t.a", "t.s", "t1.a"]
+    );
+    assert_eq!(bind_context.result_columns(), vec![
+        (0, "a".to_string()),
+        (1, "s".to_string()),
+        (3, "a".to_string()),
+    ]);
+    let schema = bind_context.output_schema();
+    let fields = schema
+        .fields()
+        .iter()
+        .map(|field| field.name().as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(fields, vec!["a", "s", "a"]);
+
+    Ok(())
+}
+
+#[test]
+fn test_resolve_hidden_column() -> Result<()> {
+    let bind_context = bind_context();
+    let span = Token {
+        source: "",
+        kind: TokenKind::Ident,
+        span: (0..0),
+    };
+
+    match bind_context.resolve_name(None, Some("t"), "s:x", &span, &[])? {
+        NameResolutionResult::Column(column) => assert_eq!(column.index, 2),
+        NameResolutionResult::Alias { .. } => panic!("expect a column"),
+    }
+    match bind_context.resolve_name(None, None, "s:x", &span, &[])? {
+        NameResolutionResult::Column(column) => assert_eq!(column.index, 2),
+        NameResolutionResult::Alias { .. } => panic!("expect a column"),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_rename_columns_with_hidden_columns() -> Result<()> {
+    let mut bind_context = bind_context();
+    bind_context.rename_columns("u".to_string(), &[
+        "a1".to_string(),
+        "s1".to_string(),
+        "b1".to_string(),
+    ])?;
+
+    // Hidden columns are not renamed by column aliases, but are qualified by the
+    // new table name to be resolvable as before.
+    assert_eq!(
+        column_names(bind_context.all_column_bindings().iter()),
+        vec!["u.a1", "u.s1", "u.s:x", "u.b1"]
+    );
+    assert!(
+        bind_context
+            .rename_columns("u".to_string(), &[
+                "a1".to_string(),
+                "s1".to_string(),
+                "b1".to_string(),
+                "c1".to_string(),
+            ])
+            .is_err()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_lineage_through_aliases() -> Result<()> {
+    let mut bind_context = bind_context();
+    assert_eq!(bind_context.all_column_bindings()[0].lineage_hint(), None);
+
+    // `(...) AS u(a1)` and then `(...) AS v(a2)`
+    bind_context.rename_columns("u".to_string(), &["a1".to_string()])?;
+    bind_context.rename_columns("v".to_string(), &["a2".to_string()])?;
+    let column = &bind_context.all_column_bindings()[0];
+    assert_eq!(
+        column.lineage,
+        Some(ColumnLineage::Table {
+            database: "db".to_string(),
+            table: "t".to_string(),
+            column: "a".to_string(),
+        })
+    );
+    assert_eq!(
+        column.lineage_hint(),
+        Some("\"a2\" is derived from column db.t.a".to_string())
+    );
+
+    let mut column = column.clone();
+    column.lineage = Some(ColumnLineage::Expression("a + 1".to_string()));
+    assert_eq!(
+        column.lineage_hint(),
+        Some("\"a2\" is derived from expression a + 1".to_string())
+    );
+
+    Ok(())
+}
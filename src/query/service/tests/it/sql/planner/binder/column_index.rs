@@ -0,0 +1,137 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashSet;
+
+use common_base::base::tokio;
+use common_exception::Result;
+use databend_query::sql::optimizer::SExpr;
+use databend_query::sql::plans::Aggregate;
+use databend_query::sql::plans::Plan;
+use databend_query::sql::plans::RelOperator;
+use databend_query::sql::Planner;
+
+async fn result_column_indexes(sql: &str) -> Result<Vec<usize>> {
+    let (_guard, ctx) = crate::tests::create_query_context().await?;
+    let mut planner = Planner::new(ctx.clone());
+    let (plan, _, _) = planner.plan_sql(sql).await?;
+    match plan {
+        Plan::Query { bind_context, .. } => Ok(bind_context
+            .result_columns()
+            .into_iter()
+            .map(|(index, _)| index)
+            .collect()),
+        _ => unreachable!("expect a query plan, but got {}", plan),
+    }
+}
+
+fn assert_distinct(indexes: &[usize]) {
+    let distinct: HashSet<&usize> = indexes.iter().collect();
+    assert_eq!(distinct.len(), indexes.len(), "indexes: {:?}", indexes);
+}
+
+#[tokio::test]
+async fn test_column_indexes_of_subqueries_over_same_table() -> Result<()> {
+    let indexes = result_column_indexes(
+        "SELECT * FROM (SELECT number, number + 1 AS n FROM numbers(3)) a, \
+         (SELECT number, number + 1 AS n FROM numbers(3)) b",
+    )
+    .await?;
+    assert_eq!(indexes.len(), 4);
+    assert_distinct(&indexes);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_column_indexes_of_cte_referenced_twice() -> Result<()> {
+    let indexes = result_column_indexes(
+        "WITH c AS (SELECT number, number + 1 AS n FROM numbers(3)) \
+         SELECT * FROM c c1, c c2",
+    )
+    .await?;
+    assert_eq!(indexes.len(), 4);
+    assert_distinct(&indexes);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_column_indexes_of_nested_cte_referenced_twice() -> Result<()> {
+    let indexes = result_column_indexes(
+        "WITH c1 AS (SELECT number FROM numbers(2)), \
+         c2 AS (SELECT a.number AS x, b.number AS y FROM c1 a, c1 b) \
+         SELECT * FROM c2 t1, c2 t2, c1",
+    )
+    .await?;
+    assert_eq!(indexes.len(), 5);
+    assert_distinct(&indexes);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_column_indexes_of_repeated_aggregate() -> Result<()> {
+    let indexes =
+        result_column_indexes("SELECT count(number) AS c1, count(number) AS c2 FROM numbers(3)")
+            .await?;
+    assert_eq!(indexes.len(), 2);
+    assert_eq!(indexes[0], indexes[1]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_column_indexes_of_repeated_group_item() -> Result<()> {
+    let indexes = result_column_indexes(
+        "SELECT number % 2 AS a, number % 2 AS b FROM numbers(3) GROUP BY number % 2",
+    )
+    .await?;
+    assert_eq!(indexes.len(), 2);
+    assert_eq!(indexes[0], indexes[1]);
+    Ok(())
+}
+
+fn find_aggregate(s_expr: &SExpr) -> Option<&Aggregate> {
+    match s_expr.plan() {
+        RelOperator::Aggregate(aggregate) => Some(aggregate),
+        _ => s_expr.children().iter().find_map(find_aggregate),
+    }
+}
+
+#[tokio::test]
+async fn test_aggregate_in_having_and_order_by_bound_once() -> Result<()> {
+    let (_guard, ctx) = crate::tests::create_query_context().await?;
+    let mut planner = Planner::new(ctx.clone());
+    let (plan, _, _) = planner
+        .plan_sql(
+            "SELECT count(number) FROM numbers(3) HAVING count(number) > 1 ORDER BY count(number)",
+        )
+        .await?;
+    match plan {
+        Plan::Query {
+            s_expr,
+            bind_context,
+            ..
+        } => {
+            let aggregate = find_aggregate(&s_expr).unwrap();
+            assert_eq!(aggregate.aggregate_functions.len(), 1);
+
+            let indexes: Vec<usize> = bind_context
+                .result_columns()
+                .into_iter()
+                .map(|(index, _)| index)
+                .collect();
+            assert_eq!(indexes, vec![aggregate.aggregate_functions[0].index]);
+        }
+        _ => unreachable!("expect a query plan, but got {}", plan),
+    }
+    Ok(())
+}
@@ -0,0 +1,50 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_base::base::tokio;
+use common_exception::Result;
+use databend_query::sessions::TableContext;
+use databend_query::sql::Planner;
+
+#[tokio::test]
+async fn test_group_by_alias_shadowing_column_warning() -> Result<()> {
+    let (_guard, ctx) = crate::tests::create_query_context().await?;
+    let mut planner = Planner::new(ctx.clone());
+
+    planner
+        .plan_sql("SELECT number % 2 AS number, count(*) FROM numbers(10) GROUP BY number")
+        .await?;
+    let warnings = ctx.get_warnings();
+    assert_eq!(warnings, vec![
+        "alias \"number\" of expression number % 2 shadows column numbers.number".to_string(),
+        "GROUP BY \"number\" refers to column numbers.number, not the alias of the select list"
+            .to_string(),
+    ]);
+
+    // Aliasing the column with its own name doesn't shadow it, and the warnings of the
+    // previous statement are cleared.
+    planner
+        .plan_sql("SELECT number AS number, count(*) FROM numbers(10) GROUP BY number")
+        .await?;
+    assert!(ctx.get_warnings().is_empty());
+
+    // `SHOW WARNINGS` keeps the warnings of the previous statement.
+    planner
+        .plan_sql("SELECT number % 2 AS number FROM numbers(10) GROUP BY number")
+        .await?;
+    planner.plan_sql("SHOW WARNINGS").await?;
+    assert_eq!(ctx.get_warnings().len(), 2);
+
+    Ok(())
+}
@@ -90,6 +90,7 @@ fn test_format() {
                                 index: col1,
                                 data_type: Box::new(BooleanType::new_impl()),
                                 visibility: Visibility::Visible,
+                                lineage: None,
                             },
                         }
                         .into(),
@@ -112,6 +113,7 @@ fn test_format() {
                         index: col2,
                         data_type: Box::new(BooleanType::new_impl()),
                         visibility: Visibility::Visible,
+                        lineage: None,
                     },
                 }
                 .into(),
@@ -12,5 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod binder;
 mod format;
+mod prepared_statement;
 mod semantic;
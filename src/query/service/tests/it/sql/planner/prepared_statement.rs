@@ -0,0 +1,201 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_base::base::tokio;
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use databend_query::interpreters::InterpreterFactory;
+use databend_query::sessions::QueryContext;
+use databend_query::sql::Planner;
+use databend_query::sql::PreparedStatement;
+use futures::TryStreamExt;
+
+async fn execute(
+    ctx: Arc<QueryContext>,
+    prepared: &PreparedStatement,
+    values: &[DataValue],
+) -> Result<Vec<DataBlock>> {
+    let mut planner = Planner::new(ctx.clone());
+    let (plan, _, _) = planner.plan_prepared(prepared, values)?;
+    let executor = InterpreterFactory::get(ctx.clone(), &plan).await?;
+    let stream = executor.execute(ctx.clone()).await?;
+    stream.try_collect::<Vec<_>>().await
+}
+
+#[tokio::test]
+async fn test_prepared_statement_with_question_marks() -> Result<()> {
+    let (_guard, ctx) = crate::tests::create_query_context().await?;
+    let mut planner = Planner::new(ctx.clone());
+    let prepared = planner
+        .prepare_sql("SELECT number FROM numbers(10) WHERE number > ? AND number < ?")
+        .await?;
+    assert_eq!(prepared.parameter_types(), vec![
+        UInt64Type::new_impl(),
+        UInt64Type::new_impl()
+    ]);
+
+    let result = execute(ctx.clone(), &prepared, &[
+        DataValue::UInt64(3),
+        DataValue::UInt64(6),
+    ])
+    .await?;
+    common_datablocks::assert_blocks_sorted_eq(
+        vec![
+            "+--------+",
+            "| number |",
+            "+--------+",
+            "| 4      |",
+            "| 5      |",
+            "+--------+",
+        ],
+        result.as_slice(),
+    );
+
+    // The same prepared statement is executed with other values.
+    let result = execute(ctx.clone(), &prepared, &[
+        DataValue::Int64(7),
+        DataValue::Int64(9),
+    ])
+    .await?;
+    common_datablocks::assert_blocks_sorted_eq(
+        vec![
+            "+--------+",
+            "| number |",
+            "+--------+",
+            "| 8      |",
+            "+--------+",
+        ],
+        result.as_slice(),
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_prepared_statement_with_numbered_placeholders() -> Result<()> {
+    let (_guard, ctx) = crate::tests::create_query_context().await?;
+    let mut planner = Planner::new(ctx.clone());
+    let prepared = planner
+        .prepare_sql("SELECT number FROM numbers(5) WHERE ($1 IS NULL OR number = $1) AND $2 = 'b'")
+        .await?;
+    assert_eq!(prepared.parameter_types(), vec![
+        UInt64Type::new_impl(),
+        StringType::new_impl()
+    ]);
+
+    let result = execute(ctx.clone(), &prepared, &[
+        DataValue::UInt64(2),
+        DataValue::String(b"b".to_vec()),
+    ])
+    .await?;
+    common_datablocks::assert_blocks_sorted_eq(
+        vec![
+            "+--------+",
+            "| number |",
+            "+--------+",
+            "| 2      |",
+            "+--------+",
+        ],
+        result.as_slice(),
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_prepared_statement_with_placeholders_in_subqueries() -> Result<()> {
+    let (_guard, ctx) = crate::tests::create_query_context().await?;
+    let mut planner = Planner::new(ctx.clone());
+    let prepared = planner
+        .prepare_sql(
+            "SELECT number FROM numbers(5) \
+             WHERE number = (SELECT max(number) FROM numbers(10) WHERE number < ?) \
+             AND EXISTS (SELECT * FROM numbers(3) WHERE number = ?)",
+        )
+        .await?;
+    assert_eq!(prepared.parameter_types(), vec![
+        UInt64Type::new_impl(),
+        UInt64Type::new_impl()
+    ]);
+
+    let result = execute(ctx.clone(), &prepared, &[
+        DataValue::UInt64(3),
+        DataValue::UInt64(1),
+    ])
+    .await?;
+    common_datablocks::assert_blocks_sorted_eq(
+        vec![
+            "+--------+",
+            "| number |",
+            "+--------+",
+            "| 2      |",
+            "+--------+",
+        ],
+        result.as_slice(),
+    );
+
+    let result = execute(ctx.clone(), &prepared, &[
+        DataValue::UInt64(3),
+        DataValue::UInt64(7),
+    ])
+    .await?;
+    assert_eq!(
+        result.iter().map(|block| block.num_rows()).sum::<usize>(),
+        0
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_prepared_statement_with_wrong_number_of_values() -> Result<()> {
+    let (_guard, ctx) = crate::tests::create_query_context().await?;
+    let mut planner = Planner::new(ctx.clone());
+    let prepared = planner
+        .prepare_sql("SELECT number FROM numbers(10) WHERE number = $1")
+        .await?;
+
+    let err = planner
+        .plan_prepared(&prepared, &[DataValue::UInt64(1), DataValue::UInt64(2)])
+        .unwrap_err();
+    assert_eq!(
+        err.message(),
+        "Statement expects 1 parameter ($1 UInt64), but 2 supplied"
+    );
+
+    let err = planner.plan_sql("SELECT $1 + 1").await.unwrap_err();
+    assert_eq!(
+        err.message(),
+        "Could not determine data type of placeholder $1"
+    );
+
+    let err = planner
+        .plan_sql("SELECT number FROM numbers(10) WHERE number = $2")
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err.message(),
+        "Placeholder $1 is not referenced, placeholders must be numbered consecutively from $1"
+    );
+
+    let err = planner
+        .plan_sql("SELECT number FROM numbers(10) WHERE number = ? OR number = $1")
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err.message(),
+        "Placeholders `?` and `$n` cannot be mixed in a statement"
+    );
+    Ok(())
+}
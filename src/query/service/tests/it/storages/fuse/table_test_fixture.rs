@@ -78,6 +78,7 @@ impl TestFixture {
             // use `TempDir` as root path (auto clean)
             root: tmp_dir.path().to_str().unwrap().to_string(),
         });
+        conf.query.spill_dir = tmp_dir.path().join("spill").to_str().unwrap().to_string();
 
         let (_guard, ctx) = crate::tests::create_query_context_with_config(conf, None)
             .await
@@ -95,6 +95,7 @@ async fn test_configs_table() -> Result<()> {
         "| query   | rpc_tls_query_service_domain_name    | localhost                      |             |",
         "| query   | rpc_tls_server_cert                  |                                |             |",
         "| query   | rpc_tls_server_key                   |                                |             |",
+        "| query   | spill_dir                            | ./.databend/spill              |             |",
         "| query   | table_cache_block_meta_count         | 102400                         |             |",
         "| query   | table_cache_enabled                  | false                          |             |",
         "| query   | table_cache_segment_count            | 10240                          |             |",
@@ -229,6 +230,7 @@ async fn test_configs_table_redact() -> Result<()> {
         "| query   | rpc_tls_query_service_domain_name    | localhost                      |             |",
         "| query   | rpc_tls_server_cert                  |                                |             |",
         "| query   | rpc_tls_server_key                   |                                |             |",
+        "| query   | spill_dir                            | ./.databend/spill              |             |",
         "| query   | table_cache_block_meta_count         | 102400                         |             |",
         "| query   | table_cache_enabled                  | false                          |             |",
         "| query   | table_cache_segment_count            | 10240                          |             |",
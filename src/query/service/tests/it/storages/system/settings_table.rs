@@ -36,23 +36,33 @@ async fn test_settings_table() -> Result<()> {
         "+--------------------------------+------------+------------+---------+----------------------------------------------------------------------------------------------------+--------+",
         "| compression                    | None       | None       | SESSION | Format compression, default value: None                                                            | String |",
         "| empty_as_default               | 1          | 1          | SESSION | Format empty_as_default, default value: 1                                                          | UInt64 |",
+        "| enable_aggregator_self_check   | 0          | 0          | SESSION | Cross-check group by results against a reference aggregator, for testing only, default value: 0    | UInt64 |",
         "| enable_async_insert            | 0          | 0          | SESSION | Whether the client open async insert mode, default value: 0                                        | UInt64 |",
         "| enable_cbo                     | 1          | 1          | SESSION | If enable cost based optimization, default value: 1                                                | UInt64 |",
+        "| enable_lateral_column_alias    | 1          | 1          | SESSION | Allow referencing the aliases of preceding items in the same select list, default value: 1         | UInt64 |",
         "| enable_new_processor_framework | 1          | 1          | SESSION | Enable new processor framework if value != 0, default value: 1                                     | UInt64 |",
         "| enable_planner_v2              | 1          | 1          | SESSION | Enable planner v2 by setting this variable to 1, default value: 1                                  | UInt64 |",
         "| field_delimiter                | ,          | ,          | SESSION | Format field delimiter, default value: ,                                                           | String |",
         "| flight_client_timeout          | 60         | 60         | SESSION | Max duration the flight client request is allowed to take in seconds. By default, it is 60 seconds | UInt64 |",
+        "| group_by_spill_threshold_bytes | 0          | 0          | SESSION | Spill partial group by states to disk above this many bytes, 0 disables spilling, default value: 0 | UInt64 |",
+        "| group_by_hash_method           | auto       | auto       | SESSION | Force the hash method of group by, e.g. \"serializer\" or \"keys_u64\", default value: \"auto\"          | String |",
+        "| group_by_fold_repeated_keys    | 0          | 0          | SESSION | Group the rows of each block by key before looking up the hash table, default value: 0             | UInt64 |",
+        "| group_by_first_seen_order      | 0          | 0          | SESSION | Emit group by results in the order their keys are first seen on each node, default value: 0        | UInt64 |",
         "| input_read_buffer_size         | 1048576    | 1048576    | SESSION | The size of buffer in bytes for input with format. By default, it is 1MB.                          | UInt64 |",
         "| group_by_two_level_threshold   | 10000      | 10000      | SESSION | The threshold of keys to open two-level aggregation, default value: 10000                          | UInt64 |",
         "| max_block_size                 | 10000      | 10000      | SESSION | Maximum block size for reading                                                                     | UInt64 |",
         "| max_execute_time               | 0          | 0          | SESSION | The maximum query execution time. it means no limit if the value is zero. default value: 0         | UInt64 |",
+        "| max_recursive_cte_iterations   | 1000       | 1000       | SESSION | The maximum iterations of a recursive CTE before the query fails, default value: 1000              | UInt64 |",
+        "| max_result_rows                | 0          | 0          | SESSION | The maximum rows of the query result. it means no limit if the value is zero. default value: 0     | UInt64 |",
         "| max_threads                    | 2          | 16         | SESSION | The maximum number of threads to execute the request. By default, it is determined automatically.  | UInt64 |",
         "| quote_char                     | '\"'        | '\"'        | SESSION | The quote char for CSV. default value: '\"'.                                                        | String |",
         "| quoted_ident_case_sensitive    | 1          | 1          | SESSION | Case sensitivity of quoted identifiers, default value: 1 (aka case-sensitive)                      | UInt64 |",
         "| record_delimiter               | \"\\n\"       | \"\\n\"       | SESSION | Format record_delimiter, default value: \"\\n\"                                                       | String |",
+        "| result_overflow_mode           | throw      | throw      | SESSION | What to do if the result exceeds max_result_rows, \"throw\" or \"break\", default value: \"throw\"       | String |",
         "| skip_header                    | 0          | 0          | SESSION | Whether to skip the input header, default value: 0                                                 | UInt64 |",
         "| sql_dialect                    | PostgreSQL | PostgreSQL | SESSION | SQL dialect, support \"PostgreSQL\" and \"MySQL\", default value: \"PostgreSQL\"                         | String |",
         "| storage_read_buffer_size       | 1048576    | 1048576    | SESSION | The size of buffer in bytes for buffered reader of dal. By default, it is 1MB.                     | UInt64 |",
+        "| strict_alias_shadowing         | 0          | 0          | SESSION | Fail the query if an alias shadows a column or a CTE shadows a table, default value: 0             | UInt64 |",
         "| timezone                       | UTC        | UTC        | SESSION | Timezone, default value: UTC,                                                                      | String |",
         "| unquoted_ident_case_sensitive  | 0          | 0          | SESSION | Case sensitivity of unquoted identifiers, default value: 0 (aka case-insensitive)                  | UInt64 |",
         "| wait_for_async_insert          | 1          | 1          | SESSION | Whether the client wait for the reply of async insert, default value: 1                            | UInt64 |",
@@ -245,6 +245,66 @@ impl Settings {
                 desc: "The threshold of keys to open two-level aggregation, default value: 10000",
                 possible_values: None,
             },
+            SettingValue {
+                default_value: UserSettingValue::UInt64(0),
+                user_setting: UserSetting::create(
+                    "group_by_spill_threshold_bytes",
+                    UserSettingValue::UInt64(0),
+                ),
+                level: ScopeLevel::Session,
+                desc: "Spill partial group by states to disk above this many bytes, 0 disables spilling, default value: 0",
+                possible_values: None,
+            },
+            SettingValue {
+                default_value: UserSettingValue::String("auto".to_owned()),
+                user_setting: UserSetting::create(
+                    "group_by_hash_method",
+                    UserSettingValue::String("auto".to_owned()),
+                ),
+                level: ScopeLevel::Session,
+                desc: "Force the hash method of group by, e.g. \"serializer\" or \"keys_u64\", default value: \"auto\"",
+                possible_values: Some(vec![
+                    "auto",
+                    "serializer",
+                    "keys_u8",
+                    "keys_u16",
+                    "keys_u32",
+                    "keys_u64",
+                    "keys_u128",
+                    "keys_u256",
+                    "keys_u512",
+                ]),
+            },
+            SettingValue {
+                default_value: UserSettingValue::UInt64(0),
+                user_setting: UserSetting::create(
+                    "enable_aggregator_self_check",
+                    UserSettingValue::UInt64(0),
+                ),
+                level: ScopeLevel::Session,
+                desc: "Cross-check group by results against a reference aggregator, for testing only, default value: 0",
+                possible_values: None,
+            },
+            SettingValue {
+                default_value: UserSettingValue::UInt64(0),
+                user_setting: UserSetting::create(
+                    "group_by_first_seen_order",
+                    UserSettingValue::UInt64(0),
+                ),
+                level: ScopeLevel::Session,
+                desc: "Emit group by results in the order their keys are first seen on each node, default value: 0",
+                possible_values: None,
+            },
+            SettingValue {
+                default_value: UserSettingValue::UInt64(0),
+                user_setting: UserSetting::create(
+                    "group_by_fold_repeated_keys",
+                    UserSettingValue::UInt64(0),
+                ),
+                level: ScopeLevel::Session,
+                desc: "Group the rows of each block by key before looking up the hash table, default value: 0",
+                possible_values: None,
+            },
             SettingValue {
                 default_value: UserSettingValue::UInt64(0),
                 user_setting: UserSetting::create(
@@ -312,6 +372,26 @@ impl Settings {
                 desc: "If enable cost based optimization, default value: 1",
                 possible_values: None,
             },
+            SettingValue {
+                default_value: UserSettingValue::UInt64(1),
+                user_setting: UserSetting::create(
+                    "enable_lateral_column_alias",
+                    UserSettingValue::UInt64(1),
+                ),
+                level: ScopeLevel::Session,
+                desc: "Allow referencing the aliases of preceding items in the same select list, default value: 1",
+                possible_values: None,
+            },
+            SettingValue {
+                default_value: UserSettingValue::UInt64(0),
+                user_setting: UserSetting::create(
+                    "strict_alias_shadowing",
+                    UserSettingValue::UInt64(0),
+                ),
+                level: ScopeLevel::Session,
+                desc: "Fail the query if an alias shadows a column or a CTE shadows a table, default value: 0",
+                possible_values: None,
+            },
             // max_execute_time
             SettingValue {
                 default_value: UserSettingValue::UInt64(0),
@@ -320,6 +400,33 @@ impl Settings {
                 desc: "The maximum query execution time. it means no limit if the value is zero. default value: 0",
                 possible_values: None,
             },
+            SettingValue {
+                default_value: UserSettingValue::UInt64(0),
+                user_setting: UserSetting::create("max_result_rows", UserSettingValue::UInt64(0)),
+                level: ScopeLevel::Session,
+                desc: "The maximum rows of the query result. it means no limit if the value is zero. default value: 0",
+                possible_values: None,
+            },
+            SettingValue {
+                default_value: UserSettingValue::UInt64(1000),
+                user_setting: UserSetting::create(
+                    "max_recursive_cte_iterations",
+                    UserSettingValue::UInt64(1000),
+                ),
+                level: ScopeLevel::Session,
+                desc: "The maximum iterations of a recursive CTE before the query fails, default value: 1000",
+                possible_values: None,
+            },
+            SettingValue {
+                default_value: UserSettingValue::String("throw".to_owned()),
+                user_setting: UserSetting::create(
+                    "result_overflow_mode",
+                    UserSettingValue::String("throw".to_owned()),
+                ),
+                level: ScopeLevel::Session,
+                desc: "What to do if the result exceeds max_result_rows, \"throw\" or \"break\", default value: \"throw\"",
+                possible_values: Some(vec!["throw", "break"]),
+            },
             SettingValue {
                 default_value: UserSettingValue::String("\"".to_owned()),
                 user_setting: UserSetting::create(
@@ -378,6 +485,33 @@ impl Settings {
         self.try_set_u64("max_execute_time", val, false)
     }
 
+    // Get max_result_rows.
+    pub fn get_max_result_rows(&self) -> Result<u64> {
+        self.try_get_u64("max_result_rows")
+    }
+
+    // Set max_result_rows.
+    pub fn set_max_result_rows(&self, val: u64) -> Result<()> {
+        self.try_set_u64("max_result_rows", val, false)
+    }
+
+    // Get max_recursive_cte_iterations.
+    pub fn get_max_recursive_cte_iterations(&self) -> Result<u64> {
+        self.try_get_u64("max_recursive_cte_iterations")
+    }
+
+    // Set max_recursive_cte_iterations.
+    pub fn set_max_recursive_cte_iterations(&self, val: u64) -> Result<()> {
+        self.try_set_u64("max_recursive_cte_iterations", val, false)
+    }
+
+    // Get result_overflow_mode, "throw" or "break".
+    pub fn get_result_overflow_mode(&self) -> Result<String> {
+        let key = "result_overflow_mode";
+        self.check_and_get_setting_value(key)
+            .and_then(|v| v.user_setting.value.as_string())
+    }
+
     // Get flight client timeout.
     pub fn get_flight_client_timeout(&self) -> Result<u64> {
         let key = "flight_client_timeout";
@@ -457,6 +591,61 @@ impl Settings {
         self.try_set_u64(key, val, false)
     }
 
+    // Get the bytes of partial group by states to spill them to disk, 0 means never.
+    pub fn get_group_by_spill_threshold_bytes(&self) -> Result<u64> {
+        let key = "group_by_spill_threshold_bytes";
+        self.try_get_u64(key)
+    }
+
+    // Set the bytes of partial group by states to spill them to disk.
+    pub fn set_group_by_spill_threshold_bytes(&self, val: u64) -> Result<()> {
+        let key = "group_by_spill_threshold_bytes";
+        self.try_set_u64(key, val, false)
+    }
+
+    // Get group by hash method, "auto" lets the planner choose it.
+    pub fn get_group_by_hash_method(&self) -> Result<String> {
+        let key = "group_by_hash_method";
+        self.check_and_get_setting_value(key)
+            .and_then(|v| v.user_setting.value.as_string())
+    }
+
+    pub fn get_enable_aggregator_self_check(&self) -> Result<bool> {
+        static KEY: &str = "enable_aggregator_self_check";
+        let v = self.try_get_u64(KEY)?;
+        Ok(v != 0)
+    }
+
+    pub fn set_enable_aggregator_self_check(&self, val: bool) -> Result<()> {
+        static KEY: &str = "enable_aggregator_self_check";
+        let v = u64::from(val);
+        self.try_set_u64(KEY, v, false)
+    }
+
+    pub fn get_group_by_first_seen_order(&self) -> Result<bool> {
+        static KEY: &str = "group_by_first_seen_order";
+        let v = self.try_get_u64(KEY)?;
+        Ok(v != 0)
+    }
+
+    pub fn set_group_by_first_seen_order(&self, val: bool) -> Result<()> {
+        static KEY: &str = "group_by_first_seen_order";
+        let v = u64::from(val);
+        self.try_set_u64(KEY, v, false)
+    }
+
+    pub fn get_group_by_fold_repeated_keys(&self) -> Result<bool> {
+        static KEY: &str = "group_by_fold_repeated_keys";
+        let v = self.try_get_u64(KEY)?;
+        Ok(v != 0)
+    }
+
+    pub fn set_group_by_fold_repeated_keys(&self, val: bool) -> Result<()> {
+        static KEY: &str = "group_by_fold_repeated_keys";
+        let v = u64::from(val);
+        self.try_set_u64(KEY, v, false)
+    }
+
     pub fn get_enable_async_insert(&self) -> Result<u64> {
         let key = "enable_async_insert";
         self.try_get_u64(key)
@@ -523,6 +712,30 @@ impl Settings {
         self.try_set_u64(KEY, v, false)
     }
 
+    pub fn get_enable_lateral_column_alias(&self) -> Result<bool> {
+        static KEY: &str = "enable_lateral_column_alias";
+        let v = self.try_get_u64(KEY)?;
+        Ok(v != 0)
+    }
+
+    pub fn set_enable_lateral_column_alias(&self, val: bool) -> Result<()> {
+        static KEY: &str = "enable_lateral_column_alias";
+        let v = u64::from(val);
+        self.try_set_u64(KEY, v, false)
+    }
+
+    pub fn get_strict_alias_shadowing(&self) -> Result<bool> {
+        static KEY: &str = "strict_alias_shadowing";
+        let v = self.try_get_u64(KEY)?;
+        Ok(v != 0)
+    }
+
+    pub fn set_strict_alias_shadowing(&self, val: bool) -> Result<()> {
+        static KEY: &str = "strict_alias_shadowing";
+        let v = u64::from(val);
+        self.try_set_u64(KEY, v, false)
+    }
+
     pub fn get_sql_dialect(&self) -> Result<Dialect> {
         let key = "sql_dialect";
         self.check_and_get_setting_value(key)
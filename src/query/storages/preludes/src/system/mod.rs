@@ -33,6 +33,7 @@ mod tables_table;
 mod tracing_table;
 mod tracing_table_stream;
 mod users_table;
+mod warnings_table;
 
 pub use clustering_history_table::ClusteringHistoryLogElement;
 pub use clustering_history_table::ClusteringHistoryQueue;
@@ -65,3 +66,4 @@ pub use tables_table::TablesTableWithoutHistory;
 pub use tracing_table::TracingTable;
 pub use tracing_table_stream::TracingTableStream;
 pub use users_table::UsersTable;
+pub use warnings_table::WarningsTable;
@@ -0,0 +1,75 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_meta_app::schema::TableIdent;
+use common_meta_app::schema::TableInfo;
+use common_meta_app::schema::TableMeta;
+
+use crate::sessions::TableContext;
+use crate::storages::system::table::SyncOneBlockSystemTable;
+use crate::storages::system::table::SyncSystemTable;
+use crate::storages::Table;
+
+/// The warnings of the last statement of the session, see `SHOW WARNINGS`.
+pub struct WarningsTable {
+    table_info: TableInfo,
+}
+
+impl SyncSystemTable for WarningsTable {
+    const NAME: &'static str = "system.warnings";
+
+    fn get_table_info(&self) -> &TableInfo {
+        &self.table_info
+    }
+
+    fn get_full_data(&self, ctx: Arc<dyn TableContext>) -> Result<DataBlock> {
+        let warnings = ctx.get_warnings();
+
+        let levels: Vec<&[u8]> = warnings.iter().map(|_| "Warning".as_bytes()).collect();
+        let messages: Vec<&[u8]> = warnings.iter().map(|x| x.as_bytes()).collect();
+
+        Ok(DataBlock::create(self.table_info.schema(), vec![
+            Series::from_data(levels),
+            Series::from_data(messages),
+        ]))
+    }
+}
+
+impl WarningsTable {
+    pub fn create(table_id: u64) -> Arc<dyn Table> {
+        let schema = DataSchemaRefExt::create(vec![
+            DataField::new("level", Vu8::to_data_type()),
+            DataField::new("message", Vu8::to_data_type()),
+        ]);
+
+        let table_info = TableInfo {
+            desc: "'system'.'warnings'".to_string(),
+            name: "warnings".to_string(),
+            ident: TableIdent::new(table_id, 0),
+            meta: TableMeta {
+                schema,
+                engine: "SystemWarnings".to_string(),
+
+                ..Default::default()
+            },
+        };
+
+        SyncOneBlockSystemTable::create(WarningsTable { table_info })
+    }
+}
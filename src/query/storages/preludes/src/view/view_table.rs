@@ -30,6 +30,8 @@ pub struct ViewTable {
 
 pub const VIEW_ENGINE: &str = "VIEW";
 pub const QUERY: &str = "query";
+/// Column names declared by `CREATE VIEW v (a, b) AS ...`, stored as a JSON array.
+pub const COLUMN_NAMES: &str = "column_names";
 
 impl ViewTable {
     pub fn try_create(_ctx: StorageContext, table_info: TableInfo) -> Result<Box<dyn Table>> {